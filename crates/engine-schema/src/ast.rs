@@ -1,6 +1,79 @@
 /// Abstract syntax tree types for the ECS IDL.
 use serde::{Deserialize, Serialize};
 
+// ---------------------------------------------------------------------------
+// Spans
+// ---------------------------------------------------------------------------
+
+/// The source range an AST node was parsed from, for pointing diagnostics
+/// (semantic analysis, codegen errors, an LSP) at the exact `record`/
+/// `field`/`query`/etc. that caused them instead of re-parsing the file.
+/// Lines and columns are 1-based, matching [`crate::lexer::SpannedToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+/// Structural equality for AST nodes that ignores [`Span`] — most tests only
+/// care about what parsed, not where in the source it came from, so filling
+/// in an expected span by hand for every assertion would be noise.
+pub trait SpanInsensitiveEq {
+    fn eq_ignoring_span(&self, other: &Self) -> bool;
+}
+
+impl SpanInsensitiveEq for TypeExpr {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl<T: SpanInsensitiveEq> SpanInsensitiveEq for Vec<T> {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self.iter().zip(other).all(|(a, b)| a.eq_ignoring_span(b))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Constant-value expressions
+// ---------------------------------------------------------------------------
+
+/// A constant-value expression — an enum discriminant, a flag bit, or a
+/// record field default. Built by [`crate::parser::Parser::parse_expr`]'s
+/// precedence-climbing parser; resolving `Ident` references and checking
+/// that the result's type matches where it's used is a later pass's job, not
+/// the parser's.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Expr {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+    /// A reference to another constant (e.g. another enum member's name).
+    Ident(String),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BinaryOp {
+    Or,
+    And,
+    Shl,
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
 // ---------------------------------------------------------------------------
 // Top-level file
 // ---------------------------------------------------------------------------
@@ -88,6 +161,13 @@ pub enum TypeExpr {
 pub struct TypeAlias {
     pub name: String,
     pub target: TypeExpr,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for TypeAlias {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name && self.target == other.target
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -97,7 +177,30 @@ pub struct TypeAlias {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnumDef {
     pub name: String,
-    pub variants: Vec<String>,
+    pub variants: Vec<EnumMember>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for EnumDef {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name && self.variants.eq_ignoring_span(&other.variants)
+    }
+}
+
+/// One `name` or `name = <expr>` member of an [`EnumDef`]. An explicit
+/// discriminant pins the wire value the MessagePack codec encodes for that
+/// member; resolving an omitted one is left to a later pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumMember {
+    pub name: String,
+    pub value: Option<Expr>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for EnumMember {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -108,12 +211,26 @@ pub struct EnumDef {
 pub struct VariantDef {
     pub name: String,
     pub cases: Vec<VariantCase>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for VariantDef {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name && self.cases.eq_ignoring_span(&other.cases)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VariantCase {
     pub name: String,
     pub payload: Option<Vec<TypeExpr>>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for VariantCase {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name && self.payload == other.payload
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -123,7 +240,29 @@ pub struct VariantCase {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlagsDef {
     pub name: String,
-    pub flags: Vec<String>,
+    pub flags: Vec<FlagMember>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for FlagsDef {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name && self.flags.eq_ignoring_span(&other.flags)
+    }
+}
+
+/// One `name` or `name = <expr>` member of a [`FlagsDef`], e.g. the
+/// `terrain = 0x1` in `flags layers { terrain = 0x1, objects = 0x2 }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlagMember {
+    pub name: String,
+    pub value: Option<Expr>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for FlagMember {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -134,12 +273,27 @@ pub struct FlagsDef {
 pub struct RecordDef {
     pub name: String,
     pub fields: Vec<Field>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for RecordDef {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name && self.fields.eq_ignoring_span(&other.fields)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Field {
     pub name: String,
     pub ty: TypeExpr,
+    pub default: Option<Expr>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for Field {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name && self.ty == other.ty && self.default == other.default
+    }
 }
 
 impl RecordDef {
@@ -147,6 +301,38 @@ impl RecordDef {
     pub fn is_tag(&self) -> bool {
         self.fields.is_empty()
     }
+
+    /// FNV-1a 64-bit offset basis. Matches `ComponentTypeId`'s constant so
+    /// the same algorithm is used everywhere a deterministic, language-neutral
+    /// content hash is needed — see `engine_component::ComponentTypeId`.
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+    /// FNV-1a 64-bit prime.
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    /// A content hash of this record's ordered field names and types.
+    ///
+    /// Two `RecordDef`s with the same fields in the same order (by name and
+    /// type, not declaration whitespace) produce the same `layout_version`;
+    /// adding, removing, renaming, or retyping a field changes it. `Schema`
+    /// uses this to tell whether a later `record` declaration for an
+    /// already-known name is the same layout or a new version that should be
+    /// retained alongside it.
+    #[must_use]
+    pub fn layout_version(&self) -> u64 {
+        let mut hash = Self::FNV_OFFSET_BASIS;
+        let mut feed = |bytes: &[u8]| {
+            for &byte in bytes {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(Self::FNV_PRIME);
+            }
+        };
+        for field in &self.fields {
+            feed(field.name.as_bytes());
+            feed(format!("{:?}", field.ty).as_bytes());
+        }
+        hash
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -156,7 +342,14 @@ impl RecordDef {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PhaseDef {
     pub name: String,
-    pub hz: Option<u32>,
+    pub hz: Option<f64>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for PhaseDef {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name && self.hz == other.hz
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -170,6 +363,17 @@ pub struct SystemDef {
     pub phase: Option<String>,
     pub order_after: Vec<String>,
     pub order_before: Vec<String>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for SystemDef {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.queries.eq_ignoring_span(&other.queries)
+            && self.phase == other.phase
+            && self.order_after == other.order_after
+            && self.order_before == other.order_before
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -180,6 +384,20 @@ pub struct QueryDef {
     pub optional: Vec<String>,
     pub exclude: Vec<String>,
     pub changed: Vec<String>,
+    pub added: Vec<String>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for QueryDef {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.read == other.read
+            && self.write == other.write
+            && self.optional == other.optional
+            && self.exclude == other.exclude
+            && self.changed == other.changed
+            && self.added == other.added
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -191,6 +409,15 @@ pub struct WorldDef {
     pub name: String,
     pub includes: Vec<IncludeStmt>,
     pub items: Vec<TopLevelItem>,
+    pub span: Span,
+}
+
+impl SpanInsensitiveEq for WorldDef {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.includes.eq_ignoring_span(&other.includes)
+            && self.items.eq_ignoring_span(&other.items)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -198,3 +425,28 @@ pub struct IncludeStmt {
     pub package: PackageRef,
     pub item: Option<String>,
 }
+
+impl SpanInsensitiveEq for IncludeStmt {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        self.package.namespace == other.package.namespace
+            && self.package.name == other.package.name
+            && self.package.version == other.package.version
+            && self.item == other.item
+    }
+}
+
+impl SpanInsensitiveEq for TopLevelItem {
+    fn eq_ignoring_span(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TopLevelItem::TypeAlias(a), TopLevelItem::TypeAlias(b)) => a.eq_ignoring_span(b),
+            (TopLevelItem::Enum(a), TopLevelItem::Enum(b)) => a.eq_ignoring_span(b),
+            (TopLevelItem::Variant(a), TopLevelItem::Variant(b)) => a.eq_ignoring_span(b),
+            (TopLevelItem::Flags(a), TopLevelItem::Flags(b)) => a.eq_ignoring_span(b),
+            (TopLevelItem::Record(a), TopLevelItem::Record(b)) => a.eq_ignoring_span(b),
+            (TopLevelItem::System(a), TopLevelItem::System(b)) => a.eq_ignoring_span(b),
+            (TopLevelItem::Phase(a), TopLevelItem::Phase(b)) => a.eq_ignoring_span(b),
+            (TopLevelItem::World(a), TopLevelItem::World(b)) => a.eq_ignoring_span(b),
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,402 @@
+//! Binary runtime schema descriptor.
+//!
+//! `Schema::to_json` emits field types as `format!("{:?}", f.ty)`, a lossy
+//! debug string meant for human/editor consumption — a runtime can't
+//! reliably parse it back into real layout information for allocating
+//! archetype columns or validating `ComponentShard` bytes. [`Schema::to_descriptor`]
+//! produces a structured [`SchemaDescriptor`] instead: stable numeric
+//! [`ComponentTypeId`]s assigned deterministically per record, explicit
+//! per-field encodings, and a computed fixed byte size where the record's
+//! layout allows one. It's serialized via MessagePack so the coordinator can
+//! hand it to systems at registration time, giving them a machine-readable
+//! contract to check their compiled-in layout against.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ast::{RecordDef, TypeExpr};
+use crate::schema::Schema;
+
+/// Current wire version of [`SchemaDescriptor`] itself — bumped whenever the
+/// descriptor's own shape changes, independent of any individual record's
+/// `layout_version`.
+pub const DESCRIPTOR_VERSION: u32 = 1;
+
+/// A numeric identifier for a component type.
+///
+/// Derived from the record's name with the same FNV-1a 64-bit algorithm as
+/// `engine_component::ComponentTypeId`, so a system's compiled-in type ID and
+/// the coordinator's descriptor agree without either crate depending on the
+/// other — see [`RecordDef::layout_version`] for the same rationale applied
+/// to layout hashing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct ComponentTypeId(pub u64);
+
+impl ComponentTypeId {
+    /// FNV-1a 64-bit offset basis.
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+    /// FNV-1a 64-bit prime.
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    /// Compute the type ID for a record named `name`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Self {
+        let mut hash = Self::FNV_OFFSET_BASIS;
+        for &byte in name.as_bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(Self::FNV_PRIME);
+        }
+        Self(hash)
+    }
+}
+
+/// A primitive field's wire encoding, and its fixed on-wire byte size.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PrimitiveEncoding {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    String,
+    Bytes,
+}
+
+impl PrimitiveEncoding {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "bool" => Self::Bool,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "f32" => Self::F32,
+            "f64" => Self::F64,
+            "string" => Self::String,
+            "bytes" => Self::Bytes,
+            _ => return None,
+        })
+    }
+
+    /// The fixed on-wire byte size, or `None` for a variable-length
+    /// primitive (`string`/`bytes`).
+    #[must_use]
+    pub fn fixed_size(self) -> Option<usize> {
+        match self {
+            Self::Bool | Self::U8 | Self::I8 => Some(1),
+            Self::U16 | Self::I16 => Some(2),
+            Self::U32 | Self::I32 | Self::F32 => Some(4),
+            Self::U64 | Self::I64 | Self::F64 => Some(8),
+            Self::String | Self::Bytes => None,
+        }
+    }
+}
+
+/// A field's wire encoding — explicit rather than inferred from a debug
+/// string, so a runtime can act on it directly instead of re-parsing `.ecs`
+/// source.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum FieldEncoding {
+    Primitive(PrimitiveEncoding),
+    /// A named type (record, enum, variant, flags, or alias). Carries its
+    /// own `type_id` so a runtime can cross-reference it against the rest
+    /// of the descriptor's `records` without a name lookup.
+    Named { name: String, type_id: ComponentTypeId },
+    List(Box<FieldEncoding>),
+    Option(Box<FieldEncoding>),
+    Set(Box<FieldEncoding>),
+    Map(Box<FieldEncoding>, Box<FieldEncoding>),
+    Tuple(Vec<FieldEncoding>),
+}
+
+impl FieldEncoding {
+    fn from_type_expr(ty: &TypeExpr) -> Self {
+        match ty {
+            TypeExpr::Primitive(name) => match PrimitiveEncoding::from_name(name) {
+                Some(encoding) => Self::Primitive(encoding),
+                // Not reachable for a schema that passed `Schema::validate`,
+                // but fall back to treating it as a named reference rather
+                // than panicking on an unrecognized primitive spelling.
+                None => Self::Named {
+                    name: name.clone(),
+                    type_id: ComponentTypeId::from_name(name),
+                },
+            },
+            TypeExpr::Named(name) => Self::Named {
+                name: name.clone(),
+                type_id: ComponentTypeId::from_name(name),
+            },
+            TypeExpr::List(inner) => Self::List(Box::new(Self::from_type_expr(inner))),
+            TypeExpr::Option(inner) => Self::Option(Box::new(Self::from_type_expr(inner))),
+            TypeExpr::Set(inner) => Self::Set(Box::new(Self::from_type_expr(inner))),
+            TypeExpr::Map(k, v) => Self::Map(
+                Box::new(Self::from_type_expr(k)),
+                Box::new(Self::from_type_expr(v)),
+            ),
+            TypeExpr::Tuple(types) => {
+                Self::Tuple(types.iter().map(Self::from_type_expr).collect())
+            }
+        }
+    }
+
+    /// The fixed on-wire byte size of a value with this encoding, or `None`
+    /// if it's variable-length (a `string`/`bytes` primitive, or anything
+    /// built from one).
+    fn fixed_size(&self) -> Option<usize> {
+        match self {
+            Self::Primitive(p) => p.fixed_size(),
+            // A named type's layout lives on its own `RecordDescriptor`;
+            // without re-resolving it here, treat it as variable-length
+            // rather than claiming a size we haven't verified.
+            Self::Named { .. } => None,
+            Self::List(_) | Self::Set(_) | Self::Map(_, _) | Self::Option(_) => None,
+            Self::Tuple(elements) => {
+                let mut total = 0;
+                for element in elements {
+                    total += element.fixed_size()?;
+                }
+                Some(total)
+            }
+        }
+    }
+}
+
+/// One field's descriptor: its name and wire encoding.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldDescriptor {
+    pub name: String,
+    pub encoding: FieldEncoding,
+}
+
+/// A record's full binary descriptor.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordDescriptor {
+    pub name: String,
+    /// Matches `engine_component::ComponentTypeId::from_name(&name)`.
+    pub type_id: ComponentTypeId,
+    /// Matches `RecordDef::layout_version` for this exact field layout.
+    pub layout_version: u64,
+    pub is_tag: bool,
+    pub fields: Vec<FieldDescriptor>,
+    /// The record's total byte size if every field is fixed-layout, `None`
+    /// if any field is variable-length (directly or through a collection).
+    pub fixed_size: Option<usize>,
+}
+
+impl RecordDescriptor {
+    fn from_record(record: &RecordDef) -> Self {
+        let fields: Vec<FieldDescriptor> = record
+            .fields
+            .iter()
+            .map(|f| FieldDescriptor {
+                name: f.name.clone(),
+                encoding: FieldEncoding::from_type_expr(&f.ty),
+            })
+            .collect();
+
+        let fixed_size = fields
+            .iter()
+            .try_fold(0usize, |total, f| Some(total + f.encoding.fixed_size()?));
+
+        Self {
+            name: record.name.clone(),
+            type_id: ComponentTypeId::from_name(&record.name),
+            layout_version: record.layout_version(),
+            is_tag: record.is_tag(),
+            fields,
+            fixed_size,
+        }
+    }
+}
+
+/// The full schema's binary descriptor — the current layout of every known
+/// record, plus a version for the descriptor shape itself.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SchemaDescriptor {
+    pub version: u32,
+    pub records: Vec<RecordDescriptor>,
+}
+
+impl SchemaDescriptor {
+    /// Serialize to MessagePack bytes for transport at system registration
+    /// time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if encoding fails (e.g. an unsupported type nested
+    /// too deeply for the encoder's recursion limit).
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec_named(self)
+    }
+
+    /// Decode a descriptor previously produced by [`Self::to_msgpack`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` isn't a valid MessagePack encoding of a
+    /// `SchemaDescriptor`.
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
+}
+
+impl Schema {
+    /// Build the structured, versioned [`SchemaDescriptor`] for this
+    /// schema's current record layouts.
+    ///
+    /// Unlike [`Schema::to_json`], every field carries an explicit
+    /// [`FieldEncoding`] rather than a debug-formatted type string, and each
+    /// record carries a deterministic [`ComponentTypeId`] plus a computed
+    /// `fixed_size` where its layout allows one. The JSON form remains
+    /// available for human/editor consumption.
+    #[must_use]
+    pub fn to_descriptor(&self) -> SchemaDescriptor {
+        let mut records: Vec<RecordDescriptor> = self
+            .records
+            .values()
+            .filter_map(|versions| versions.last())
+            .map(RecordDescriptor::from_record)
+            .collect();
+        records.sort_by(|a, b| a.name.cmp(&b.name));
+
+        SchemaDescriptor {
+            version: DESCRIPTOR_VERSION,
+            records,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema_with(source: &str) -> Schema {
+        let mut schema = Schema::new();
+        schema.load_source(source).unwrap();
+        schema
+    }
+
+    #[test]
+    fn test_descriptor_assigns_deterministic_type_ids() {
+        let schema = schema_with(
+            r#"
+            package test:game@0.1.0
+
+            record velocity {
+                x: f32,
+                y: f32,
+            }
+        "#,
+        );
+        let descriptor = schema.to_descriptor();
+        let record = descriptor.records.iter().find(|r| r.name == "velocity").unwrap();
+        assert_eq!(record.type_id, ComponentTypeId::from_name("velocity"));
+        assert_eq!(record.layout_version, schema.get_record("velocity").unwrap().layout_version());
+    }
+
+    #[test]
+    fn test_descriptor_computes_fixed_size_for_all_primitive_record() {
+        let schema = schema_with(
+            r#"
+            package test:game@0.1.0
+
+            record velocity {
+                x: f32,
+                y: f32,
+                z: f32,
+            }
+        "#,
+        );
+        let descriptor = schema.to_descriptor();
+        let record = descriptor.records.iter().find(|r| r.name == "velocity").unwrap();
+        assert_eq!(record.fixed_size, Some(12));
+    }
+
+    #[test]
+    fn test_descriptor_fixed_size_is_none_with_variable_length_field() {
+        let schema = schema_with(
+            r#"
+            package test:game@0.1.0
+
+            record label {
+                text: string,
+            }
+        "#,
+        );
+        let descriptor = schema.to_descriptor();
+        let record = descriptor.records.iter().find(|r| r.name == "label").unwrap();
+        assert_eq!(record.fixed_size, None);
+    }
+
+    #[test]
+    fn test_descriptor_tag_record_has_zero_fixed_size() {
+        let schema = schema_with(
+            r#"
+            package test:game@0.1.0
+
+            record frozen {}
+        "#,
+        );
+        let descriptor = schema.to_descriptor();
+        let record = descriptor.records.iter().find(|r| r.name == "frozen").unwrap();
+        assert!(record.is_tag);
+        assert_eq!(record.fixed_size, Some(0));
+    }
+
+    #[test]
+    fn test_descriptor_roundtrips_through_msgpack() {
+        let schema = schema_with(
+            r#"
+            package test:game@0.1.0
+
+            record velocity {
+                x: f32,
+                y: f32,
+            }
+
+            record tag_only {}
+        "#,
+        );
+        let descriptor = schema.to_descriptor();
+        let bytes = descriptor.to_msgpack().unwrap();
+        let decoded = SchemaDescriptor::from_msgpack(&bytes).unwrap();
+        assert_eq!(decoded, descriptor);
+    }
+
+    #[test]
+    fn test_descriptor_named_field_carries_its_own_type_id() {
+        let schema = schema_with(
+            r#"
+            package test:game@0.1.0
+
+            record transform {
+                x: f32,
+            }
+
+            record parent {
+                of: transform,
+            }
+        "#,
+        );
+        let descriptor = schema.to_descriptor();
+        let record = descriptor.records.iter().find(|r| r.name == "parent").unwrap();
+        let field = &record.fields[0];
+        match &field.encoding {
+            FieldEncoding::Named { name, type_id } => {
+                assert_eq!(name, "transform");
+                assert_eq!(*type_id, ComponentTypeId::from_name("transform"));
+            }
+            other => panic!("expected a named field encoding, got {other:?}"),
+        }
+    }
+}
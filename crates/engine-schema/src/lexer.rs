@@ -0,0 +1,655 @@
+//! Tokenizer for the ECS IDL.
+//!
+//! Turns source text into a flat [`Vec<SpannedToken>`], terminated by
+//! [`Token::Eof`], for [`crate::parser::Parser`] to consume. Identifiers and
+//! keywords share the same scanner: a word is classified as a keyword only
+//! if it exactly matches one of the fixed keyword strings below, so a name
+//! that merely contains a keyword (e.g. `records`, `added_at`) stays an
+//! identifier. A name equal to a keyword lexes as that keyword; the parser
+//! accepts only some keywords (`read`, `write`, `hz`, ...) where an
+//! identifier is expected.
+
+use std::fmt;
+
+/// A lexical token.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    // -- Literals --
+    Ident(String),
+    Integer(u64),
+    Float(f64),
+    Str(String),
+    True,
+    False,
+    /// A `major.minor.patch` literal, e.g. the `0.1.0` in `package foo:bar@0.1.0`.
+    Version(u64, u64, u64),
+
+    // -- Punctuation --
+    Colon,
+    Comma,
+    Dot,
+    Eq,
+    At,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    LAngle,
+    RAngle,
+
+    // -- Operators (constant-value expressions) --
+    Pipe,
+    Amp,
+    Shl,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+
+    // -- Keywords --
+    Package,
+    Use,
+    As,
+    Type,
+    Enum,
+    Variant,
+    Flags,
+    Record,
+    Phase,
+    System,
+    World,
+    Include,
+    Query,
+    Read,
+    Write,
+    Optional,
+    Exclude,
+    Changed,
+    Added,
+    OrderAfter,
+    OrderBefore,
+    Hz,
+
+    // -- Type-expression keywords --
+    List,
+    OptionKw,
+    Set,
+    Map,
+    Tuple,
+
+    Eof,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(s) => write!(f, "{s}"),
+            Token::Integer(n) => write!(f, "{n}"),
+            Token::Float(n) => write!(f, "{n}"),
+            Token::Str(s) => write!(f, "{s:?}"),
+            Token::True => write!(f, "true"),
+            Token::False => write!(f, "false"),
+            Token::Version(major, minor, patch) => write!(f, "{major}.{minor}.{patch}"),
+            Token::Colon => write!(f, ":"),
+            Token::Comma => write!(f, ","),
+            Token::Dot => write!(f, "."),
+            Token::Eq => write!(f, "="),
+            Token::At => write!(f, "@"),
+            Token::LBrace => write!(f, "{{"),
+            Token::RBrace => write!(f, "}}"),
+            Token::LBracket => write!(f, "["),
+            Token::RBracket => write!(f, "]"),
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+            Token::LAngle => write!(f, "<"),
+            Token::RAngle => write!(f, ">"),
+            Token::Pipe => write!(f, "|"),
+            Token::Amp => write!(f, "&"),
+            Token::Shl => write!(f, "<<"),
+            Token::Plus => write!(f, "+"),
+            Token::Minus => write!(f, "-"),
+            Token::Star => write!(f, "*"),
+            Token::Slash => write!(f, "/"),
+            Token::Package => write!(f, "package"),
+            Token::Use => write!(f, "use"),
+            Token::As => write!(f, "as"),
+            Token::Type => write!(f, "type"),
+            Token::Enum => write!(f, "enum"),
+            Token::Variant => write!(f, "variant"),
+            Token::Flags => write!(f, "flags"),
+            Token::Record => write!(f, "record"),
+            Token::Phase => write!(f, "phase"),
+            Token::System => write!(f, "system"),
+            Token::World => write!(f, "world"),
+            Token::Include => write!(f, "include"),
+            Token::Query => write!(f, "query"),
+            Token::Read => write!(f, "read"),
+            Token::Write => write!(f, "write"),
+            Token::Optional => write!(f, "optional"),
+            Token::Exclude => write!(f, "exclude"),
+            Token::Changed => write!(f, "changed"),
+            Token::Added => write!(f, "added"),
+            Token::OrderAfter => write!(f, "order_after"),
+            Token::OrderBefore => write!(f, "order_before"),
+            Token::Hz => write!(f, "hz"),
+            Token::List => write!(f, "list"),
+            Token::OptionKw => write!(f, "option"),
+            Token::Set => write!(f, "set"),
+            Token::Map => write!(f, "map"),
+            Token::Tuple => write!(f, "tuple"),
+            Token::Eof => write!(f, "<eof>"),
+        }
+    }
+}
+
+/// A token tagged with the line/column it started at, for error reporting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedToken {
+    pub token: Token,
+    pub line: usize,
+    pub col: usize,
+}
+
+/// An error produced while scanning source text into tokens.
+#[derive(Debug, Clone)]
+pub struct LexError {
+    pub line: usize,
+    pub col: usize,
+    pub message: String,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.col, self.message)
+    }
+}
+
+impl std::error::Error for LexError {}
+
+/// Maps a scanned word to its keyword token, or `None` if it's a plain
+/// identifier.
+fn keyword(word: &str) -> Option<Token> {
+    Some(match word {
+        "package" => Token::Package,
+        "use" => Token::Use,
+        "as" => Token::As,
+        "type" => Token::Type,
+        "enum" => Token::Enum,
+        "variant" => Token::Variant,
+        "flags" => Token::Flags,
+        "record" => Token::Record,
+        "phase" => Token::Phase,
+        "system" => Token::System,
+        "world" => Token::World,
+        "include" => Token::Include,
+        "query" => Token::Query,
+        "read" => Token::Read,
+        "write" => Token::Write,
+        "optional" => Token::Optional,
+        "exclude" => Token::Exclude,
+        "changed" => Token::Changed,
+        "added" => Token::Added,
+        "order_after" => Token::OrderAfter,
+        "order_before" => Token::OrderBefore,
+        "hz" => Token::Hz,
+        "list" => Token::List,
+        "option" => Token::OptionKw,
+        "set" => Token::Set,
+        "map" => Token::Map,
+        "tuple" => Token::Tuple,
+        "true" => Token::True,
+        "false" => Token::False,
+        _ => return None,
+    })
+}
+
+/// Scans IDL source text into a token stream.
+pub struct Lexer<'a> {
+    input: &'a [u8],
+    pos: usize,
+    line: usize,
+    col: usize,
+}
+
+impl<'a> Lexer<'a> {
+    #[must_use]
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input: input.as_bytes(),
+            pos: 0,
+            line: 1,
+            col: 1,
+        }
+    }
+
+    /// Scans the entire input into a token stream, ending with [`Token::Eof`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LexError`] on an unterminated or malformed token (e.g. an
+    /// unrecognised character).
+    pub fn tokenize(&mut self) -> Result<Vec<SpannedToken>, LexError> {
+        let mut tokens = Vec::new();
+        loop {
+            self.skip_whitespace_and_comments();
+            let (line, col) = (self.line, self.col);
+            let Some(c) = self.peek_char() else {
+                tokens.push(SpannedToken {
+                    token: Token::Eof,
+                    line,
+                    col,
+                });
+                break;
+            };
+
+            let token = if c.is_ascii_digit() {
+                self.scan_number()?
+            } else if c == '_' || c.is_ascii_alphabetic() {
+                self.scan_word()
+            } else if c == '"' {
+                self.scan_string()?
+            } else {
+                self.scan_punct(c)?
+            };
+
+            tokens.push(SpannedToken { token, line, col });
+        }
+        Ok(tokens)
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.input.get(self.pos).map(|&b| b as char)
+    }
+
+    fn advance_char(&mut self) -> Option<char> {
+        let c = self.peek_char()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            match self.peek_char() {
+                Some(c) if c.is_whitespace() => {
+                    self.advance_char();
+                }
+                Some('/') if self.input.get(self.pos + 1) == Some(&b'/') => {
+                    while let Some(c) = self.peek_char() {
+                        if c == '\n' {
+                            break;
+                        }
+                        self.advance_char();
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Scans a numeric literal: a bare digit run is [`Token::Integer`]; one
+    /// `.`-separated digit run beyond that is [`Token::Float`]; two are a
+    /// `major.minor.patch` [`Token::Version`]. Scanning the dots here (rather
+    /// than leaving them as separate [`Token::Dot`]s) keeps `hz: 59.94` and
+    /// `@0.1.0` literals atomic, with precise span information, instead of
+    /// making the parser reassemble them from an ambiguous run of dots.
+    fn scan_number(&mut self) -> Result<Token, LexError> {
+        let (line, col) = (self.line, self.col);
+        if self.peek_char() == Some('0')
+            && matches!(self.input.get(self.pos + 1), Some(b'x') | Some(b'X'))
+        {
+            return self.scan_hex_integer(line, col);
+        }
+        let major = self.scan_digit_run();
+        if !self.at_fractional_dot() {
+            return self.parse_digits::<u64>(&major, "integer").map(Token::Integer);
+        }
+        self.advance_char(); // the '.'
+        let minor = self.scan_digit_run();
+        if !self.at_fractional_dot() {
+            let text = format!("{major}.{minor}");
+            return text
+                .parse::<f64>()
+                .map(Token::Float)
+                .map_err(|_| LexError {
+                    line,
+                    col,
+                    message: format!("invalid float literal '{text}'"),
+                });
+        }
+        self.advance_char(); // the second '.'
+        let patch = self.scan_digit_run();
+        let major = self.parse_digits::<u64>(&major, "version")?;
+        let minor = self.parse_digits::<u64>(&minor, "version")?;
+        let patch = self.parse_digits::<u64>(&patch, "version")?;
+        Ok(Token::Version(major, minor, patch))
+    }
+
+    /// Scans a `0x`/`0X`-prefixed hex integer literal, for bit-flag-friendly
+    /// constant expressions like `flags layers { terrain = 0x1 }`.
+    fn scan_hex_integer(&mut self, line: usize, col: usize) -> Result<Token, LexError> {
+        self.advance_char(); // '0'
+        self.advance_char(); // 'x'/'X'
+        let start = self.pos;
+        while self.peek_char().is_some_and(|c| c.is_ascii_hexdigit()) {
+            self.advance_char();
+        }
+        let digits = std::str::from_utf8(&self.input[start..self.pos]).unwrap();
+        if digits.is_empty() {
+            return Err(LexError {
+                line,
+                col,
+                message: "expected hex digits after '0x'".to_string(),
+            });
+        }
+        u64::from_str_radix(digits, 16)
+            .map(Token::Integer)
+            .map_err(|_| LexError {
+                line,
+                col,
+                message: format!("invalid hex literal '0x{digits}'"),
+            })
+    }
+
+    /// Scans a run of ASCII digits and returns it as a string slice.
+    fn scan_digit_run(&mut self) -> String {
+        let start = self.pos;
+        while self.peek_char().is_some_and(|c| c.is_ascii_digit()) {
+            self.advance_char();
+        }
+        std::str::from_utf8(&self.input[start..self.pos])
+            .unwrap()
+            .to_string()
+    }
+
+    /// Whether the lexer is sitting on a `.` that continues a numeric
+    /// literal, i.e. followed immediately by another digit (as opposed to a
+    /// standalone `Token::Dot`, e.g. the field-access dot in `pkg.Item`).
+    fn at_fractional_dot(&self) -> bool {
+        self.peek_char() == Some('.')
+            && self.input.get(self.pos + 1).is_some_and(u8::is_ascii_digit)
+    }
+
+    fn parse_digits<T: std::str::FromStr>(&self, text: &str, kind: &str) -> Result<T, LexError> {
+        let (line, col) = (self.line, self.col);
+        text.parse::<T>().map_err(|_| LexError {
+            line,
+            col,
+            message: format!("invalid {kind} literal '{text}'"),
+        })
+    }
+
+    fn scan_word(&mut self) -> Token {
+        let start = self.pos;
+        while self
+            .peek_char()
+            .is_some_and(|c| c == '_' || c.is_ascii_alphanumeric())
+        {
+            self.advance_char();
+        }
+        let word = std::str::from_utf8(&self.input[start..self.pos]).unwrap();
+        keyword(word).unwrap_or_else(|| Token::Ident(word.to_string()))
+    }
+
+    /// Scans a `"..."` string literal. Supports the common backslash escapes
+    /// (`\"`, `\\`, `\n`, `\t`, `\r`); any other escape is an error rather
+    /// than silently passing the backslash through.
+    fn scan_string(&mut self) -> Result<Token, LexError> {
+        let (line, col) = (self.line, self.col);
+        self.advance_char(); // opening quote
+        let mut value = String::new();
+        loop {
+            match self.advance_char() {
+                Some('"') => return Ok(Token::Str(value)),
+                Some('\\') => match self.advance_char() {
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('r') => value.push('\r'),
+                    other => {
+                        return Err(LexError {
+                            line,
+                            col,
+                            message: format!("invalid escape '\\{}' in string literal", other.unwrap_or(' ')),
+                        });
+                    }
+                },
+                Some(c) => value.push(c),
+                None => {
+                    return Err(LexError {
+                        line,
+                        col,
+                        message: "unterminated string literal".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn scan_punct(&mut self, c: char) -> Result<Token, LexError> {
+        let (line, col) = (self.line, self.col);
+        self.advance_char();
+        Ok(match c {
+            ':' => Token::Colon,
+            ',' => Token::Comma,
+            '.' => Token::Dot,
+            '=' => Token::Eq,
+            '@' => Token::At,
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '<' => {
+                if self.peek_char() == Some('<') {
+                    self.advance_char();
+                    Token::Shl
+                } else {
+                    Token::LAngle
+                }
+            }
+            '>' => Token::RAngle,
+            '|' => Token::Pipe,
+            '&' => Token::Amp,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            other => {
+                return Err(LexError {
+                    line,
+                    col,
+                    message: format!("unexpected character '{other}'"),
+                });
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input: &str) -> Vec<Token> {
+        Lexer::new(input)
+            .tokenize()
+            .unwrap()
+            .into_iter()
+            .map(|t| t.token)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_package_decl() {
+        assert_eq!(
+            tokens("package test:minimal@0.1.0"),
+            vec![
+                Token::Package,
+                Token::Ident("test".into()),
+                Token::Colon,
+                Token::Ident("minimal".into()),
+                Token::At,
+                Token::Version(0, 1, 0),
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_plain_integer() {
+        assert_eq!(tokens("60"), vec![Token::Integer(60), Token::Eof]);
+    }
+
+    #[test]
+    fn test_tokenize_float_literal() {
+        assert_eq!(
+            tokens("hz: 59.94"),
+            vec![Token::Hz, Token::Colon, Token::Float(59.94), Token::Eof]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_version_literal() {
+        assert_eq!(
+            tokens("engine:std@12.34.5"),
+            vec![
+                Token::Ident("engine".into()),
+                Token::Colon,
+                Token::Ident("std".into()),
+                Token::At,
+                Token::Version(12, 34, 5),
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_dot_after_integer_is_not_fractional() {
+        // A `.` not followed by a digit is a plain field-access dot, not the
+        // start of a float or version literal.
+        assert_eq!(
+            tokens("1.foo"),
+            vec![
+                Token::Integer(1),
+                Token::Dot,
+                Token::Ident("foo".into()),
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_keywords_vs_idents() {
+        assert_eq!(
+            tokens("read write optional exclude changed added velocity"),
+            vec![
+                Token::Read,
+                Token::Write,
+                Token::Optional,
+                Token::Exclude,
+                Token::Changed,
+                Token::Added,
+                Token::Ident("velocity".into()),
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_skips_line_comments() {
+        assert_eq!(
+            tokens("record foo {} // trailing comment\nrecord bar {}"),
+            vec![
+                Token::Record,
+                Token::Ident("foo".into()),
+                Token::LBrace,
+                Token::RBrace,
+                Token::Record,
+                Token::Ident("bar".into()),
+                Token::LBrace,
+                Token::RBrace,
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_generic_angle_brackets() {
+        assert_eq!(
+            tokens("list<u32>"),
+            vec![
+                Token::List,
+                Token::LAngle,
+                Token::Ident("u32".into()),
+                Token::RAngle,
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_reports_line_and_col_on_error() {
+        let err = Lexer::new("record foo {\n  #bad\n}").tokenize().unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.col, 3);
+    }
+
+    #[test]
+    fn test_spanned_token_tracks_position() {
+        let spanned = Lexer::new("  record").tokenize().unwrap();
+        assert_eq!(spanned[0].line, 1);
+        assert_eq!(spanned[0].col, 3);
+    }
+
+    #[test]
+    fn test_tokenize_constant_expr_operators() {
+        assert_eq!(
+            tokens("0x1 | 0x2 & 1 << 3 + 4 - 5 * 6 / 7"),
+            vec![
+                Token::Integer(1),
+                Token::Pipe,
+                Token::Integer(2),
+                Token::Amp,
+                Token::Integer(1),
+                Token::Shl,
+                Token::Integer(3),
+                Token::Plus,
+                Token::Integer(4),
+                Token::Minus,
+                Token::Integer(5),
+                Token::Star,
+                Token::Integer(6),
+                Token::Slash,
+                Token::Integer(7),
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_hex_integer_literal() {
+        assert_eq!(tokens("0x2a"), vec![Token::Integer(42), Token::Eof]);
+    }
+
+    #[test]
+    fn test_tokenize_string_literal_with_escapes() {
+        assert_eq!(
+            tokens(r#""hello\nworld""#),
+            vec![Token::Str("hello\nworld".to_string()), Token::Eof]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_bool_keywords() {
+        assert_eq!(tokens("true false"), vec![Token::True, Token::False, Token::Eof]);
+    }
+}
@@ -1,7 +1,14 @@
 pub mod ast;
+pub mod descriptor;
 pub mod lexer;
+pub mod migration;
 pub mod parser;
 pub mod schema;
 
 pub use ast::*;
+pub use descriptor::{
+    ComponentTypeId, FieldDescriptor, FieldEncoding, PrimitiveEncoding, RecordDescriptor,
+    SchemaDescriptor, DESCRIPTOR_VERSION,
+};
+pub use migration::{MigrationRegistry, RecordMigration};
 pub use schema::{Schema, SchemaError};
@@ -0,0 +1,142 @@
+//! Declared migrations between `RecordDef` layout versions.
+//!
+//! Adding or removing a field is already handled generically wherever a
+//! record is decoded against the current schema (missing fields get a
+//! type-appropriate default, unknown fields are dropped) — see
+//! `engine_net::schema_codec::decode_record`. A rename can't be inferred
+//! that way, since it's indistinguishable from "one field removed, an
+//! unrelated field added" without more information, so callers declare
+//! renames explicitly via [`MigrationRegistry`].
+
+use std::collections::{HashMap, HashSet};
+
+/// A single declared step from one layout version of a record to another.
+#[derive(Debug, Clone, Default)]
+pub struct RecordMigration {
+    /// The `layout_version` this step starts from.
+    pub from_version: u64,
+    /// The `layout_version` this step produces.
+    pub to_version: u64,
+    /// Fields renamed between `from_version` and `to_version`, as
+    /// `(old_name, new_name)` pairs.
+    pub renamed_fields: Vec<(String, String)>,
+}
+
+/// A registry of declared [`RecordMigration`] steps, keyed by record name.
+///
+/// Steps don't need to cover every version jump directly — [`Self::path`]
+/// chains consecutive steps together, so a record that moved through several
+/// layouts only needs each hop declared once.
+#[derive(Debug, Clone, Default)]
+pub struct MigrationRegistry {
+    migrations: HashMap<String, Vec<RecordMigration>>,
+}
+
+impl MigrationRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a migration step for `record_name`.
+    pub fn register(&mut self, record_name: &str, migration: RecordMigration) {
+        self.migrations
+            .entry(record_name.to_string())
+            .or_default()
+            .push(migration);
+    }
+
+    /// Find a chain of steps taking `record_name` from `from` to `to`,
+    /// hopping through intermediate versions if there's no single direct
+    /// step. Returns an empty chain if `from == to`, and `None` if no chain
+    /// connects them.
+    #[must_use]
+    pub fn path(&self, record_name: &str, from: u64, to: u64) -> Option<Vec<&RecordMigration>> {
+        if from == to {
+            return Some(Vec::new());
+        }
+        let steps = self.migrations.get(record_name)?;
+        let mut visited = HashSet::new();
+        Self::search(steps, from, to, &mut visited)
+    }
+
+    /// Whether a migration chain connects `from` to `to` for `record_name`.
+    #[must_use]
+    pub fn has_path(&self, record_name: &str, from: u64, to: u64) -> bool {
+        self.path(record_name, from, to).is_some()
+    }
+
+    fn search<'a>(
+        steps: &'a [RecordMigration],
+        from: u64,
+        to: u64,
+        visited: &mut HashSet<u64>,
+    ) -> Option<Vec<&'a RecordMigration>> {
+        if !visited.insert(from) {
+            return None;
+        }
+        for step in steps {
+            if step.from_version != from {
+                continue;
+            }
+            if step.to_version == to {
+                return Some(vec![step]);
+            }
+            if let Some(mut rest) = Self::search(steps, step.to_version, to, visited) {
+                let mut chain = vec![step];
+                chain.append(&mut rest);
+                return Some(chain);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(from: u64, to: u64) -> RecordMigration {
+        RecordMigration {
+            from_version: from,
+            to_version: to,
+            renamed_fields: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_path_finds_direct_step() {
+        let mut reg = MigrationRegistry::new();
+        reg.register("velocity", step(1, 2));
+        assert!(reg.has_path("velocity", 1, 2));
+    }
+
+    #[test]
+    fn test_path_chains_multiple_steps() {
+        let mut reg = MigrationRegistry::new();
+        reg.register("velocity", step(1, 2));
+        reg.register("velocity", step(2, 3));
+        let path = reg.path("velocity", 1, 3).unwrap();
+        assert_eq!(path.len(), 2);
+    }
+
+    #[test]
+    fn test_path_same_version_is_empty_chain() {
+        let reg = MigrationRegistry::new();
+        assert_eq!(reg.path("velocity", 5, 5).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_path_returns_none_when_unreachable() {
+        let mut reg = MigrationRegistry::new();
+        reg.register("velocity", step(1, 2));
+        assert!(reg.path("velocity", 1, 99).is_none());
+    }
+
+    #[test]
+    fn test_path_ignores_unrelated_record() {
+        let mut reg = MigrationRegistry::new();
+        reg.register("velocity", step(1, 2));
+        assert!(reg.path("health", 1, 2).is_none());
+    }
+}
@@ -32,6 +32,17 @@ impl From<LexError> for ParseError {
     }
 }
 
+/// The result of [`Parser::parse_incremental`] — distinguishes input that's
+/// merely cut off mid-construct (an interactive editor or REPL still typing
+/// a multi-line `record { ... }`) from input that's actually malformed, so a
+/// caller can tell "keep prompting for more" apart from "report this error".
+#[derive(Debug)]
+pub enum ParseOutcome {
+    Complete(File),
+    Incomplete,
+    Error(ParseError),
+}
+
 // ---------------------------------------------------------------------------
 // Parser
 // ---------------------------------------------------------------------------
@@ -39,16 +50,141 @@ impl From<LexError> for ParseError {
 pub struct Parser {
     tokens: Vec<SpannedToken>,
     pos: usize,
+    /// When set, a failing `parse_top_level_item`, `parse_query` field, or
+    /// record field records its error in `errors` and skips to a
+    /// synchronizing point instead of returning `Err`, so
+    /// [`Parser::parse_recovering`] can keep going past it.
+    recovering: bool,
+    errors: Vec<ParseError>,
 }
 
 impl Parser {
     pub fn parse(input: &str) -> Result<File, ParseError> {
         let mut lexer = Lexer::new(input);
         let tokens = lexer.tokenize()?;
-        let mut parser = Self { tokens, pos: 0 };
+        let mut parser = Self::from_tokens(tokens);
         parser.parse_file()
     }
 
+    /// Like [`Self::parse`], but instead of aborting on the first
+    /// [`ParseError`] it records the error, skips tokens in panic-mode
+    /// until a synchronizing point (a top-level keyword, or the closing
+    /// `}` of the construct that failed), and resumes — so a file with
+    /// several independent mistakes reports all of them in one pass.
+    ///
+    /// Returns a best-effort `File` built from whatever parsed
+    /// successfully alongside every error encountered, or `None` if even
+    /// the lexer or the leading package declaration — which everything
+    /// else depends on — couldn't be parsed.
+    #[must_use]
+    pub fn parse_recovering(input: &str) -> (Option<File>, Vec<ParseError>) {
+        let mut lexer = Lexer::new(input);
+        let tokens = match lexer.tokenize() {
+            Ok(tokens) => tokens,
+            Err(e) => return (None, vec![e.into()]),
+        };
+        let mut parser = Self::from_tokens(tokens);
+        parser.recovering = true;
+
+        let package = match parser.parse_package_decl() {
+            Ok(package) => package,
+            Err(e) => {
+                parser.errors.push(e);
+                return (None, parser.errors);
+            }
+        };
+
+        let mut imports = Vec::new();
+        while parser.at(&Token::Use) {
+            match parser.parse_import() {
+                Ok(import) => imports.push(import),
+                Err(e) => {
+                    parser.errors.push(e);
+                    parser.recover_to_sync_point();
+                }
+            }
+        }
+
+        let mut items = Vec::new();
+        while !parser.at(&Token::Eof) {
+            match parser.parse_top_level_item() {
+                Ok(item) => items.push(item),
+                Err(e) => {
+                    parser.errors.push(e);
+                    parser.recover_to_sync_point();
+                }
+            }
+        }
+
+        let errors = parser.errors;
+        (
+            Some(File {
+                package,
+                imports,
+                items,
+            }),
+            errors,
+        )
+    }
+
+    /// Parses `input` for a streaming/REPL caller that feeds the IDL
+    /// line-by-line: distinguishes text that's syntactically incomplete
+    /// (still inside an open `{`/`[`/`(`/`<`, or ending right after a `:`/
+    /// `,` clearly expecting a continuation) from an actual [`ParseError`],
+    /// so the caller knows to keep prompting for more input rather than
+    /// surface a confusing "expected X, got <eof>".
+    #[must_use]
+    pub fn parse_incremental(input: &str) -> ParseOutcome {
+        let mut lexer = Lexer::new(input);
+        let tokens = match lexer.tokenize() {
+            Ok(tokens) => tokens,
+            Err(e) => return ParseOutcome::Error(e.into()),
+        };
+
+        if Self::has_unclosed_delimiters(&tokens) {
+            return ParseOutcome::Incomplete;
+        }
+
+        match Self::from_tokens(tokens).parse_file() {
+            Ok(file) => ParseOutcome::Complete(file),
+            Err(e) => ParseOutcome::Error(e),
+        }
+    }
+
+    /// Whether `tokens` leaves an opening delimiter unmatched, or ends right
+    /// after a `:`/`,` — both signs the input was cut off mid-construct
+    /// rather than actually malformed. Doesn't check that each closing
+    /// delimiter matches the kind it closes; a real mismatch there still
+    /// surfaces as a normal [`ParseError`] from the subsequent full parse.
+    fn has_unclosed_delimiters(tokens: &[SpannedToken]) -> bool {
+        let mut open_stack: Vec<&Token> = Vec::new();
+        let mut last_significant: Option<&Token> = None;
+        for spanned in tokens {
+            match &spanned.token {
+                Token::Eof => break,
+                open @ (Token::LBrace | Token::LBracket | Token::LParen | Token::LAngle) => {
+                    open_stack.push(open);
+                }
+                Token::RBrace | Token::RBracket | Token::RParen | Token::RAngle => {
+                    open_stack.pop();
+                }
+                _ => {}
+            }
+            last_significant = Some(&spanned.token);
+        }
+        !open_stack.is_empty()
+            || matches!(last_significant, Some(Token::Colon) | Some(Token::Comma))
+    }
+
+    fn from_tokens(tokens: Vec<SpannedToken>) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            recovering: false,
+            errors: Vec::new(),
+        }
+    }
+
     // -- Helpers --
 
     fn peek(&self) -> &Token {
@@ -94,6 +230,7 @@ impl Parser {
             | Token::Optional
             | Token::Exclude
             | Token::Changed
+            | Token::Added
             | Token::Hz => {
                 let s = self.peek().to_string();
                 self.advance();
@@ -123,6 +260,86 @@ impl Parser {
         }
     }
 
+    /// Skips tokens until a synchronizing point for top-level recovery,
+    /// tracking brace depth so a `}` closing a nested block (e.g. a
+    /// `query { ... }` inside an unfinished `system`) isn't mistaken for
+    /// the end of the construct being recovered. Stops on (without
+    /// consuming) a top-level keyword seen at depth 0, or just past a `}`
+    /// seen at depth 0, i.e. one closing a block opened before recovery
+    /// began.
+    fn recover_to_sync_point(&mut self) {
+        let mut depth = 0i32;
+        loop {
+            match self.peek() {
+                Token::Eof => return,
+                Token::LBrace => {
+                    depth += 1;
+                    self.advance();
+                }
+                Token::RBrace => {
+                    self.advance();
+                    if depth == 0 {
+                        return;
+                    }
+                    depth -= 1;
+                }
+                Token::Type
+                | Token::Enum
+                | Token::Variant
+                | Token::Flags
+                | Token::Record
+                | Token::System
+                | Token::Phase
+                | Token::World
+                    if depth == 0 =>
+                {
+                    return;
+                }
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+    }
+
+    /// Builds a [`Span`] running from `start` (captured via
+    /// [`Self::current_span`] before the production consumed its first
+    /// token) to the span of the most recently consumed token.
+    fn close_span(&self, start: (usize, usize)) -> Span {
+        let end = &self.tokens[self.pos.saturating_sub(1)];
+        Span {
+            start_line: start.0,
+            start_col: start.1,
+            end_line: end.line,
+            end_col: end.col,
+        }
+    }
+
+    /// Skips to the next `,` (consuming it) or `}` (leaving it for the
+    /// caller), so one malformed entry in a comma-separated list doesn't
+    /// abort the whole enclosing construct.
+    ///
+    /// Returns `false` if it ran off the end of input instead of finding a
+    /// synchronizing point. `pos` sits on `Eof`, which never advances, so a
+    /// caller that blindly retried the failing parse at the same position
+    /// would get the same error and call this again forever; returning
+    /// `false` tells it to give up instead.
+    fn recover_to_next_item(&mut self) -> bool {
+        loop {
+            match self.peek() {
+                Token::Comma => {
+                    self.advance();
+                    return true;
+                }
+                Token::RBrace => return true,
+                Token::Eof => return false,
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+    }
+
     // -- Top-level --
 
     fn parse_file(&mut self) -> Result<File, ParseError> {
@@ -162,12 +379,20 @@ impl Parser {
     }
 
     fn parse_version(&mut self) -> Result<String, ParseError> {
-        let major = self.expect_integer()?;
-        self.expect(&Token::Dot)?;
-        let minor = self.expect_integer()?;
-        self.expect(&Token::Dot)?;
-        let patch = self.expect_integer()?;
-        Ok(format!("{major}.{minor}.{patch}"))
+        match self.peek().clone() {
+            Token::Version(major, minor, patch) => {
+                self.advance();
+                Ok(format!("{major}.{minor}.{patch}"))
+            }
+            other => {
+                let (line, col) = self.current_span();
+                Err(ParseError {
+                    line,
+                    col,
+                    message: format!("expected a major.minor.patch version, got {other}"),
+                })
+            }
+        }
     }
 
     fn expect_integer(&mut self) -> Result<u64, ParseError> {
@@ -187,6 +412,30 @@ impl Parser {
         }
     }
 
+    /// Parses an integer or float literal as an `f64`, for numeric fields
+    /// like `hz` that accept fractional rates (e.g. `hz: 59.94`) as well as
+    /// whole ones (`hz: 60`).
+    fn expect_number(&mut self) -> Result<f64, ParseError> {
+        match self.peek().clone() {
+            Token::Integer(n) => {
+                self.advance();
+                Ok(n as f64)
+            }
+            Token::Float(n) => {
+                self.advance();
+                Ok(n)
+            }
+            other => {
+                let (line, col) = self.current_span();
+                Err(ParseError {
+                    line,
+                    col,
+                    message: format!("expected a number, got {other}"),
+                })
+            }
+        }
+    }
+
     // -- Import --
 
     fn parse_import(&mut self) -> Result<Import, ParseError> {
@@ -261,11 +510,16 @@ impl Parser {
     // -- Type alias --
 
     fn parse_type_alias(&mut self) -> Result<TypeAlias, ParseError> {
+        let start = self.current_span();
         self.expect(&Token::Type)?;
         let name = self.expect_ident()?;
         self.expect(&Token::Eq)?;
         let target = self.parse_type_expr()?;
-        Ok(TypeAlias { name, target })
+        Ok(TypeAlias {
+            name,
+            target,
+            span: self.close_span(start),
+        })
     }
 
     // -- Type expression --
@@ -335,9 +589,98 @@ impl Parser {
         }
     }
 
+    // -- Constant-value expressions --
+
+    /// The binary operators recognised in a constant-value expression,
+    /// along with their precedence (higher binds tighter). `|` is the
+    /// weakest so `a | b & c` parses as `a | (b & c)`, matching the usual
+    /// bitwise-operator convention in most C-family languages.
+    fn binary_op(token: &Token) -> Option<(BinaryOp, u8)> {
+        match token {
+            Token::Pipe => Some((BinaryOp::Or, 1)),
+            Token::Amp => Some((BinaryOp::And, 2)),
+            Token::Shl => Some((BinaryOp::Shl, 3)),
+            Token::Plus => Some((BinaryOp::Add, 4)),
+            Token::Minus => Some((BinaryOp::Sub, 4)),
+            Token::Star => Some((BinaryOp::Mul, 5)),
+            Token::Slash => Some((BinaryOp::Div, 5)),
+            _ => None,
+        }
+    }
+
+    /// Precedence-climbing parse of a constant-value expression: parses a
+    /// unary/atom, then keeps consuming binary operators whose precedence is
+    /// at least `min_bp`, recursing with `op_prec + 1` so same-precedence
+    /// operators associate left (`a - b - c` is `(a - b) - c`).
+    fn parse_expr(&mut self, min_bp: u8) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_unary()?;
+        while let Some((op, prec)) = Self::binary_op(self.peek()) {
+            if prec < min_bp {
+                break;
+            }
+            self.advance();
+            let rhs = self.parse_expr(prec + 1)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        if self.eat(&Token::Minus) {
+            let operand = self.parse_unary()?;
+            Ok(Expr::Unary(UnaryOp::Neg, Box::new(operand)))
+        } else {
+            self.parse_atom()
+        }
+    }
+
+    fn parse_atom(&mut self) -> Result<Expr, ParseError> {
+        match self.peek().clone() {
+            Token::Integer(n) => {
+                self.advance();
+                Ok(Expr::Int(n as i64))
+            }
+            Token::Float(n) => {
+                self.advance();
+                Ok(Expr::Float(n))
+            }
+            Token::True => {
+                self.advance();
+                Ok(Expr::Bool(true))
+            }
+            Token::False => {
+                self.advance();
+                Ok(Expr::Bool(false))
+            }
+            Token::Str(s) => {
+                self.advance();
+                Ok(Expr::Str(s))
+            }
+            Token::Ident(s) => {
+                self.advance();
+                Ok(Expr::Ident(s))
+            }
+            Token::LParen => {
+                self.advance();
+                let inner = self.parse_expr(0)?;
+                self.expect(&Token::RParen)?;
+                Ok(inner)
+            }
+            other => {
+                let (line, col) = self.current_span();
+                Err(ParseError {
+                    line,
+                    col,
+                    message: format!("expected an expression, got {other}"),
+                })
+            }
+        }
+    }
+
     // -- Enum --
 
     fn parse_enum(&mut self) -> Result<EnumDef, ParseError> {
+        let start = self.current_span();
         self.expect(&Token::Enum)?;
         let name = self.expect_ident()?;
         self.expect(&Token::LBrace)?;
@@ -346,18 +689,34 @@ impl Parser {
             if self.at(&Token::RBrace) {
                 break;
             }
-            variants.push(self.expect_ident()?);
+            let member_start = self.current_span();
+            let member_name = self.expect_ident()?;
+            let value = if self.eat(&Token::Eq) {
+                Some(self.parse_expr(0)?)
+            } else {
+                None
+            };
+            variants.push(EnumMember {
+                name: member_name,
+                value,
+                span: self.close_span(member_start),
+            });
             if !self.eat(&Token::Comma) {
                 break;
             }
         }
         self.expect(&Token::RBrace)?;
-        Ok(EnumDef { name, variants })
+        Ok(EnumDef {
+            name,
+            variants,
+            span: self.close_span(start),
+        })
     }
 
     // -- Variant --
 
     fn parse_variant(&mut self) -> Result<VariantDef, ParseError> {
+        let start = self.current_span();
         self.expect(&Token::Variant)?;
         let name = self.expect_ident()?;
         self.expect(&Token::LBrace)?;
@@ -366,6 +725,7 @@ impl Parser {
             if self.at(&Token::RBrace) {
                 break;
             }
+            let case_start = self.current_span();
             let case_name = self.expect_ident()?;
             let payload = if self.eat(&Token::LParen) {
                 let mut types = vec![self.parse_type_expr()?];
@@ -383,18 +743,24 @@ impl Parser {
             cases.push(VariantCase {
                 name: case_name,
                 payload,
+                span: self.close_span(case_start),
             });
             if !self.eat(&Token::Comma) {
                 break;
             }
         }
         self.expect(&Token::RBrace)?;
-        Ok(VariantDef { name, cases })
+        Ok(VariantDef {
+            name,
+            cases,
+            span: self.close_span(start),
+        })
     }
 
     // -- Flags --
 
     fn parse_flags(&mut self) -> Result<FlagsDef, ParseError> {
+        let start = self.current_span();
         self.expect(&Token::Flags)?;
         let name = self.expect_ident()?;
         self.expect(&Token::LBrace)?;
@@ -403,18 +769,34 @@ impl Parser {
             if self.at(&Token::RBrace) {
                 break;
             }
-            flags.push(self.expect_ident()?);
+            let member_start = self.current_span();
+            let member_name = self.expect_ident()?;
+            let value = if self.eat(&Token::Eq) {
+                Some(self.parse_expr(0)?)
+            } else {
+                None
+            };
+            flags.push(FlagMember {
+                name: member_name,
+                value,
+                span: self.close_span(member_start),
+            });
             if !self.eat(&Token::Comma) {
                 break;
             }
         }
         self.expect(&Token::RBrace)?;
-        Ok(FlagsDef { name, flags })
+        Ok(FlagsDef {
+            name,
+            flags,
+            span: self.close_span(start),
+        })
     }
 
     // -- Record --
 
     fn parse_record(&mut self) -> Result<RecordDef, ParseError> {
+        let start = self.current_span();
         self.expect(&Token::Record)?;
         let name = self.expect_ident()?;
         self.expect(&Token::LBrace)?;
@@ -423,24 +805,52 @@ impl Parser {
             if self.at(&Token::RBrace) {
                 break;
             }
-            let field_name = self.expect_ident()?;
-            self.expect(&Token::Colon)?;
-            let ty = self.parse_type_expr()?;
-            fields.push(Field {
-                name: field_name,
-                ty,
-            });
-            if !self.eat(&Token::Comma) {
-                break;
+            match self.parse_field() {
+                Ok(field) => {
+                    fields.push(field);
+                    if !self.eat(&Token::Comma) {
+                        break;
+                    }
+                }
+                Err(e) if self.recovering => {
+                    self.errors.push(e);
+                    if !self.recover_to_next_item() {
+                        break;
+                    }
+                }
+                Err(e) => return Err(e),
             }
         }
         self.expect(&Token::RBrace)?;
-        Ok(RecordDef { name, fields })
+        Ok(RecordDef {
+            name,
+            fields,
+            span: self.close_span(start),
+        })
+    }
+
+    fn parse_field(&mut self) -> Result<Field, ParseError> {
+        let start = self.current_span();
+        let field_name = self.expect_ident()?;
+        self.expect(&Token::Colon)?;
+        let ty = self.parse_type_expr()?;
+        let default = if self.eat(&Token::Eq) {
+            Some(self.parse_expr(0)?)
+        } else {
+            None
+        };
+        Ok(Field {
+            name: field_name,
+            ty,
+            default,
+            span: self.close_span(start),
+        })
     }
 
     // -- Phase --
 
     fn parse_phase(&mut self) -> Result<PhaseDef, ParseError> {
+        let start = self.current_span();
         self.expect(&Token::Phase)?;
         let name = self.expect_ident()?;
         self.expect(&Token::LBrace)?;
@@ -448,16 +858,21 @@ impl Parser {
         if self.at(&Token::Hz) {
             self.advance();
             self.expect(&Token::Colon)?;
-            hz = Some(self.expect_integer()? as u32);
+            hz = Some(self.expect_number()?);
             self.eat(&Token::Comma);
         }
         self.expect(&Token::RBrace)?;
-        Ok(PhaseDef { name, hz })
+        Ok(PhaseDef {
+            name,
+            hz,
+            span: self.close_span(start),
+        })
     }
 
     // -- System --
 
     fn parse_system(&mut self) -> Result<SystemDef, ParseError> {
+        let start = self.current_span();
         self.expect(&Token::System)?;
         let name = self.expect_ident()?;
         self.expect(&Token::LBrace)?;
@@ -508,10 +923,12 @@ impl Parser {
             phase,
             order_after,
             order_before,
+            span: self.close_span(start),
         })
     }
 
     fn parse_query(&mut self) -> Result<QueryDef, ParseError> {
+        let start = self.current_span();
         self.expect(&Token::Query)?;
 
         // Optional query name
@@ -528,6 +945,7 @@ impl Parser {
         let mut optional = Vec::new();
         let mut exclude = Vec::new();
         let mut changed = Vec::new();
+        let mut added = Vec::new();
 
         while !self.at(&Token::RBrace) {
             match self.peek() {
@@ -561,13 +979,27 @@ impl Parser {
                     changed = self.parse_ident_list()?;
                     self.eat(&Token::Comma);
                 }
+                Token::Added => {
+                    self.advance();
+                    self.expect(&Token::Colon)?;
+                    added = self.parse_ident_list()?;
+                    self.eat(&Token::Comma);
+                }
                 other => {
                     let (line, col) = self.current_span();
-                    return Err(ParseError {
+                    let error = ParseError {
                         line,
                         col,
                         message: format!("unexpected token in query body: {other}"),
-                    });
+                    };
+                    if self.recovering {
+                        self.errors.push(error);
+                        if !self.recover_to_next_item() {
+                            break;
+                        }
+                    } else {
+                        return Err(error);
+                    }
                 }
             }
         }
@@ -580,6 +1012,8 @@ impl Parser {
             optional,
             exclude,
             changed,
+            added,
+            span: self.close_span(start),
         })
     }
 
@@ -602,6 +1036,7 @@ impl Parser {
     // -- World --
 
     fn parse_world(&mut self) -> Result<WorldDef, ParseError> {
+        let start = self.current_span();
         self.expect(&Token::World)?;
         let name = self.expect_ident()?;
         self.expect(&Token::LBrace)?;
@@ -629,6 +1064,7 @@ impl Parser {
             name,
             includes,
             items,
+            span: self.close_span(start),
         })
     }
 }
@@ -712,6 +1148,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_query_added_filter() {
+        let input = r#"
+            package test:systems@0.1.0
+
+            system spawner {
+                query {
+                    read: [transform],
+                    added: [transform],
+                }
+            }
+        "#;
+        let file = Parser::parse(input).unwrap();
+        if let TopLevelItem::System(sys) = &file.items[0] {
+            assert_eq!(sys.queries[0].added, vec!["transform"]);
+        } else {
+            panic!("expected system");
+        }
+    }
+
     #[test]
     fn test_parse_system() {
         let input = r#"
@@ -780,4 +1236,370 @@ mod tests {
         let file = Parser::parse(input).unwrap();
         assert_eq!(file.items.len(), 3);
     }
+
+    #[test]
+    fn test_parse_enum_discriminants_and_flag_bits() {
+        let input = r#"
+            package test:constvalues@0.1.0
+
+            enum color { red = 0, green = 2 }
+
+            flags layers { terrain = 0x1, objects = 0x2 }
+        "#;
+        let file = Parser::parse(input).unwrap();
+        if let TopLevelItem::Enum(e) = &file.items[0] {
+            assert_eq!(e.variants[0].value, Some(Expr::Int(0)));
+            assert_eq!(e.variants[1].value, Some(Expr::Int(2)));
+        } else {
+            panic!("expected enum");
+        }
+        if let TopLevelItem::Flags(f) = &file.items[1] {
+            assert_eq!(f.flags[0].value, Some(Expr::Int(1)));
+            assert_eq!(f.flags[1].value, Some(Expr::Int(2)));
+        } else {
+            panic!("expected flags");
+        }
+    }
+
+    #[test]
+    fn test_parse_record_field_default_expression() {
+        let input = r#"
+            package test:constvalues@0.1.0
+
+            record transform {
+                x: f32 = 1.5,
+                layers: u32 = 0x1 | 0x2,
+                label: string = "origin",
+            }
+        "#;
+        let file = Parser::parse(input).unwrap();
+        if let TopLevelItem::Record(rec) = &file.items[0] {
+            assert_eq!(rec.fields[0].default, Some(Expr::Float(1.5)));
+            assert_eq!(
+                rec.fields[1].default,
+                Some(Expr::Binary(
+                    BinaryOp::Or,
+                    Box::new(Expr::Int(1)),
+                    Box::new(Expr::Int(2)),
+                ))
+            );
+            assert_eq!(
+                rec.fields[2].default,
+                Some(Expr::Str("origin".to_string()))
+            );
+        } else {
+            panic!("expected record");
+        }
+    }
+
+    #[test]
+    fn test_parse_expr_precedence_and_associativity() {
+        // `*` binds tighter than `+`/`-`, and same-precedence operators
+        // associate left: `1 + 2 * 3 - 4` is `(1 + (2 * 3)) - 4`.
+        let input = r#"
+            package test:constvalues@0.1.0
+
+            record r { x: i32 = 1 + 2 * 3 - 4 }
+        "#;
+        let file = Parser::parse(input).unwrap();
+        if let TopLevelItem::Record(rec) = &file.items[0] {
+            assert_eq!(
+                rec.fields[0].default,
+                Some(Expr::Binary(
+                    BinaryOp::Sub,
+                    Box::new(Expr::Binary(
+                        BinaryOp::Add,
+                        Box::new(Expr::Int(1)),
+                        Box::new(Expr::Binary(
+                            BinaryOp::Mul,
+                            Box::new(Expr::Int(2)),
+                            Box::new(Expr::Int(3)),
+                        )),
+                    )),
+                    Box::new(Expr::Int(4)),
+                ))
+            );
+        } else {
+            panic!("expected record");
+        }
+    }
+
+    #[test]
+    fn test_parse_expr_unary_negation_and_parens_and_ident() {
+        let input = r#"
+            package test:constvalues@0.1.0
+
+            record r { x: i32 = -(base + 1) }
+        "#;
+        let file = Parser::parse(input).unwrap();
+        if let TopLevelItem::Record(rec) = &file.items[0] {
+            assert_eq!(
+                rec.fields[0].default,
+                Some(Expr::Unary(
+                    UnaryOp::Neg,
+                    Box::new(Expr::Binary(
+                        BinaryOp::Add,
+                        Box::new(Expr::Ident("base".to_string())),
+                        Box::new(Expr::Int(1)),
+                    )),
+                ))
+            );
+        } else {
+            panic!("expected record");
+        }
+    }
+
+    #[test]
+    fn test_parse_phase_fractional_hz() {
+        let input = r#"
+            package test:phases@0.1.0
+
+            phase render { hz: 59.94 }
+        "#;
+        let file = Parser::parse(input).unwrap();
+        if let TopLevelItem::Phase(phase) = &file.items[0] {
+            assert_eq!(phase.name, "render");
+            assert_eq!(phase.hz, Some(59.94));
+        } else {
+            panic!("expected a phase item");
+        }
+    }
+
+    #[test]
+    fn test_parse_package_decl_version() {
+        let input = "package engine:std@12.34.5\n";
+        let file = Parser::parse(input).unwrap();
+        assert_eq!(file.package.version.as_deref(), Some("12.34.5"));
+    }
+
+    #[test]
+    fn test_parse_recovering_reports_all_independent_top_level_errors() {
+        // The middle record is missing its name, a grammar error (not a
+        // lex error), so recovery can skip it and keep going.
+        let input = r#"
+            package test:recover@0.1.0
+
+            record good_one {
+                x: f32,
+            }
+
+            record {
+                bad: f32,
+            }
+
+            record good_two {
+                y: f32,
+            }
+        "#;
+        let (file, errors) = Parser::parse_recovering(input);
+        let file = file.expect("package declaration parsed, so a best-effort File is returned");
+        assert_eq!(errors.len(), 1);
+
+        let names: Vec<&str> = file
+            .items
+            .iter()
+            .map(|item| match item {
+                TopLevelItem::Record(r) => r.name.as_str(),
+                _ => panic!("expected only records"),
+            })
+            .collect();
+        assert_eq!(names, vec!["good_one", "good_two"]);
+    }
+
+    #[test]
+    fn test_parse_recovering_skips_nested_brace_without_early_exit() {
+        // `phase:` isn't a valid query-body key, a grammar error surfaced
+        // from inside a query nested inside a system.
+        let input = r#"
+            package test:recover@0.1.0
+
+            system broken {
+                query {
+                    read: [transform],
+                    phase: [oops],
+                }
+            }
+
+            record after {}
+        "#;
+        let (file, errors) = Parser::parse_recovering(input);
+        let file = file.unwrap();
+        assert_eq!(errors.len(), 1);
+        // Recovery inside the query body shouldn't lose the rest of the file.
+        assert!(matches!(file.items.last(), Some(TopLevelItem::Record(r)) if r.name == "after"));
+    }
+
+    #[test]
+    fn test_parse_recovering_recovers_a_single_bad_field() {
+        // `phase` isn't a valid field name (unlike `read`/`write`/etc, it's
+        // not in `expect_ident`'s allowed-keyword list).
+        let input = r#"
+            package test:recover@0.1.0
+
+            record mixed {
+                x: f32,
+                phase,
+                y: f32,
+            }
+        "#;
+        let (file, errors) = Parser::parse_recovering(input);
+        let file = file.unwrap();
+        assert_eq!(errors.len(), 1);
+        if let TopLevelItem::Record(rec) = &file.items[0] {
+            let field_names: Vec<&str> = rec.fields.iter().map(|f| f.name.as_str()).collect();
+            assert_eq!(field_names, vec!["x", "y"]);
+        } else {
+            panic!("expected record");
+        }
+    }
+
+    #[test]
+    fn test_parse_recovering_with_no_errors_matches_parse() {
+        let input = r#"
+            package test:recover@0.1.0
+
+            record clean {
+                x: f32,
+            }
+        "#;
+        let (file, errors) = Parser::parse_recovering(input);
+        assert!(errors.is_empty());
+        assert_eq!(file.unwrap().items.len(), 1);
+    }
+
+    /// Runs `parse` on its own thread and fails the test if it doesn't
+    /// return within 5 seconds, for regression tests guarding against a
+    /// parser recovery loop that never makes progress.
+    fn assert_completes_within_5s(input: &'static str) -> (Option<File>, Vec<ParseError>) {
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(Parser::parse_recovering(input));
+        });
+        rx.recv_timeout(std::time::Duration::from_secs(5))
+            .expect("parse_recovering did not return within 5s — recovery loop likely hung")
+    }
+
+    #[test]
+    fn test_parse_recovering_truncated_record_body_does_not_hang() {
+        let input = r#"
+            package test:recover@0.1.0
+
+            record foo {
+                x: u32,
+                y:
+        "#;
+        let (_file, errors) = assert_completes_within_5s(input);
+        assert!(!errors.is_empty());
+    }
+
+    #[test]
+    fn test_parse_recovering_truncated_query_body_does_not_hang() {
+        let input = r#"
+            package test:recover@0.1.0
+
+            system broken {
+                query {
+        "#;
+        let (_file, errors) = assert_completes_within_5s(input);
+        assert!(!errors.is_empty());
+    }
+
+    #[test]
+    fn test_record_span_covers_its_source_range() {
+        let input = r#"
+            package test:spans@0.1.0
+
+            record transform {
+                x: f32,
+            }
+        "#;
+        let file = Parser::parse(input).unwrap();
+        if let TopLevelItem::Record(rec) = &file.items[0] {
+            assert_eq!(rec.span.start_line, 4);
+            assert_eq!(rec.span.end_line, 6);
+            assert_eq!(rec.fields[0].span.start_line, 5);
+        } else {
+            panic!("expected record");
+        }
+    }
+
+    #[test]
+    fn test_eq_ignoring_span_matches_same_content_with_different_spans() {
+        let a = Parser::parse(
+            r#"
+                package test:spans@0.1.0
+
+                record transform {
+                    x: f32,
+                }
+            "#,
+        )
+        .unwrap();
+        // Same content, reformatted so every span differs from `a`'s.
+        let b = Parser::parse(
+            r#"
+                package test:spans@0.1.0
+                record transform { x: f32, }
+            "#,
+        )
+        .unwrap();
+
+        if let (TopLevelItem::Record(rec_a), TopLevelItem::Record(rec_b)) =
+            (&a.items[0], &b.items[0])
+        {
+            assert_ne!(rec_a.span, rec_b.span);
+        } else {
+            panic!("expected records");
+        }
+        assert!(a.items.eq_ignoring_span(&b.items));
+    }
+
+    #[test]
+    fn test_parse_incremental_reports_incomplete_inside_open_brace() {
+        let outcome = Parser::parse_incremental(
+            r#"
+                package test:repl@0.1.0
+
+                record transform {
+                    x: f32,
+            "#,
+        );
+        assert!(matches!(outcome, ParseOutcome::Incomplete));
+    }
+
+    #[test]
+    fn test_parse_incremental_reports_incomplete_after_trailing_colon() {
+        // No open delimiter at all here — just a dangling `:` with no
+        // balance issue, which is exactly the case delimiter-depth alone
+        // wouldn't catch.
+        let outcome = Parser::parse_incremental("package test:");
+        assert!(matches!(outcome, ParseOutcome::Incomplete));
+    }
+
+    #[test]
+    fn test_parse_incremental_reports_incomplete_after_trailing_comma() {
+        let outcome = Parser::parse_incremental("package test:repl@0.1.0\n\nenum e { red,");
+        assert!(matches!(outcome, ParseOutcome::Incomplete));
+    }
+
+    #[test]
+    fn test_parse_incremental_completes_once_closed() {
+        let input = r#"
+            package test:repl@0.1.0
+
+            record transform {
+                x: f32,
+            }
+        "#;
+        match Parser::parse_incremental(input) {
+            ParseOutcome::Complete(file) => assert_eq!(file.items.len(), 1),
+            other => panic!("expected Complete, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_incremental_reports_error_for_genuinely_malformed_input() {
+        let outcome = Parser::parse_incremental("package test:repl@0.1.0\n\nrecord 123 {}");
+        assert!(matches!(outcome, ParseOutcome::Error(_)));
+    }
 }
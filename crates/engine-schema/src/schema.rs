@@ -1,6 +1,7 @@
 /// Resolved schema registry — collects all definitions from parsed files into
 /// a unified type registry that the ECS runtime uses for validation.
 use crate::ast::*;
+use crate::migration::MigrationRegistry;
 use crate::parser::Parser;
 use std::collections::HashMap;
 use std::path::Path;
@@ -20,13 +21,19 @@ pub enum SchemaError {
     DuplicatePhase(String),
     #[error("unknown type referenced: {0}")]
     UnknownType(String),
+    #[error("no migration path for record '{record}' from layout version {from} to {to}")]
+    NoMigrationPath { record: String, from: u64, to: u64 },
 }
 
 /// A resolved schema containing all definitions.
 #[derive(Debug, Clone, Default)]
 pub struct Schema {
-    /// All record definitions (components/tags/events), keyed by name.
-    pub records: HashMap<String, RecordDef>,
+    /// All record definitions (components/tags/events), keyed by name. Each
+    /// name maps to every distinct layout seen for it, oldest first, rather
+    /// than just the current one — so a shard tagged with an older
+    /// `layout_version` can still be looked up and migrated forward. Use
+    /// [`Schema::get_record`] for the current (latest) version.
+    pub records: HashMap<String, Vec<RecordDef>>,
     /// All enum definitions, keyed by name.
     pub enums: HashMap<String, EnumDef>,
     /// All variant definitions, keyed by name.
@@ -87,12 +94,18 @@ impl Schema {
                         .or_insert_with(|| f.clone());
                 }
                 TopLevelItem::Record(r) => {
-                    // Skip duplicate records — they may come from multiple
-                    // domain files (e.g. physics defines `transform` and
-                    // gameplay imports it, but both files are loaded).
-                    self.records
-                        .entry(r.name.clone())
-                        .or_insert_with(|| r.clone());
+                    // Multiple domain files may declare the same record name
+                    // (e.g. physics defines `transform` and gameplay imports
+                    // it, but both files are loaded) — that's the identical
+                    // layout turning up twice, not a new version, so only
+                    // append when this exact layout hasn't been seen yet.
+                    let versions = self.records.entry(r.name.clone()).or_default();
+                    if !versions
+                        .iter()
+                        .any(|existing| existing.layout_version() == r.layout_version())
+                    {
+                        versions.push(r.clone());
+                    }
                 }
                 TopLevelItem::System(s) => {
                     self.systems
@@ -113,9 +126,25 @@ impl Schema {
         Ok(())
     }
 
-    /// Get a record definition by name, or None if it doesn't exist.
+    /// Get the current (latest) version of a record definition by name, or
+    /// `None` if it doesn't exist.
     pub fn get_record(&self, name: &str) -> Option<&RecordDef> {
-        self.records.get(name)
+        self.records.get(name).and_then(|versions| versions.last())
+    }
+
+    /// Get a specific past version of a record by name and `layout_version`,
+    /// or `None` if that name or version is unknown.
+    pub fn get_record_version(&self, name: &str, layout_version: u64) -> Option<&RecordDef> {
+        self.records
+            .get(name)?
+            .iter()
+            .find(|r| r.layout_version() == layout_version)
+    }
+
+    /// Every version of a record retained for `name`, oldest first, or `None`
+    /// if the name is unknown.
+    pub fn record_history(&self, name: &str) -> Option<&[RecordDef]> {
+        self.records.get(name).map(Vec::as_slice)
     }
 
     /// Check if a name refers to any known type (record, enum, variant, flags, alias, or primitive).
@@ -133,28 +162,32 @@ impl Schema {
         self.records.keys().map(|s| s.as_str()).collect()
     }
 
-    /// List all tag record names (empty records).
+    /// List all tag record names (empty records), by current layout.
     pub fn tag_names(&self) -> Vec<&str> {
         self.records
             .iter()
-            .filter(|(_, r)| r.is_tag())
+            .filter(|(_, versions)| versions.last().is_some_and(RecordDef::is_tag))
             .map(|(k, _)| k.as_str())
             .collect()
     }
 
-    /// List all component record names (non-empty records).
+    /// List all component record names (non-empty records), by current layout.
     pub fn component_names(&self) -> Vec<&str> {
         self.records
             .iter()
-            .filter(|(_, r)| !r.is_tag())
+            .filter(|(_, versions)| versions.last().is_some_and(|r| !r.is_tag()))
             .map(|(k, _)| k.as_str())
             .collect()
     }
 
     /// Validate that all types referenced in records and systems are defined.
     pub fn validate(&self) -> Result<(), SchemaError> {
-        // Validate record field types
-        for rec in self.records.values() {
+        // Validate record field types (current layout only — older retained
+        // versions were valid when they were current and aren't re-checked).
+        for versions in self.records.values() {
+            let Some(rec) = versions.last() else {
+                continue;
+            };
             for field in &rec.fields {
                 self.validate_type_expr(&field.ty)?;
             }
@@ -170,6 +203,7 @@ impl Schema {
                     .chain(&query.optional)
                     .chain(&query.exclude)
                     .chain(&query.changed)
+                    .chain(&query.added)
                 {
                     if !self.records.contains_key(name) {
                         return Err(SchemaError::UnknownType(format!(
@@ -194,6 +228,40 @@ impl Schema {
         Ok(())
     }
 
+    /// Validate that every retained non-current version of a record has a
+    /// migration path forward to its current layout, per `migrations`.
+    ///
+    /// Without this, a record could pick up new, incompatible layouts over
+    /// time with no declared way to bring old wire data up to date — a
+    /// shard tagged with that old `layout_version` would have to be dropped
+    /// silently at decode time instead of being caught here, at schema load.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SchemaError::NoMigrationPath`] for the first retained
+    /// version that has no path to the current layout.
+    pub fn validate_migrations(&self, migrations: &MigrationRegistry) -> Result<(), SchemaError> {
+        for (name, versions) in &self.records {
+            let Some(current) = versions.last() else {
+                continue;
+            };
+            let current_version = current.layout_version();
+            for old in &versions[..versions.len().saturating_sub(1)] {
+                let old_version = old.layout_version();
+                if old_version != current_version
+                    && !migrations.has_path(name, old_version, current_version)
+                {
+                    return Err(SchemaError::NoMigrationPath {
+                        record: name.clone(),
+                        from: old_version,
+                        to: current_version,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn validate_type_expr(&self, ty: &TypeExpr) -> Result<(), SchemaError> {
         match ty {
             TypeExpr::Primitive(_) => Ok(()),
@@ -221,12 +289,17 @@ impl Schema {
     }
 
     /// Serialize the schema to a JSON description for clients.
+    ///
+    /// Field types are formatted with `{:?}` for human/editor readability —
+    /// see [`Schema::to_descriptor`] for a structured, versioned binary form
+    /// a runtime can act on directly.
     pub fn to_json(&self) -> serde_json::Value {
         serde_json::json!({
-            "records": self.records.values().map(|r| {
+            "records": self.records.values().filter_map(|versions| versions.last()).map(|r| {
                 serde_json::json!({
                     "name": r.name,
                     "is_tag": r.is_tag(),
+                    "layout_version": r.layout_version(),
                     "fields": r.fields.iter().map(|f| {
                         serde_json::json!({
                             "name": f.name,
@@ -247,6 +320,7 @@ impl Schema {
                             "optional": q.optional,
                             "exclude": q.exclude,
                             "changed": q.changed,
+                            "added": q.added,
                         })
                     }).collect::<Vec<_>>(),
                 })
@@ -326,4 +400,134 @@ mod tests {
         assert_eq!(schema.systems.len(), 1);
         assert!(schema.get_record("frozen").unwrap().is_tag());
     }
+
+    #[test]
+    fn test_merge_retains_new_record_version_instead_of_discarding() {
+        let mut schema = Schema::new();
+        schema
+            .load_source(
+                r#"
+            package test:game@0.1.0
+
+            record velocity {
+                x: f32,
+                y: f32,
+            }
+        "#,
+            )
+            .unwrap();
+        let v1 = schema.get_record("velocity").unwrap().layout_version();
+
+        schema
+            .load_source(
+                r#"
+            package test:game@0.2.0
+
+            record velocity {
+                x: f32,
+                y: f32,
+                z: f32,
+            }
+        "#,
+            )
+            .unwrap();
+        let v2 = schema.get_record("velocity").unwrap().layout_version();
+
+        assert_ne!(v1, v2);
+        assert_eq!(schema.get_record("velocity").unwrap().fields.len(), 3);
+        assert_eq!(schema.record_history("velocity").unwrap().len(), 2);
+        assert_eq!(
+            schema.get_record_version("velocity", v1).unwrap().fields.len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_merge_is_idempotent_for_identical_redeclaration() {
+        let mut schema = Schema::new();
+        let source = r#"
+            package test:game@0.1.0
+
+            record velocity {
+                x: f32,
+                y: f32,
+            }
+        "#;
+        schema.load_source(source).unwrap();
+        schema.load_source(source).unwrap();
+        assert_eq!(schema.record_history("velocity").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_validate_migrations_rejects_missing_path() {
+        let mut schema = Schema::new();
+        schema
+            .load_source(
+                r#"
+            package test:game@0.1.0
+
+            record velocity {
+                x: f32,
+            }
+        "#,
+            )
+            .unwrap();
+        schema
+            .load_source(
+                r#"
+            package test:game@0.2.0
+
+            record velocity {
+                x: f32,
+                y: f32,
+            }
+        "#,
+            )
+            .unwrap();
+
+        let migrations = crate::MigrationRegistry::new();
+        let err = schema.validate_migrations(&migrations).unwrap_err();
+        assert!(matches!(err, SchemaError::NoMigrationPath { .. }));
+    }
+
+    #[test]
+    fn test_validate_migrations_accepts_declared_path() {
+        let mut schema = Schema::new();
+        schema
+            .load_source(
+                r#"
+            package test:game@0.1.0
+
+            record velocity {
+                x: f32,
+            }
+        "#,
+            )
+            .unwrap();
+        let v1 = schema.get_record("velocity").unwrap().layout_version();
+        schema
+            .load_source(
+                r#"
+            package test:game@0.2.0
+
+            record velocity {
+                x: f32,
+                y: f32,
+            }
+        "#,
+            )
+            .unwrap();
+        let v2 = schema.get_record("velocity").unwrap().layout_version();
+
+        let mut migrations = crate::MigrationRegistry::new();
+        migrations.register(
+            "velocity",
+            crate::RecordMigration {
+                from_version: v1,
+                to_version: v2,
+                renamed_fields: Vec::new(),
+            },
+        );
+        schema.validate_migrations(&migrations).unwrap();
+    }
 }
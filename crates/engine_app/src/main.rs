@@ -10,6 +10,7 @@
 //! 2. Subscribe to `engine.system.register`.
 //! 3. Enter the fixed-timestep tick loop.
 
+mod api;
 mod registry;
 mod scheduler;
 mod tick;
@@ -23,11 +23,41 @@ pub struct SystemInfo {
     pub instances: Vec<String>,
 }
 
+/// A stable handle for a system registered via
+/// [`SystemRegistry::register_once`].
+///
+/// Unlike instance IDs — which are chosen by the connecting system process
+/// and only meaningful while that process is attached — a `SystemId` is
+/// minted by the registry itself and stays valid for the life of the
+/// registration, so it can be held by a caller (an editor command, a
+/// migration script) across invocations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SystemId(u64);
+
+/// A system registered for one-shot, push-based execution via
+/// [`SystemRegistry::register_once`], run on demand outside the normal
+/// phase schedule (e.g. `TickLoop::run_system_once`).
+#[derive(Debug, Clone)]
+pub struct OnceSystem {
+    /// Human-readable name, for logging — the `SystemId` is the stable
+    /// handle, this is not used to look anything up.
+    pub name: String,
+    /// The system's data access requirements.
+    pub query: QueryDescriptor,
+    /// The connected instance that should receive the invocation.
+    pub instance_id: String,
+}
+
 /// Registry of all systems known to the coordinator.
 #[derive(Debug, Default)]
 pub struct SystemRegistry {
     /// Systems keyed by name.
     systems: HashMap<String, SystemInfo>,
+    /// Systems registered for one-shot, push-based execution, keyed by the
+    /// `SystemId` handed back from `register_once`.
+    registered_once: HashMap<SystemId, OnceSystem>,
+    /// Counter used to mint the next `SystemId`.
+    next_system_id: u64,
 }
 
 impl SystemRegistry {
@@ -36,6 +66,8 @@ impl SystemRegistry {
     pub fn new() -> Self {
         Self {
             systems: HashMap::new(),
+            registered_once: HashMap::new(),
+            next_system_id: 0,
         }
     }
 
@@ -79,6 +111,18 @@ impl SystemRegistry {
         self.systems.get(name)
     }
 
+    /// Find the system that owns a given instance ID.
+    ///
+    /// Used to target a single running instance directly (e.g. for
+    /// `TickLoop::run_system_by_id`) rather than broadcasting to every
+    /// instance of a system.
+    #[must_use]
+    pub fn find_instance(&self, instance_id: &str) -> Option<&SystemInfo> {
+        self.systems
+            .values()
+            .find(|info| info.instances.iter().any(|id| id == instance_id))
+    }
+
     /// Returns an iterator over all registered systems.
     pub fn iter(&self) -> impl Iterator<Item = &SystemInfo> {
         self.systems.values()
@@ -95,6 +139,47 @@ impl SystemRegistry {
     pub fn total_instances(&self) -> usize {
         self.systems.values().map(|s| s.instances.len()).sum()
     }
+
+    /// Register a system for one-shot, push-based execution and return a
+    /// stable [`SystemId`] the caller can invoke later.
+    ///
+    /// This is independent of the connected-instance register/unregister
+    /// protocol above: `instance_id` is simply the target that should
+    /// receive the invocation when the caller runs it, there is no
+    /// requirement that the instance have gone through `register` first.
+    /// Useful for editor commands, migrations, and deterministic setup
+    /// steps that must run exactly once, outside the fixed-timestep
+    /// schedule.
+    pub fn register_once(
+        &mut self,
+        name: impl Into<String>,
+        query: QueryDescriptor,
+        instance_id: impl Into<String>,
+    ) -> SystemId {
+        let id = SystemId(self.next_system_id);
+        self.next_system_id += 1;
+        self.registered_once.insert(
+            id,
+            OnceSystem {
+                name: name.into(),
+                query,
+                instance_id: instance_id.into(),
+            },
+        );
+        id
+    }
+
+    /// Look up a system registered via `register_once` by its `SystemId`.
+    #[must_use]
+    pub fn get_once(&self, id: SystemId) -> Option<&OnceSystem> {
+        self.registered_once.get(&id)
+    }
+
+    /// Remove a one-shot system registration, preventing further
+    /// invocations of it. Returns `true` if `id` was registered.
+    pub fn unregister_once(&mut self, id: SystemId) -> bool {
+        self.registered_once.remove(&id).is_some()
+    }
 }
 
 #[cfg(test)]
@@ -164,4 +249,48 @@ mod tests {
         registry.register(make_descriptor("physics", "inst-1"));
         assert_eq!(registry.total_instances(), 1);
     }
+
+    #[test]
+    fn test_find_instance_locates_owning_system() {
+        let mut registry = SystemRegistry::new();
+        registry.register(make_descriptor("physics", "inst-1"));
+        registry.register(make_descriptor("ai", "inst-2"));
+        assert_eq!(registry.find_instance("inst-2").unwrap().name, "ai");
+    }
+
+    #[test]
+    fn test_find_instance_unknown_id_returns_none() {
+        let mut registry = SystemRegistry::new();
+        registry.register(make_descriptor("physics", "inst-1"));
+        assert!(registry.find_instance("missing").is_none());
+    }
+
+    #[test]
+    fn test_register_once_returns_distinct_ids() {
+        let mut registry = SystemRegistry::new();
+        let q = QueryDescriptor::new().read(ComponentTypeId(1));
+        let a = registry.register_once("migrate_inventory", q.clone(), "inst-1");
+        let b = registry.register_once("migrate_inventory", q, "inst-1");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_get_once_returns_registration() {
+        let mut registry = SystemRegistry::new();
+        let q = QueryDescriptor::new().write(ComponentTypeId(2));
+        let id = registry.register_once("setup_step", q, "inst-1");
+        let once = registry.get_once(id).unwrap();
+        assert_eq!(once.name, "setup_step");
+        assert_eq!(once.instance_id, "inst-1");
+    }
+
+    #[test]
+    fn test_unregister_once_removes_registration() {
+        let mut registry = SystemRegistry::new();
+        let q = QueryDescriptor::new().read(ComponentTypeId(1));
+        let id = registry.register_once("setup_step", q, "inst-1");
+        assert!(registry.unregister_once(id));
+        assert!(registry.get_once(id).is_none());
+        assert!(!registry.unregister_once(id));
+    }
 }
@@ -1,20 +1,54 @@
 //! System scheduler — conflict detection and stage computation.
 //!
 //! The scheduler groups registered systems into **stages** based on their
-//! read/write access sets. Systems within a stage have no conflicts and run
-//! in parallel. Stages execute sequentially with a merge barrier between them.
+//! read/write access sets and explicit `order_after`/`order_before`
+//! constraints. Systems within a stage have no conflicts and run in
+//! parallel. Stages execute sequentially with a merge barrier between them.
 
 #![allow(dead_code)]
 
-use engine_component::QueryDescriptor;
+use std::collections::{BTreeSet, HashMap};
 
-/// A registered system with its name and query descriptor.
+use engine_component::{pack_into_stages, ComponentTypeId, QueryDescriptor};
+use thiserror::Error;
+
+/// A registered system with its name, query descriptor, and ordering edges.
 #[derive(Debug, Clone)]
 pub struct RegisteredSystem {
     /// The system name (e.g. `"physics"`).
     pub name: String,
     /// The system's data access requirements.
     pub query: QueryDescriptor,
+    /// Names of systems that must run in a strictly earlier stage than
+    /// this one (`order_after` in the IDL: this system runs after them).
+    pub order_after: Vec<String>,
+    /// Names of systems that must run in a strictly later stage than this
+    /// one (`order_before` in the IDL: this system runs before them).
+    pub order_before: Vec<String>,
+    /// The phase this system belongs to, if any. Systems with no phase
+    /// default to running every frame (see [`build_schedule`]).
+    pub phase: Option<String>,
+}
+
+impl RegisteredSystem {
+    /// Create a system with no explicit ordering constraints or phase.
+    #[must_use]
+    pub fn new(name: impl Into<String>, query: QueryDescriptor) -> Self {
+        Self {
+            name: name.into(),
+            query,
+            order_after: Vec::new(),
+            order_before: Vec::new(),
+            phase: None,
+        }
+    }
+
+    /// Assign this system to a named phase.
+    #[must_use]
+    pub fn with_phase(mut self, phase: impl Into<String>) -> Self {
+        self.phase = Some(phase.into());
+        self
+    }
 }
 
 /// A stage is a group of systems that can run in parallel (no conflicts).
@@ -24,49 +58,354 @@ pub struct Stage {
     pub system_indices: Vec<usize>,
 }
 
+/// Errors produced while computing execution stages.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// The `order_after`/`order_before` constraints form a cycle, so no
+    /// valid topological ordering exists.
+    #[error("ordering cycle detected among systems: {0:?}")]
+    OrderingCycle(Vec<String>),
+}
+
+/// Number of component bits packed into one word of a [`ConflictMask`].
+const MASK_BITS: usize = u64::BITS as usize;
+
+/// A growable bitset over component bit indices.
+///
+/// Conflict detection needs only set membership and intersection, so a
+/// system's reads/writes are packed into `u64` words instead of a
+/// `BTreeSet<ComponentTypeId>` — testing two systems for conflict becomes a
+/// handful of word ANDs instead of an O(n·m) set walk. The bitset spans as
+/// many words as the largest bit index seen requires, so the component id
+/// space isn't capped at 64.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct ConflictMask {
+    words: Vec<u64>,
+}
+
+impl ConflictMask {
+    fn set(&mut self, bit: usize) {
+        let word = bit / MASK_BITS;
+        if self.words.len() <= word {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1u64 << (bit % MASK_BITS);
+    }
+
+    /// Returns `true` if the two masks share any set bit.
+    fn intersects(&self, other: &ConflictMask) -> bool {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .any(|(a, b)| a & b != 0)
+    }
+
+    /// OR `other`'s bits into `self`, growing `self` if `other` is wider.
+    fn or_with(&mut self, other: &ConflictMask) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a |= b;
+        }
+    }
+}
+
+/// A system's (or a stage's accumulated) read and write access, as masks.
+#[derive(Debug, Clone, Default)]
+struct AccessMasks {
+    reads: ConflictMask,
+    writes: ConflictMask,
+}
+
+impl AccessMasks {
+    /// Returns `true` if placing a system with these access masks alongside
+    /// a stage (or another system) with `other` access would conflict:
+    /// `(writes & other.reads) | (writes & other.writes) | (reads & other.writes) != 0`.
+    fn conflicts_with(&self, other: &AccessMasks) -> bool {
+        self.writes.intersects(&other.reads)
+            || self.writes.intersects(&other.writes)
+            || self.reads.intersects(&other.writes)
+    }
+
+    fn or_with(&mut self, other: &AccessMasks) {
+        self.reads.or_with(&other.reads);
+        self.writes.or_with(&other.writes);
+    }
+}
+
+/// Assigns each `ComponentTypeId` read or written by `systems` a distinct
+/// bit index, in order of first appearance.
+fn assign_bit_indices(systems: &[RegisteredSystem]) -> HashMap<ComponentTypeId, usize> {
+    let mut bit_of = HashMap::new();
+    for system in systems {
+        for &ty in system.query.reads.iter().chain(system.query.writes.iter()) {
+            let next = bit_of.len();
+            bit_of.entry(ty).or_insert(next);
+        }
+    }
+    bit_of
+}
+
+/// Builds each system's [`AccessMasks`] from its query, using the bit
+/// indices assigned by [`assign_bit_indices`].
+fn build_access_masks(
+    systems: &[RegisteredSystem],
+    bit_of: &HashMap<ComponentTypeId, usize>,
+) -> Vec<AccessMasks> {
+    systems
+        .iter()
+        .map(|system| {
+            let mut masks = AccessMasks::default();
+            for ty in &system.query.reads {
+                masks.reads.set(bit_of[ty]);
+            }
+            for ty in &system.query.writes {
+                masks.writes.set(bit_of[ty]);
+            }
+            masks
+        })
+        .collect()
+}
+
 /// Computes execution stages from a set of registered systems.
 ///
-/// The algorithm is a greedy graph colouring:
-/// 1. For each system, check if it conflicts with any system already placed
-///    in the current stage.
-/// 2. If no conflict, add it to the current stage.
-/// 3. If conflict, try the next stage, or create a new one.
+/// Delegates the actual graph-plus-packing algorithm to
+/// [`engine_component::pack_into_stages`], supplying [`AccessMasks`] as the
+/// per-stage accumulator: a candidate stage conflicts with a system when
+/// its accumulated [`ConflictMask`]s intersect, and placing a system there
+/// ORs its masks in. That shared algorithm re-derives each system's floor
+/// from where its `order_after`/`order_before` predecessors *actually*
+/// landed, not just their topological depth, so a predecessor pushed later
+/// by an unrelated conflict still forces its successors later too.
 ///
-/// This produces a valid (though not necessarily optimal) stage assignment
-/// that guarantees no two conflicting systems run in the same stage.
-#[must_use]
-pub fn compute_stages(systems: &[RegisteredSystem]) -> Vec<Stage> {
+/// # Errors
+///
+/// Returns [`ScheduleError::OrderingCycle`] if `order_after`/`order_before`
+/// constraints among the systems are unsatisfiable.
+pub fn compute_stages(systems: &[RegisteredSystem]) -> Result<Vec<Stage>, ScheduleError> {
     if systems.is_empty() {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
-    let mut stages: Vec<Stage> = Vec::new();
+    let order_edges = order_edges(systems);
+    let bit_of = assign_bit_indices(systems);
+    let access_masks = build_access_masks(systems, &bit_of);
 
-    for (sys_idx, system) in systems.iter().enumerate() {
-        let mut placed = false;
+    let stages = pack_into_stages::<AccessMasks>(
+        systems.len(),
+        &order_edges,
+        |stage_mask, sys_idx| access_masks[sys_idx].conflicts_with(stage_mask),
+        |stage_mask, sys_idx| stage_mask.or_with(&access_masks[sys_idx]),
+    )
+    .map_err(|cyclic| {
+        ScheduleError::OrderingCycle(cyclic.into_iter().map(|i| systems[i].name.clone()).collect())
+    })?;
 
-        for stage in &mut stages {
-            // Check if this system conflicts with any system in this stage.
-            let conflicts = stage
-                .system_indices
-                .iter()
-                .any(|&existing_idx| system.query.conflicts_with(&systems[existing_idx].query));
+    Ok(stages
+        .into_iter()
+        .map(|system_indices| Stage { system_indices })
+        .collect())
+}
 
-            if !conflicts {
-                stage.system_indices.push(sys_idx);
-                placed = true;
-                break;
+/// Builds the `a -> b` dependency edges (`b.order_after` names `a`, or
+/// `a.order_before` names `b`) that [`engine_component::pack_into_stages`]
+/// treats as "`b` must run no earlier than one past `a`'s actual stage".
+fn order_edges(systems: &[RegisteredSystem]) -> Vec<(usize, usize)> {
+    let index_of: HashMap<&str, usize> = systems
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.name.as_str(), i))
+        .collect();
+
+    let mut edges = Vec::new();
+    for (i, system) in systems.iter().enumerate() {
+        for pred_name in &system.order_after {
+            if let Some(&pred_idx) = index_of.get(pred_name.as_str()) {
+                edges.push((pred_idx, i));
             }
         }
-
-        if !placed {
-            stages.push(Stage {
-                system_indices: vec![sys_idx],
-            });
+        for succ_name in &system.order_before {
+            if let Some(&succ_idx) = index_of.get(succ_name.as_str()) {
+                edges.push((i, succ_idx));
+            }
         }
     }
+    edges
+}
+
+/// The set of components a stage must broadcast across the network barrier
+/// after it finishes — i.e. the components it wrote that some later stage
+/// (or an external subscriber) actually reads.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StageBroadcastPlan {
+    /// Component types this stage must publish once it completes.
+    pub broadcast: BTreeSet<ComponentTypeId>,
+}
+
+/// Computes a [`StageBroadcastPlan`] for each stage via backward live-variable
+/// analysis, so the net layer only encodes/publishes components that are
+/// actually consumed later.
+///
+/// For each stage `i`, `live_out[i]` is the union of the read sets of all
+/// stages after `i`, minus components fully overwritten before any
+/// downstream read, plus `externally_observed` (components an outside
+/// subscriber — e.g. an ad-hoc query or the debug inspector — cares about
+/// every tick). The broadcast set for stage `i` is `writes(i) ∩ live_out[i]`:
+/// a component written in stage `i` but never read downstream, and not
+/// externally observed, never needs to cross the barrier.
+#[must_use]
+pub fn compute_broadcast_plans(
+    systems: &[RegisteredSystem],
+    stages: &[Stage],
+    externally_observed: &[ComponentTypeId],
+) -> Vec<StageBroadcastPlan> {
+    let stage_reads: Vec<BTreeSet<ComponentTypeId>> = stages
+        .iter()
+        .map(|stage| {
+            stage
+                .system_indices
+                .iter()
+                .flat_map(|&idx| systems[idx].query.reads.iter().copied())
+                .collect()
+        })
+        .collect();
+    let stage_writes: Vec<BTreeSet<ComponentTypeId>> = stages
+        .iter()
+        .map(|stage| {
+            stage
+                .system_indices
+                .iter()
+                .flat_map(|&idx| systems[idx].query.writes.iter().copied())
+                .collect()
+        })
+        .collect();
+
+    let external: BTreeSet<ComponentTypeId> = externally_observed.iter().copied().collect();
+    let mut live_out: Vec<BTreeSet<ComponentTypeId>> = vec![BTreeSet::new(); stages.len()];
+
+    // Walk backward: live_out[i] = live_in[i+1], live_in[i] = reads[i] ∪ (live_out[i] - writes[i]).
+    let mut next_live_in = external.clone();
+    for i in (0..stages.len()).rev() {
+        live_out[i] = next_live_in.clone();
+        let live_in: BTreeSet<ComponentTypeId> = stage_reads[i]
+            .iter()
+            .copied()
+            .chain(live_out[i].difference(&stage_writes[i]).copied())
+            .collect();
+        next_live_in = live_in;
+    }
 
     stages
+        .iter()
+        .enumerate()
+        .map(|(i, _)| StageBroadcastPlan {
+            broadcast: stage_writes[i]
+                .intersection(&live_out[i])
+                .copied()
+                .collect(),
+        })
+        .collect()
+}
+
+/// The key used to group systems by phase. `None` is the default phase —
+/// systems with no assigned phase run every frame.
+pub type PhaseKey = Option<String>;
+
+/// A phase's computed stages paired with its tick rate.
+#[derive(Debug, Clone)]
+pub struct PhaseSchedule {
+    /// The execution stages for this phase, in order.
+    pub stages: Vec<Stage>,
+    /// The phase's tick rate in Hz, or `None` to run every frame.
+    pub hz: Option<f64>,
+}
+
+/// A complete multi-rate schedule: one [`PhaseSchedule`] per phase.
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    /// Per-phase stage lists and tick rates, keyed by phase name (`None`
+    /// for the default every-frame phase).
+    pub phases: HashMap<PhaseKey, PhaseSchedule>,
+}
+
+/// Groups systems by their assigned phase, computes stages independently
+/// within each phase, and pairs each phase's stages with its tick rate.
+///
+/// `phase_hz` supplies the tick rate for each named phase (e.g. parsed from
+/// `PhaseDef.hz`); a phase absent from the map runs every frame, same as
+/// the default `None` phase.
+///
+/// # Errors
+///
+/// Returns [`ScheduleError::OrderingCycle`] if any phase's systems have
+/// unsatisfiable `order_after`/`order_before` constraints.
+pub fn build_schedule(
+    systems: &[RegisteredSystem],
+    phase_hz: &HashMap<String, f64>,
+) -> Result<Schedule, ScheduleError> {
+    let mut by_phase: HashMap<PhaseKey, Vec<RegisteredSystem>> = HashMap::new();
+    for system in systems {
+        by_phase
+            .entry(system.phase.clone())
+            .or_default()
+            .push(system.clone());
+    }
+
+    let mut schedule = Schedule::default();
+    for (phase, phase_systems) in by_phase {
+        let stages = compute_stages(&phase_systems)?;
+        let hz = phase.as_ref().and_then(|name| phase_hz.get(name)).copied();
+        schedule.phases.insert(phase, PhaseSchedule { stages, hz });
+    }
+
+    Ok(schedule)
+}
+
+/// Drives phase execution at each phase's own cadence using a fixed-timestep
+/// accumulator, so e.g. a 60 Hz physics phase and a 10 Hz AI phase advance
+/// independently within the same world tick.
+#[derive(Debug, Clone, Default)]
+pub struct PhaseAccumulator {
+    /// Accumulated wall-clock time per phase, in seconds.
+    accumulated: HashMap<PhaseKey, f64>,
+}
+
+impl PhaseAccumulator {
+    /// Create a new accumulator with no accumulated time.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance the accumulator by `dt` seconds and return the phases that
+    /// should run this frame, in the schedule's iteration order. A phase
+    /// with no tick rate (`hz: None`) always runs once per call. A phase
+    /// with a tick rate runs zero or more times, carrying its remainder
+    /// forward.
+    pub fn advance<'a>(&mut self, schedule: &'a Schedule, dt: f64) -> Vec<(&'a PhaseKey, usize)> {
+        let mut due = Vec::new();
+        for (phase, phase_schedule) in &schedule.phases {
+            match phase_schedule.hz {
+                None => due.push((phase, 1)),
+                Some(hz) if hz > 0.0 => {
+                    let step = 1.0 / hz;
+                    let acc = self.accumulated.entry(phase.clone()).or_insert(0.0);
+                    *acc += dt;
+                    let mut runs = 0usize;
+                    while *acc >= step {
+                        *acc -= step;
+                        runs += 1;
+                    }
+                    if runs > 0 {
+                        due.push((phase, runs));
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        due
+    }
 }
 
 #[cfg(test)]
@@ -83,22 +422,19 @@ mod tests {
         for &w in writes {
             query = query.write(ComponentTypeId(w));
         }
-        RegisteredSystem {
-            name: name.to_string(),
-            query,
-        }
+        RegisteredSystem::new(name, query)
     }
 
     #[test]
     fn test_no_systems_no_stages() {
-        let stages = compute_stages(&[]);
+        let stages = compute_stages(&[]).unwrap();
         assert!(stages.is_empty());
     }
 
     #[test]
     fn test_single_system_one_stage() {
         let systems = vec![make_system("physics", &[1], &[2])];
-        let stages = compute_stages(&systems);
+        let stages = compute_stages(&systems).unwrap();
         assert_eq!(stages.len(), 1);
         assert_eq!(stages[0].system_indices, vec![0]);
     }
@@ -111,7 +447,7 @@ mod tests {
             make_system("physics", &[1], &[2]),
             make_system("ai", &[1], &[3]),
         ];
-        let stages = compute_stages(&systems);
+        let stages = compute_stages(&systems).unwrap();
         assert_eq!(
             stages.len(),
             1,
@@ -128,7 +464,7 @@ mod tests {
             make_system("physics", &[1], &[2]),
             make_system("movement", &[2], &[1]),
         ];
-        let stages = compute_stages(&systems);
+        let stages = compute_stages(&systems).unwrap();
         assert_eq!(
             stages.len(),
             2,
@@ -149,11 +485,191 @@ mod tests {
             make_system("ai", &[1], &[3]),
             make_system("movement", &[2], &[1]),
         ];
-        let stages = compute_stages(&systems);
+        let stages = compute_stages(&systems).unwrap();
         assert_eq!(stages.len(), 2);
         // Stage 1: Physics and AI (no conflict).
         assert_eq!(stages[0].system_indices, vec![0, 1]);
         // Stage 2: Movement (conflicts with both).
         assert_eq!(stages[1].system_indices, vec![2]);
     }
+
+    #[test]
+    fn test_order_after_forces_separate_stage_even_without_conflict() {
+        // "render" doesn't conflict with "input" on components, but must
+        // run strictly after it.
+        let mut render = make_system("render", &[9], &[10]);
+        render.order_after = vec!["input".to_string()];
+        let systems = vec![make_system("input", &[1], &[2]), render];
+
+        let stages = compute_stages(&systems).unwrap();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0].system_indices, vec![0]);
+        assert_eq!(stages[1].system_indices, vec![1]);
+    }
+
+    #[test]
+    fn test_order_before_is_equivalent_to_order_after() {
+        let mut input = make_system("input", &[1], &[2]);
+        input.order_before = vec!["render".to_string()];
+        let systems = vec![input, make_system("render", &[9], &[10])];
+
+        let stages = compute_stages(&systems).unwrap();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0].system_indices, vec![0]);
+        assert_eq!(stages[1].system_indices, vec![1]);
+    }
+
+    #[test]
+    fn test_ordering_cycle_is_an_error() {
+        let mut a = make_system("a", &[1], &[2]);
+        a.order_after = vec!["b".to_string()];
+        let mut b = make_system("b", &[3], &[4]);
+        b.order_after = vec!["a".to_string()];
+
+        let err = compute_stages(&[a, b]).unwrap_err();
+        assert!(matches!(err, ScheduleError::OrderingCycle(_)));
+    }
+
+    #[test]
+    fn test_order_after_predecessor_pushed_later_by_unrelated_conflict_still_orders_successor() {
+        // "z" and "a" both write Health(1), so they can't share a stage —
+        // "a" gets pushed into stage 1 even though it has no ordering
+        // constraints of its own (topological depth 0). "c" has no
+        // component conflict with anyone but declares order_after("a"), so
+        // it must land strictly after "a"'s *actual* stage (1), not "a"'s
+        // topological depth (0).
+        let z = make_system("z", &[], &[1]);
+        let a = make_system("a", &[], &[1]);
+        let mut c = make_system("c", &[], &[2]);
+        c.order_after = vec!["a".to_string()];
+
+        let systems = vec![z, a, c];
+        let stages = compute_stages(&systems).unwrap();
+
+        let stage_of = |name: &str| {
+            stages
+                .iter()
+                .position(|s| s.system_indices.iter().any(|&i| systems[i].name == name))
+                .unwrap()
+        };
+        assert_eq!(stage_of("z"), 0);
+        assert_eq!(stage_of("a"), 1, "a conflicts with z, so must move to stage 1");
+        assert_eq!(
+            stage_of("c"), 2,
+            "c must land strictly after a's actual stage (1), not a's topological depth (0)"
+        );
+    }
+
+    #[test]
+    fn test_build_schedule_groups_by_phase() {
+        let systems = vec![
+            make_system("physics", &[1], &[2]).with_phase("fixed_update"),
+            make_system("ai", &[3], &[4]).with_phase("ai_tick"),
+            make_system("hud", &[5], &[6]),
+        ];
+        let mut phase_hz = HashMap::new();
+        phase_hz.insert("fixed_update".to_string(), 60.0);
+        phase_hz.insert("ai_tick".to_string(), 10.0);
+
+        let schedule = build_schedule(&systems, &phase_hz).unwrap();
+        assert_eq!(schedule.phases.len(), 3);
+        assert_eq!(
+            schedule.phases[&Some("fixed_update".to_string())].hz,
+            Some(60.0)
+        );
+        assert_eq!(schedule.phases[&Some("ai_tick".to_string())].hz, Some(10.0));
+        assert_eq!(schedule.phases[&None].hz, None);
+    }
+
+    #[test]
+    fn test_phase_accumulator_runs_at_own_cadence() {
+        let systems = vec![
+            make_system("physics", &[1], &[2]).with_phase("fixed_update"),
+            make_system("ai", &[3], &[4]).with_phase("ai_tick"),
+        ];
+        let mut phase_hz = HashMap::new();
+        phase_hz.insert("fixed_update".to_string(), 60.0);
+        phase_hz.insert("ai_tick".to_string(), 10.0);
+        let schedule = build_schedule(&systems, &phase_hz).unwrap();
+
+        let mut accumulator = PhaseAccumulator::new();
+        // One 100ms frame: physics (60Hz, step ~16.7ms) should run ~6 times,
+        // ai (10Hz, step 100ms) should run exactly once.
+        let due = accumulator.advance(&schedule, 0.1);
+        let physics_runs = due
+            .iter()
+            .find(|(phase, _)| phase.as_deref() == Some("fixed_update"))
+            .map(|(_, n)| *n)
+            .unwrap();
+        let ai_runs = due
+            .iter()
+            .find(|(phase, _)| phase.as_deref() == Some("ai_tick"))
+            .map(|(_, n)| *n)
+            .unwrap();
+        assert_eq!(physics_runs, 6);
+        assert_eq!(ai_runs, 1);
+    }
+
+    #[test]
+    fn test_phase_accumulator_default_phase_runs_every_frame() {
+        let systems = vec![make_system("hud", &[5], &[6])];
+        let schedule = build_schedule(&systems, &HashMap::new()).unwrap();
+
+        let mut accumulator = PhaseAccumulator::new();
+        let due = accumulator.advance(&schedule, 0.001);
+        assert_eq!(due, vec![(&None, 1)]);
+    }
+
+    #[test]
+    fn test_broadcast_plan_drops_components_never_read_downstream() {
+        // Stage 0: physics writes Velocity(2) and Scratch(9).
+        // Stage 1: movement reads Velocity(2).
+        // Scratch(9) is never read again and not externally observed, so
+        // it should not appear in stage 0's broadcast set.
+        let systems = vec![
+            make_system("physics", &[1], &[2, 9]),
+            make_system("movement", &[2], &[1]),
+        ];
+        let stages = compute_stages(&systems).unwrap();
+        assert_eq!(stages.len(), 2);
+
+        let plans = compute_broadcast_plans(&systems, &stages, &[]);
+        assert_eq!(plans.len(), 2);
+        assert!(plans[0].broadcast.contains(&ComponentTypeId(2)));
+        assert!(!plans[0].broadcast.contains(&ComponentTypeId(9)));
+    }
+
+    #[test]
+    fn test_broadcast_plan_keeps_externally_observed_components() {
+        let systems = vec![make_system("physics", &[1], &[2])];
+        let stages = compute_stages(&systems).unwrap();
+
+        let plans = compute_broadcast_plans(&systems, &stages, &[ComponentTypeId(2)]);
+        assert!(plans[0].broadcast.contains(&ComponentTypeId(2)));
+    }
+
+    #[test]
+    fn test_conflict_detection_spans_multiple_words() {
+        // Component ids above 63 force the bitmask into a second word; make
+        // sure conflict detection still works across the word boundary.
+        let systems = vec![
+            make_system("physics", &[1], &[70]),
+            make_system("movement", &[70], &[1]),
+        ];
+        let stages = compute_stages(&systems).unwrap();
+        assert_eq!(
+            stages.len(),
+            2,
+            "conflicting systems on a >64 component id must still land in separate stages"
+        );
+    }
+
+    #[test]
+    fn test_broadcast_plan_empty_when_nothing_is_read_or_observed() {
+        let systems = vec![make_system("physics", &[1], &[2])];
+        let stages = compute_stages(&systems).unwrap();
+
+        let plans = compute_broadcast_plans(&systems, &stages, &[]);
+        assert!(plans[0].broadcast.is_empty());
+    }
 }
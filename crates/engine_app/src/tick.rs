@@ -16,18 +16,24 @@
 
 #![allow(dead_code)]
 
+use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use futures::StreamExt;
+use thiserror::Error;
+use tokio::sync::oneshot;
 use tracing::{debug, info, warn};
 
+use engine_component::{ArchetypeTable, ComponentTypeId, Entity, QueryDescriptor, Tick};
 use engine_net::NatsConnection;
 use engine_net::messages::{
-    self, ComponentShard, DataDone, SystemSchedule, SystemUnregister, TickAck,
+    self, ComponentShard, DataDone, EntityCommand, EntityCommandBatch, QueryUpdate, SystemSchedule,
+    SystemUnregister, TickAck,
 };
+use engine_net::trace::{self, TraceContext};
 
-use crate::registry::SystemRegistry;
+use crate::registry::{SystemId, SystemRegistry};
 use crate::scheduler::{self, RegisteredSystem, Stage};
 use crate::world::World;
 
@@ -40,6 +46,136 @@ pub(crate) enum PendingSystemChange {
     Unregister { name: String, instance_id: String },
 }
 
+/// An in-flight operation accepted by [`TickLoop::submit`], applied at the
+/// next [`poll_completions`](TickLoop::poll_completions) fixed point.
+///
+/// Modeled on tokio-uring's submission/completion split: decode/IO for a
+/// network shard or a system register/unregister can happen on its own
+/// schedule, but the op itself only takes effect at a deterministic tick
+/// boundary, alongside everything else the simulation step does.
+pub(crate) enum Op {
+    /// Merge a decoded component shard into the world, as
+    /// [`TickLoop::merge_shard`] would.
+    ApplyShard(ComponentShard),
+    /// Apply a queued system register/unregister change, as
+    /// [`TickLoop::enqueue_change`] would.
+    SystemChange(PendingSystemChange),
+}
+
+/// Produces a submitted op's completion value from the raw outcome of
+/// applying it — rows merged for an [`Op::ApplyShard`], the registry's
+/// post-change system count for an [`Op::SystemChange`].
+pub(crate) type OutputTransform<T> = Box<dyn FnOnce(usize) -> T + Send>;
+
+/// A handle to an op submitted via [`TickLoop::submit`].
+///
+/// Resolves once [`poll_completions`](TickLoop::poll_completions) applies
+/// the op at a tick boundary. Dropping the handle without awaiting it is
+/// harmless — the op still applies, its result is just discarded.
+pub(crate) struct OpHandle<T> {
+    rx: oneshot::Receiver<T>,
+}
+
+impl<T> OpHandle<T> {
+    /// Wait for the op to be applied and return its completion value.
+    ///
+    /// Returns `None` if the tick loop was dropped before applying the op.
+    pub(crate) async fn wait(self) -> Option<T> {
+        self.rx.await.ok()
+    }
+}
+
+/// Tracked state for one standing [`QuerySubscribe`](engine_net::messages::QuerySubscribe)
+/// subscription.
+///
+/// [`TickLoop::diff_subscriptions`] recomputes `matched` every tick and diffs
+/// it against the previous value to decide what goes in a [`QueryUpdate`]'s
+/// `asserted`/`retracted` lists.
+struct QuerySubscription {
+    /// The query this subscription tracks.
+    query: QueryDescriptor,
+    /// Entities that matched as of the last diff.
+    matched: HashSet<Entity>,
+    /// The tick `matched` was last computed at, used to tell whether a
+    /// still-matching entity's data changed since then.
+    last_tick: Tick,
+}
+
+/// How [`TickLoop::merge_shard`] resolves a shard write that conflicts with
+/// the local row's existing data (i.e. the row has a `changed_tick` the
+/// incoming `origin_tick` doesn't clearly postdate).
+///
+/// An authoritative coordinator with a single writer per entity can ignore
+/// this entirely — conflicts only arise in peer-to-peer topologies where
+/// more than one instance may edit the same row in the same tick.
+pub enum MergePolicy {
+    /// Apply the write only if `origin_tick` is newer than the local row's
+    /// `changed_tick`; ties are broken by comparing `instance_id` (the
+    /// lexicographically greater instance wins). This is the default.
+    LastWriterWins,
+    /// Reject the write outright whenever the local row has already been
+    /// changed at or after `origin_tick`, even on a tie. Useful when a
+    /// stale write should be surfaced as a conflict rather than silently
+    /// resolved.
+    RejectStale,
+    /// Defer to a user-supplied predicate: `(local_tick, local_instance,
+    /// incoming_tick, incoming_instance) -> accept?`.
+    Custom(Box<dyn Fn(Tick, &str, Tick, &str) -> bool + Send + Sync>),
+}
+
+impl std::fmt::Debug for MergePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LastWriterWins => write!(f, "MergePolicy::LastWriterWins"),
+            Self::RejectStale => write!(f, "MergePolicy::RejectStale"),
+            Self::Custom(_) => write!(f, "MergePolicy::Custom(..)"),
+        }
+    }
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        Self::LastWriterWins
+    }
+}
+
+impl MergePolicy {
+    /// Returns `true` if the incoming write should be applied over the
+    /// local row.
+    fn accepts(&self, local_tick: Tick, local_instance: &str, incoming_tick: Tick, incoming_instance: &str) -> bool {
+        match self {
+            Self::LastWriterWins => {
+                if incoming_tick.is_newer_than(local_tick) {
+                    true
+                } else if local_tick.is_newer_than(incoming_tick) {
+                    false
+                } else {
+                    // Same tick — break the tie deterministically.
+                    incoming_instance > local_instance
+                }
+            }
+            Self::RejectStale => incoming_tick.is_newer_than(local_tick),
+            Self::Custom(f) => f(local_tick, local_instance, incoming_tick, incoming_instance),
+        }
+    }
+}
+
+/// Errors produced by [`TickLoop::run_system_by_id`] and
+/// [`TickLoop::run_system_once`].
+#[derive(Debug, Error)]
+pub enum RunError {
+    /// No registered system instance has this ID.
+    #[error("no registered system instance with id {0:?}")]
+    UnknownInstance(String),
+    /// No system was registered for push-based execution with this
+    /// `SystemId`, or it has since been unregistered.
+    #[error("no system registered for push-based execution with id {0:?}")]
+    UnknownSystemId(SystemId),
+    /// A NATS communication error occurred.
+    #[error(transparent)]
+    Net(#[from] engine_net::NetError),
+}
+
 /// Configuration for the coordinator tick loop.
 #[derive(Debug, Clone)]
 pub struct TickConfig {
@@ -76,6 +212,18 @@ pub struct TickLoop {
     stages_dirty: bool,
     /// Queue of pending register/unregister changes applied before each tick.
     pending_changes: Vec<PendingSystemChange>,
+    /// Ops submitted via [`TickLoop::submit`], applied in submission order by
+    /// [`TickLoop::poll_completions`] at the next tick boundary.
+    submitted_ops: Vec<Box<dyn FnOnce(&mut TickLoop) + Send>>,
+    /// The last tick each registered system was sent component data for.
+    /// A system with `Tick::ZERO` has never been sent data and gets a full
+    /// snapshot rather than a diff.
+    last_seen: HashMap<String, Tick>,
+    /// How `merge_shard` resolves writes that conflict with a row's existing
+    /// `changed_tick`.
+    merge_policy: MergePolicy,
+    /// Standing reactive query subscriptions, keyed by `subscription_id`.
+    subscriptions: HashMap<String, QuerySubscription>,
 }
 
 impl TickLoop {
@@ -91,9 +239,19 @@ impl TickLoop {
             systems: Vec::new(),
             stages_dirty: true,
             pending_changes: Vec::new(),
+            submitted_ops: Vec::new(),
+            last_seen: HashMap::new(),
+            merge_policy: MergePolicy::default(),
+            subscriptions: HashMap::new(),
         }
     }
 
+    /// Set the conflict-resolution policy used by `merge_shard`. Defaults to
+    /// [`MergePolicy::LastWriterWins`].
+    pub fn set_merge_policy(&mut self, policy: MergePolicy) {
+        self.merge_policy = policy;
+    }
+
     /// Returns the current tick counter.
     #[must_use]
     pub fn tick_id(&self) -> u64 {
@@ -117,6 +275,12 @@ impl TickLoop {
         &self.registry
     }
 
+    /// Returns the registered systems backing the current stage list — the
+    /// same indices as each [`Stage::system_indices`].
+    pub(crate) fn registered_systems(&self) -> &[RegisteredSystem] {
+        &self.systems
+    }
+
     /// Returns a mutable reference to the system registry.
     pub fn registry_mut(&mut self) -> &mut SystemRegistry {
         self.stages_dirty = true;
@@ -128,13 +292,16 @@ impl TickLoop {
         self.systems = self
             .registry
             .iter()
-            .map(|info| RegisteredSystem {
-                name: info.name.clone(),
-                query: info.query.clone(),
-            })
+            .map(|info| RegisteredSystem::new(info.name.clone(), info.query.clone()))
             .collect();
 
-        self.stages = scheduler::compute_stages(&self.systems);
+        self.stages = match scheduler::compute_stages(&self.systems) {
+            Ok(stages) => stages,
+            Err(err) => {
+                warn!(tick_id = self.tick_id, %err, "failed to compute stages, keeping previous stages");
+                return;
+            }
+        };
         self.stages_dirty = false;
 
         info!(
@@ -150,6 +317,56 @@ impl TickLoop {
         self.pending_changes.push(change);
     }
 
+    /// Accept an in-flight operation — a decoded shard apply or a system
+    /// register/unregister — and return a handle that resolves once
+    /// [`poll_completions`](Self::poll_completions) applies it.
+    ///
+    /// Submission only queues the op; it has no effect on the world or the
+    /// registry until the next `poll_completions` call, which happens once
+    /// per tick at a fixed point. This decouples decode/IO latency (e.g.
+    /// waiting on a NATS payload) from the deterministic simulation step,
+    /// while still applying ops in the order they were submitted.
+    pub(crate) fn submit<T: Send + 'static>(
+        &mut self,
+        op: Op,
+        transform: OutputTransform<T>,
+    ) -> OpHandle<T> {
+        let (tx, rx) = oneshot::channel();
+        self.submitted_ops.push(Box::new(move |tick_loop: &mut TickLoop| {
+            let raw = match op {
+                Op::ApplyShard(shard) => tick_loop.merge_shard(&shard),
+                Op::SystemChange(change) => {
+                    tick_loop.enqueue_change(change);
+                    tick_loop.apply_pending_changes();
+                    tick_loop.registry.system_count()
+                }
+            };
+            let _ = tx.send(transform(raw));
+        }));
+        OpHandle { rx }
+    }
+
+    /// Drain and apply every op submitted via [`submit`](Self::submit) since
+    /// the last call, in submission order.
+    ///
+    /// Called once per tick, at the same fixed point as
+    /// [`apply_pending_changes`](Self::apply_pending_changes) — so ops
+    /// submitted mid-tick from decode/IO work still land on a deterministic
+    /// tick boundary rather than whenever their data happened to arrive.
+    /// Returns the number of ops applied.
+    pub(crate) fn poll_completions(&mut self) -> usize {
+        if self.submitted_ops.is_empty() {
+            return 0;
+        }
+
+        let ops: Vec<_> = self.submitted_ops.drain(..).collect();
+        let count = ops.len();
+        for op in ops {
+            op(self);
+        }
+        count
+    }
+
     /// Apply all pending register/unregister changes to the registry.
     ///
     /// This is called once at the start of each tick, ensuring that systems
@@ -168,6 +385,11 @@ impl TickLoop {
                         instance = descriptor.instance_id,
                         "applying queued registration"
                     );
+                    // A newly registered system has never seen any data, so
+                    // it gets a full snapshot on its first shard.
+                    self.last_seen
+                        .entry(descriptor.name.clone())
+                        .or_insert(Tick::ZERO);
                     self.registry.register(descriptor);
                     self.stages_dirty = true;
                 }
@@ -178,6 +400,9 @@ impl TickLoop {
                         "applying queued unregistration"
                     );
                     if self.registry.unregister_instance(&name, &instance_id) {
+                        if self.registry.get(&name).is_none() {
+                            self.last_seen.remove(&name);
+                        }
                         self.stages_dirty = true;
                     } else {
                         warn!(
@@ -206,9 +431,12 @@ impl TickLoop {
     /// version advances state locally and is useful for testing.
     pub fn tick(&mut self) {
         self.tick_id += 1;
+        self.world.advance_tick();
 
         // Apply any queued register/unregister changes before running.
         self.apply_pending_changes();
+        // Apply any ops submitted via `submit` since the last tick.
+        self.poll_completions();
 
         if self.stages_dirty {
             self.recompute_stages();
@@ -271,21 +499,37 @@ impl TickLoop {
     /// Run one NATS-connected tick.
     ///
     /// For each stage:
-    ///   1. Subscribe to `component.changed.<system>` for each system.
+    ///   1. Subscribe to `component.changed.<system>` and
+    ///      `entity.commands.<system>` for each system.
     ///   2. Publish `component.set.<system>` shards, a `DataDone` sentinel,
     ///      and `system.schedule.<system>`.
     ///   3. Drain `component.changed.<system>` until `ChangesDone` sentinels
-    ///      arrive from every instance, merging shards into the world.
+    ///      arrive from every instance, merging shards into the world, and
+    ///      drain each instance's `EntityCommandBatch` from
+    ///      `entity.commands.<system>`, buffering it for later.
     ///   4. Wait for `coord.tick.done` acks from all instances.
+    ///
+    /// Once every stage has acked, every buffered `EntityCommandBatch` is
+    /// replayed against the world in the order collected — deferred
+    /// structural changes apply only after the whole tick's component writes
+    /// have settled, never interleaved with them.
     async fn tick_async(
         &mut self,
         conn: &NatsConnection,
         ack_sub: &mut async_nats::Subscriber,
     ) -> Result<()> {
         self.tick_id += 1;
+        self.world.advance_tick();
+
+        // Root span for this tick's whole fan-out, so a tracing backend can
+        // reconstruct the causal chain from the coordinator through every
+        // system and back without any of that data living in the payload.
+        let tick_trace = TraceContext::new_root();
 
         // Apply any queued register/unregister changes before running.
         self.apply_pending_changes();
+        // Apply any ops submitted via `submit` since the last tick.
+        self.poll_completions();
 
         if self.stages_dirty {
             self.recompute_stages();
@@ -301,6 +545,11 @@ impl TickLoop {
             return Ok(());
         }
 
+        // Entity command batches collected from every stage this tick,
+        // replayed against the world once every stage has acked — see the
+        // doc comment above.
+        let mut pending_entity_commands: Vec<EntityCommandBatch> = Vec::new();
+
         // Iterate stages sequentially.
         let stage_count = self.stages.len();
         for stage_idx in 0..stage_count {
@@ -328,44 +577,91 @@ impl TickLoop {
             // Count total acks expected for this stage.
             let total_acks: usize = stage_systems.iter().map(|(_, count)| *count).sum();
 
-            // 1. Subscribe to component.changed.<system> for each system
-            //    BEFORE publishing schedules, so we don't miss any messages.
+            // 1. Subscribe to component.changed.<system> and
+            //    entity.commands.<system> for each system BEFORE publishing
+            //    schedules, so we don't miss any messages.
             let mut changed_subs: Vec<(String, usize, async_nats::Subscriber)> = Vec::new();
+            let mut commands_subs: Vec<(String, usize, async_nats::Subscriber)> = Vec::new();
             for (system_name, instance_count) in &stage_systems {
                 let changed_subject = engine_net::subjects::component_changed(system_name);
                 let sub = conn.subscribe(&changed_subject).await?;
                 changed_subs.push((system_name.clone(), *instance_count, sub));
+
+                let commands_subject = engine_net::subjects::entity_commands(system_name);
+                let commands_sub = conn.subscribe(&commands_subject).await?;
+                commands_subs.push((system_name.clone(), *instance_count, commands_sub));
             }
 
             // 2. Publish component data shards, data-done sentinel, and schedule.
             for (system_name, _) in &stage_systems {
-                // Publish component.set.<system> — for now send all matching
-                // archetype data as ComponentShards. Systems receive the full
-                // data set for the component types they declared.
+                // Publish component.set.<system> — only rows that changed
+                // since this system last saw the world, unless it has never
+                // been sent data before (full snapshot).
                 let set_subject = engine_net::subjects::component_set(system_name);
 
                 let sys_info = self.registry.get(system_name);
                 if let Some(info) = sys_info {
                     let required = info.query.required_types();
                     let matching = self.world.matching_archetypes(&required);
+                    let last_seen = self
+                        .last_seen
+                        .get(system_name)
+                        .copied()
+                        .unwrap_or(Tick::ZERO);
+                    let full_snapshot = last_seen == Tick::ZERO;
 
                     for &arch_id in &matching {
                         if let Some(table) = self.world.archetype(arch_id) {
-                            // Send one ComponentShard per component type.
+                            // Send one ComponentShard per component type,
+                            // containing only the rows that changed (or
+                            // every row, for a system's first snapshot).
                             for col in &table.columns {
+                                let mut entities = Vec::new();
+                                let mut data = Vec::new();
+                                let mut changed_ticks = Vec::new();
+                                let mut added_ticks = Vec::new();
+                                for (i, &entity) in table.entities.iter().enumerate() {
+                                    let changed = full_snapshot
+                                        || col
+                                            .changed_tick(i)
+                                            .is_some_and(|t| t.is_newer_than(last_seen));
+                                    if changed {
+                                        entities.push(entity);
+                                        let bytes = col.get_raw(i).map(|b| b.to_vec()).unwrap_or_default();
+                                        data.push(serde_bytes::ByteBuf::from(bytes));
+                                        changed_ticks.push(col.changed_tick(i).unwrap_or(Tick::ZERO));
+                                        added_ticks.push(col.added_tick(i).unwrap_or(Tick::ZERO));
+                                    }
+                                }
+
+                                if entities.is_empty() {
+                                    continue;
+                                }
+
                                 let shard = ComponentShard {
                                     component_type: col.type_id,
-                                    entities: table.entities.clone(),
-                                    data: (0..table.entities.len())
-                                        .map(|i| {
-                                            col.get_raw(i).map(|b| b.to_vec()).unwrap_or_default()
-                                        })
-                                        .collect(),
+                                    entities,
+                                    data,
+                                    origin_tick: self.world.current_tick(),
+                                    instance_id: String::new(),
+                                    changed_ticks,
+                                    added_ticks,
+                                    layout_version: 0,
+                                    producing_system: String::new(),
                                 };
-                                conn.publish(&set_subject, &shard).await?;
+                                let mut headers = async_nats::HeaderMap::new();
+                                headers.insert(
+                                    messages::headers::SCHEMA_VERSION,
+                                    shard.layout_version.to_string(),
+                                );
+                                conn.publish_with_headers(&set_subject, headers, &shard)
+                                    .await?;
                             }
                         }
                     }
+
+                    self.last_seen
+                        .insert(system_name.clone(), self.world.current_tick());
                 }
 
                 // Publish a DataDone sentinel so the system knows all data
@@ -378,13 +674,18 @@ impl TickLoop {
                 conn.publish_with_headers(&set_subject, headers, &data_done)
                     .await?;
 
-                // Publish the schedule message to trigger execution.
+                // Publish the schedule message to trigger execution, with
+                // this tick's traceparent so the system can continue the
+                // same trace.
                 let schedule = SystemSchedule {
                     tick_id: self.tick_id,
                     shard_range: None,
                 };
                 let subject = engine_net::subjects::system_schedule(system_name);
-                conn.publish(&subject, &schedule).await?;
+                let mut headers = async_nats::HeaderMap::new();
+                trace::inject(&mut headers, &tick_trace, None);
+                conn.publish_with_headers(&subject, headers, &schedule)
+                    .await?;
             }
 
             // 3. Collect changed component data from systems.
@@ -455,6 +756,55 @@ impl TickLoop {
                 drop(changed_sub);
             }
 
+            // 3b. Drain entity.commands.<system> — exactly one
+            //     `EntityCommandBatch` per instance (runner publishes one
+            //     every tick, even when empty), so there's no sentinel to
+            //     wait for, just a known count.
+            for (system_name, instance_count, mut commands_sub) in commands_subs {
+                let mut received = 0usize;
+
+                while received < instance_count {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        warn!(
+                            tick_id = self.tick_id,
+                            stage = stage_idx,
+                            system = system_name,
+                            expected = instance_count,
+                            received,
+                            "entity-commands timeout — proceeding with partial data"
+                        );
+                        break;
+                    }
+
+                    match tokio::time::timeout(remaining, commands_sub.next()).await {
+                        Ok(Some(msg)) => {
+                            received += 1;
+                            if let Ok(batch) =
+                                engine_net::decode::<EntityCommandBatch>(msg.payload.as_ref())
+                                && !batch.commands.is_empty()
+                            {
+                                pending_entity_commands.push(batch);
+                            }
+                        }
+                        Ok(None) => break, // subscriber closed
+                        Err(_) => {
+                            warn!(
+                                tick_id = self.tick_id,
+                                stage = stage_idx,
+                                system = system_name,
+                                expected = instance_count,
+                                received,
+                                "entity-commands timeout — proceeding with partial data"
+                            );
+                            break;
+                        }
+                    }
+                }
+                // Unsubscribe by dropping.
+                drop(commands_sub);
+            }
+
             // 4. Wait for acks from all system instances in this stage.
             if total_acks > 0 {
                 let mut acks_received = 0usize;
@@ -505,27 +855,500 @@ impl TickLoop {
             debug!(tick_id = self.tick_id, stage = stage_idx, "stage complete");
         }
 
+        // Replay every system's deferred structural changes, in the order
+        // collected across all stages, now that every system for the tick
+        // has acked.
+        for batch in &pending_entity_commands {
+            self.apply_entity_commands(batch);
+        }
+
+        // Diff standing reactive query subscriptions against the world state
+        // this tick settled into, and publish whatever changed.
+        for update in self.diff_subscriptions() {
+            let subject = engine_net::subjects::query_update(&update.subscription_id);
+            conn.publish(&subject, &update).await?;
+        }
+
         debug!(tick_id = self.tick_id, "tick complete");
         Ok(())
     }
 
     /// Merge a changed component shard back into the canonical world state.
     ///
-    /// For each entity in the shard, find it in the world and overwrite the
-    /// corresponding column data.
-    fn merge_shard(&mut self, shard: &ComponentShard) {
+    /// For each entity in the shard, find it in the world and — if
+    /// `self.merge_policy` accepts the write given the shard's `origin_tick`
+    /// versus the row's existing `changed_tick` — overwrite the column data
+    /// and stamp the row with the current tick and the shard's
+    /// `instance_id`. Conflicting writes that the policy rejects are
+    /// dropped, leaving the row as-is.
+    ///
+    /// Returns the number of rows actually merged, used as the raw
+    /// completion value for an [`Op::ApplyShard`] submitted via
+    /// [`submit`](Self::submit).
+    fn merge_shard(&mut self, shard: &ComponentShard) -> usize {
+        let current_tick = self.world.current_tick();
+        let mut merged = 0usize;
         for (i, &entity) in shard.entities.iter().enumerate() {
             if let Some(arch_id) = self.world.entity_archetype(entity)
                 && let Some(table) = self.world.archetype_mut(arch_id)
                 && let Some(col_idx) = table.column_index(shard.component_type)
                 && let Some(row) = table.entity_row(entity)
                 && let Some(bytes) = shard.data.get(i)
-                && let Some(dst) = table.columns[col_idx].get_raw_mut(row)
             {
-                let copy_len = dst.len().min(bytes.len());
-                dst[..copy_len].copy_from_slice(&bytes[..copy_len]);
+                let col = &table.columns[col_idx];
+                let local_tick = col.changed_tick(row).unwrap_or(Tick::ZERO);
+                let local_instance = col.changed_by(row).unwrap_or("").to_string();
+
+                let accepted = self.merge_policy.accepts(
+                    local_tick,
+                    &local_instance,
+                    shard.origin_tick,
+                    &shard.instance_id,
+                );
+                if !accepted {
+                    continue;
+                }
+
+                let col = &mut table.columns[col_idx];
+                if let Some(dst) = col.get_raw_mut(row) {
+                    let copy_len = dst.len().min(bytes.len());
+                    dst[..copy_len].copy_from_slice(&bytes[..copy_len]);
+                    col.changed_ticks[row] = current_tick;
+                    col.changed_by[row] = shard.instance_id.clone();
+                    merged += 1;
+                }
+            }
+        }
+        merged
+    }
+
+    /// Replay one system's recorded deferred structural changes against the
+    /// world, in the order the system recorded them.
+    ///
+    /// Unlike [`merge_shard`](Self::merge_shard), there's no conflicting
+    /// write to resolve — a command is a direct request to mutate the
+    /// authoritative world state, applied once every system for the tick
+    /// has acked so it never interleaves with that tick's component writes.
+    fn apply_entity_commands(&mut self, batch: &EntityCommandBatch) {
+        for command in &batch.commands {
+            match command {
+                EntityCommand::Spawn {
+                    component_types,
+                    component_data,
+                    component_sizes,
+                } => {
+                    let data: Vec<Vec<u8>> =
+                        component_data.iter().map(|bytes| bytes.to_vec()).collect();
+                    self.world
+                        .spawn_with_data(component_types, &data, component_sizes);
+                }
+                EntityCommand::Despawn(entity) => {
+                    self.world.despawn(*entity);
+                }
+                EntityCommand::AddComponent {
+                    entity,
+                    component_type,
+                    data,
+                    item_size,
+                } => {
+                    self.world
+                        .add_component(*entity, *component_type, data, *item_size);
+                }
+                EntityCommand::RemoveComponent {
+                    entity,
+                    component_type,
+                } => {
+                    self.world.remove_component(*entity, *component_type);
+                }
+            }
+        }
+    }
+
+    /// Open a standing reactive query subscription.
+    ///
+    /// Computes the full set of entities currently matching `query` and
+    /// returns a [`QueryUpdate`] asserting every one of them — this initial
+    /// batch is what lets a subscription opened mid-tick still observe
+    /// everything that already matches, rather than waiting for the next
+    /// diff to discover it. Subsequent updates come from
+    /// [`diff_subscriptions`](Self::diff_subscriptions).
+    pub fn subscribe_query(&mut self, subscription_id: String, query: QueryDescriptor) -> QueryUpdate {
+        let required = query.required_types();
+        let matching = self.world.matching_archetypes(&required);
+
+        let mut matched = HashSet::new();
+        let mut asserted = Vec::new();
+        for &arch_id in &matching {
+            if let Some(table) = self.world.archetype(arch_id) {
+                for &entity in &table.entities {
+                    matched.insert(entity);
+                    asserted.push((entity, entity_component_shards(table, entity, &required)));
+                }
+            }
+        }
+
+        let last_tick = self.world.current_tick();
+        self.subscriptions.insert(
+            subscription_id.clone(),
+            QuerySubscription {
+                query,
+                matched,
+                last_tick,
+            },
+        );
+
+        QueryUpdate {
+            subscription_id,
+            asserted,
+            retracted: Vec::new(),
+            tick_id: self.tick_id,
+        }
+    }
+
+    /// End a standing reactive query subscription.
+    ///
+    /// Returns `true` if a subscription with this ID existed.
+    pub fn unsubscribe_query(&mut self, subscription_id: &str) -> bool {
+        self.subscriptions.remove(subscription_id).is_some()
+    }
+
+    /// Recompute every standing subscription's matching set and diff it
+    /// against its previous value.
+    ///
+    /// For each subscription: entities that newly match, or still match but
+    /// whose data changed since the last diff, go in that update's
+    /// `asserted` (with fresh component shards); entities that stopped
+    /// matching — including ones that were despawned outright — go in
+    /// `retracted`. A subscription with no change either way produces no
+    /// [`QueryUpdate`] at all, since there is nothing new to tell its
+    /// subscriber.
+    pub(crate) fn diff_subscriptions(&mut self) -> Vec<QueryUpdate> {
+        let tick_id = self.tick_id;
+        let current_tick = self.world.current_tick();
+        let world = &self.world;
+
+        let mut updates = Vec::new();
+        for (subscription_id, sub) in &mut self.subscriptions {
+            let required = sub.query.required_types();
+            let matching = world.matching_archetypes(&required);
+
+            let mut new_matched = HashSet::new();
+            let mut asserted = Vec::new();
+            for &arch_id in &matching {
+                let Some(table) = world.archetype(arch_id) else {
+                    continue;
+                };
+                for &entity in &table.entities {
+                    new_matched.insert(entity);
+                    let is_new = !sub.matched.contains(&entity);
+                    let changed = is_new
+                        || required.iter().any(|&ty| {
+                            table
+                                .column_index(ty)
+                                .zip(table.entity_row(entity))
+                                .and_then(|(col, row)| table.columns[col].changed_tick(row))
+                                .is_some_and(|t| t.is_newer_than(sub.last_tick))
+                        });
+                    if changed {
+                        asserted.push((entity, entity_component_shards(table, entity, &required)));
+                    }
+                }
+            }
+
+            let retracted: Vec<Entity> = sub.matched.difference(&new_matched).copied().collect();
+            sub.matched = new_matched;
+            sub.last_tick = current_tick;
+
+            if !asserted.is_empty() || !retracted.is_empty() {
+                updates.push(QueryUpdate {
+                    subscription_id: subscription_id.clone(),
+                    asserted,
+                    retracted,
+                    tick_id,
+                });
             }
         }
+        updates
+    }
+
+    /// Run a single system instance on demand, outside the normal per-tick
+    /// stage loop.
+    ///
+    /// Publishes a one-shot snapshot of the instance's queried component
+    /// data on its private invoke subjects, waits for its changed shards
+    /// and `ChangesDone` sentinel, and merges the results into the world
+    /// via [`merge_shard`](Self::merge_shard) — the same conflict
+    /// resolution used by the normal tick loop applies.
+    ///
+    /// This bypasses `stages()`/`compute_stages` entirely: it targets one
+    /// instance directly rather than broadcasting to every instance of a
+    /// system, and it does not advance the tick counter or touch
+    /// `last_seen`. Useful for ad-hoc invocations (e.g. an editor "run this
+    /// system once" action) that sit outside the fixed-timestep schedule.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunError::UnknownInstance`] if no registered system owns
+    /// `instance_id`, or [`RunError::Net`] if NATS communication fails.
+    pub async fn run_system_by_id(
+        &mut self,
+        conn: &NatsConnection,
+        instance_id: &str,
+    ) -> Result<(), RunError> {
+        let Some(info) = self.registry.find_instance(instance_id) else {
+            return Err(RunError::UnknownInstance(instance_id.to_string()));
+        };
+        let required = info.query.required_types();
+        let matching = self.world.matching_archetypes(&required);
+
+        let data_subject = engine_net::subjects::component_invoke(instance_id);
+        let changed_subject = engine_net::subjects::component_invoke_changed(instance_id);
+        let invoke_subject = engine_net::subjects::system_invoke(instance_id);
+
+        // Subscribe to changes BEFORE publishing, so we don't miss anything.
+        let mut changed_sub = conn.subscribe(&changed_subject).await?;
+
+        // Publish a full snapshot of the instance's queried data — an
+        // ad-hoc invocation has no `last_seen` baseline to diff against.
+        for &arch_id in &matching {
+            if let Some(table) = self.world.archetype(arch_id) {
+                for col in &table.columns {
+                    let mut entities = Vec::new();
+                    let mut data = Vec::new();
+                    let mut changed_ticks = Vec::new();
+                    let mut added_ticks = Vec::new();
+                    for (i, &entity) in table.entities.iter().enumerate() {
+                        entities.push(entity);
+                        let bytes = col.get_raw(i).map(|b| b.to_vec()).unwrap_or_default();
+                        data.push(serde_bytes::ByteBuf::from(bytes));
+                        changed_ticks.push(col.changed_tick(i).unwrap_or(Tick::ZERO));
+                        added_ticks.push(col.added_tick(i).unwrap_or(Tick::ZERO));
+                    }
+
+                    if entities.is_empty() {
+                        continue;
+                    }
+
+                    let shard = ComponentShard {
+                        component_type: col.type_id,
+                        entities,
+                        data,
+                        origin_tick: self.world.current_tick(),
+                        instance_id: String::new(),
+                        changed_ticks,
+                        added_ticks,
+                        layout_version: 0,
+                        producing_system: String::new(),
+                    };
+                    let mut headers = async_nats::HeaderMap::new();
+                    headers.insert(
+                        messages::headers::SCHEMA_VERSION,
+                        shard.layout_version.to_string(),
+                    );
+                    conn.publish_with_headers(&data_subject, headers, &shard)
+                        .await?;
+                }
+            }
+        }
+
+        let data_done = DataDone {
+            tick_id: self.tick_id,
+        };
+        let mut headers = async_nats::HeaderMap::new();
+        headers.insert(messages::headers::MSG_TYPE, messages::DATA_DONE_MSG_TYPE);
+        conn.publish_with_headers(&data_subject, headers, &data_done)
+            .await?;
+
+        // Trigger execution, with a fresh root trace for this ad-hoc
+        // invocation since it sits outside the normal tick fan-out.
+        let schedule = SystemSchedule {
+            tick_id: self.tick_id,
+            shard_range: None,
+        };
+        let mut headers = async_nats::HeaderMap::new();
+        trace::inject(&mut headers, &TraceContext::new_root(), None);
+        conn.publish_with_headers(&invoke_subject, headers, &schedule)
+            .await?;
+
+        // Drain changed shards until the ChangesDone sentinel or timeout.
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                warn!(instance_id, "ad-hoc invoke timed out waiting for changes-done");
+                break;
+            }
+
+            match tokio::time::timeout(remaining, changed_sub.next()).await {
+                Ok(Some(msg)) => {
+                    let is_sentinel = msg
+                        .headers
+                        .as_ref()
+                        .and_then(|h| h.get(messages::headers::MSG_TYPE))
+                        .is_some_and(|v| v.as_str() == messages::CHANGES_DONE_MSG_TYPE);
+
+                    if is_sentinel {
+                        break;
+                    } else if let Ok(shard) =
+                        engine_net::decode::<ComponentShard>(msg.payload.as_ref())
+                    {
+                        self.merge_shard(&shard);
+                    }
+                }
+                Ok(None) => break, // subscriber closed
+                Err(_) => {
+                    warn!(instance_id, "ad-hoc invoke timed out waiting for changes-done");
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run a system registered via [`SystemRegistry::register_once`] on
+    /// demand, against a caller-chosen set of entities rather than every
+    /// entity in the matching archetypes.
+    ///
+    /// This mirrors [`run_system_by_id`](Self::run_system_by_id) — it
+    /// publishes a one-shot snapshot on the target instance's ad-hoc
+    /// invoke subjects (the same one-off subjects used there, not the
+    /// recurring `system_schedule` subject), waits for the changed shards
+    /// and `ChangesDone` sentinel, and merges the results with
+    /// [`merge_shard`](Self::merge_shard) — but it is keyed by the stable
+    /// [`SystemId`] handed back from `register_once` rather than a
+    /// connected instance ID, and it only ships the rows for `entities`
+    /// instead of every row in the query's matching archetypes. Useful for
+    /// editor commands, migrations, and deterministic setup steps that
+    /// target a specific, known set of entities exactly once.
+    ///
+    /// Like `run_system_by_id`, this does not advance the tick counter or
+    /// touch `last_seen`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunError::UnknownSystemId`] if `id` is not registered, or
+    /// [`RunError::Net`] if NATS communication fails.
+    pub async fn run_system_once(
+        &mut self,
+        conn: &NatsConnection,
+        id: SystemId,
+        entities: &[Entity],
+    ) -> Result<(), RunError> {
+        let Some(once) = self.registry.get_once(id) else {
+            return Err(RunError::UnknownSystemId(id));
+        };
+        let required = once.query.required_types();
+        let instance_id = once.instance_id.clone();
+
+        let data_subject = engine_net::subjects::component_invoke(&instance_id);
+        let changed_subject = engine_net::subjects::component_invoke_changed(&instance_id);
+        let invoke_subject = engine_net::subjects::system_invoke(&instance_id);
+
+        // Subscribe to changes BEFORE publishing, so we don't miss anything.
+        let mut changed_sub = conn.subscribe(&changed_subject).await?;
+
+        // Publish only the rows for the caller's chosen entities, one shard
+        // per required component type.
+        for &type_id in &required {
+            let mut out_entities = Vec::new();
+            let mut data = Vec::new();
+            let mut changed_ticks = Vec::new();
+            let mut added_ticks = Vec::new();
+            for &entity in entities {
+                if let Some(arch_id) = self.world.entity_archetype(entity)
+                    && let Some(table) = self.world.archetype(arch_id)
+                    && let Some(col_idx) = table.column_index(type_id)
+                    && let Some(row) = table.entity_row(entity)
+                {
+                    out_entities.push(entity);
+                    let bytes = table.columns[col_idx]
+                        .get_raw(row)
+                        .map(|b| b.to_vec())
+                        .unwrap_or_default();
+                    data.push(serde_bytes::ByteBuf::from(bytes));
+                    changed_ticks.push(table.columns[col_idx].changed_tick(row).unwrap_or(Tick::ZERO));
+                    added_ticks.push(table.columns[col_idx].added_tick(row).unwrap_or(Tick::ZERO));
+                }
+            }
+
+            if out_entities.is_empty() {
+                continue;
+            }
+
+            let shard = ComponentShard {
+                component_type: type_id,
+                entities: out_entities,
+                data,
+                origin_tick: self.world.current_tick(),
+                instance_id: String::new(),
+                changed_ticks,
+                added_ticks,
+                layout_version: 0,
+                producing_system: String::new(),
+            };
+            let mut headers = async_nats::HeaderMap::new();
+            headers.insert(
+                messages::headers::SCHEMA_VERSION,
+                shard.layout_version.to_string(),
+            );
+            conn.publish_with_headers(&data_subject, headers, &shard)
+                .await?;
+        }
+
+        let data_done = DataDone {
+            tick_id: self.tick_id,
+        };
+        let mut headers = async_nats::HeaderMap::new();
+        headers.insert(messages::headers::MSG_TYPE, messages::DATA_DONE_MSG_TYPE);
+        conn.publish_with_headers(&data_subject, headers, &data_done)
+            .await?;
+
+        // Trigger execution, with a fresh root trace for this ad-hoc
+        // invocation since it sits outside the normal tick fan-out.
+        let schedule = SystemSchedule {
+            tick_id: self.tick_id,
+            shard_range: None,
+        };
+        let mut headers = async_nats::HeaderMap::new();
+        trace::inject(&mut headers, &TraceContext::new_root(), None);
+        conn.publish_with_headers(&invoke_subject, headers, &schedule)
+            .await?;
+
+        // Drain changed shards until the ChangesDone sentinel or timeout.
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                warn!(?id, "push-based invoke timed out waiting for changes-done");
+                break;
+            }
+
+            match tokio::time::timeout(remaining, changed_sub.next()).await {
+                Ok(Some(msg)) => {
+                    let is_sentinel = msg
+                        .headers
+                        .as_ref()
+                        .and_then(|h| h.get(messages::headers::MSG_TYPE))
+                        .is_some_and(|v| v.as_str() == messages::CHANGES_DONE_MSG_TYPE);
+
+                    if is_sentinel {
+                        break;
+                    } else if let Ok(shard) =
+                        engine_net::decode::<ComponentShard>(msg.payload.as_ref())
+                    {
+                        self.merge_shard(&shard);
+                    }
+                }
+                Ok(None) => break, // subscriber closed
+                Err(_) => {
+                    warn!(?id, "push-based invoke timed out waiting for changes-done");
+                    break;
+                }
+            }
+        }
+
+        Ok(())
     }
 
     /// Run the async NATS-connected tick loop.
@@ -559,6 +1382,14 @@ impl TickLoop {
             .subscribe(engine_net::subjects::SYSTEM_UNREGISTER)
             .await?;
 
+        // Subscribe to reactive query subscribe/unsubscribe requests.
+        let mut query_subscribe_sub = conn
+            .subscribe(engine_net::subjects::QUERY_SUBSCRIBE)
+            .await?;
+        let mut query_unsubscribe_sub = conn
+            .subscribe(engine_net::subjects::QUERY_UNSUBSCRIBE)
+            .await?;
+
         info!(
             tick_rate = self.config.tick_rate,
             max_ticks = self.config.max_ticks,
@@ -603,6 +1434,35 @@ impl TickLoop {
                 }
             }
 
+            // Open any new subscriptions right away — each gets its full
+            // initial assert batch immediately rather than waiting for the
+            // next tick's diff, so a subscription opened mid-tick still sees
+            // everything that already matches.
+            while let Ok(Some(msg)) =
+                tokio::time::timeout(Duration::ZERO, query_subscribe_sub.next()).await
+            {
+                if let Ok(req) = engine_net::decode::<engine_net::messages::QuerySubscribe>(
+                    msg.payload.as_ref(),
+                ) {
+                    info!(subscription_id = req.subscription_id, "query subscribed");
+                    let update = self.subscribe_query(req.subscription_id.clone(), req.query);
+                    let subject = engine_net::subjects::query_update(&req.subscription_id);
+                    conn.publish(&subject, &update).await?;
+                }
+            }
+
+            // Drain any pending unsubscriptions.
+            while let Ok(Some(msg)) =
+                tokio::time::timeout(Duration::ZERO, query_unsubscribe_sub.next()).await
+            {
+                if let Ok(req) = engine_net::decode::<engine_net::messages::QueryUnsubscribe>(
+                    msg.payload.as_ref(),
+                ) {
+                    info!(subscription_id = req.subscription_id, "query unsubscribed");
+                    self.unsubscribe_query(&req.subscription_id);
+                }
+            }
+
             // Run the tick (applies pending changes internally).
             self.tick_async(conn, &mut ack_sub).await?;
 
@@ -629,6 +1489,46 @@ impl TickLoop {
     }
 }
 
+/// Build one [`ComponentShard`] per type in `types` that `entity` currently
+/// has data for in `table`, each carrying just that single entity's row.
+///
+/// Used by [`TickLoop::subscribe_query`] and
+/// [`TickLoop::diff_subscriptions`] to attach an asserted entity's current
+/// data to a [`QueryUpdate`]. A type in `types` the entity has no column for
+/// (e.g. an optional the query didn't require) is silently skipped.
+fn entity_component_shards(
+    table: &ArchetypeTable,
+    entity: Entity,
+    types: &[ComponentTypeId],
+) -> Vec<ComponentShard> {
+    let Some(row) = table.entity_row(entity) else {
+        return Vec::new();
+    };
+
+    let mut shards = Vec::new();
+    for &type_id in types {
+        let Some(col_idx) = table.column_index(type_id) else {
+            continue;
+        };
+        let col = &table.columns[col_idx];
+        let Some(bytes) = col.get_raw(row) else {
+            continue;
+        };
+        shards.push(ComponentShard {
+            component_type: type_id,
+            entities: vec![entity],
+            data: vec![serde_bytes::ByteBuf::from(bytes.to_vec())],
+            origin_tick: col.changed_tick(row).unwrap_or(Tick::ZERO),
+            instance_id: String::new(),
+            changed_ticks: vec![col.changed_tick(row).unwrap_or(Tick::ZERO)],
+            added_ticks: vec![col.added_tick(row).unwrap_or(Tick::ZERO)],
+            layout_version: 0,
+            producing_system: String::new(),
+        });
+    }
+    shards
+}
+
 #[cfg(test)]
 mod tests {
     use engine_component::{ComponentTypeId, QueryDescriptor};
@@ -700,11 +1600,19 @@ mod tests {
             .columns[0]
             .push_raw(&[0u8; 4]);
 
-        // Merge a shard that overwrites with [1, 2, 3, 4].
+        // Merge a shard that overwrites with [1, 2, 3, 4]. Its origin_tick
+        // must be newer than the row's current Tick::ZERO for the default
+        // last-writer-wins policy to accept the write.
         let shard = ComponentShard {
             component_type: comp,
             entities: vec![entity],
-            data: vec![vec![1, 2, 3, 4]],
+            data: vec![serde_bytes::ByteBuf::from(vec![1, 2, 3, 4])],
+            origin_tick: Tick(1),
+            instance_id: "peer-a".to_string(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
         };
         tick_loop.merge_shard(&shard);
 
@@ -716,20 +1624,363 @@ mod tests {
     }
 
     #[test]
-    fn test_pending_register_applied_on_tick() {
+    fn test_merge_shard_stamps_current_tick() {
+        use std::collections::BTreeSet;
+
         let mut tick_loop = TickLoop::new(TickConfig::default());
+        tick_loop.tick(); // advance world to Tick(1)
 
-        // Enqueue a registration.
-        tick_loop.enqueue_change(PendingSystemChange::Register(SystemDescriptor {
-            name: "physics".to_string(),
-            query: QueryDescriptor::new()
-                .read(ComponentTypeId(1))
-                .write(ComponentTypeId(2)),
-            instance_id: "inst-1".to_string(),
-        }));
+        let comp = ComponentTypeId(42);
+        let mut types = BTreeSet::new();
+        types.insert(comp);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+        let arch_id = tick_loop.world().entity_archetype(entity).unwrap();
+        tick_loop
+            .world_mut()
+            .archetype_mut(arch_id)
+            .unwrap()
+            .columns[0]
+            .push_raw(&[0u8; 4]);
 
-        // Before the tick, registry should still be empty.
-        assert_eq!(tick_loop.registry().system_count(), 0);
+        let shard = ComponentShard {
+            component_type: comp,
+            entities: vec![entity],
+            data: vec![serde_bytes::ByteBuf::from(vec![1, 2, 3, 4])],
+            origin_tick: tick_loop.world().current_tick(),
+            instance_id: "peer-a".to_string(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        };
+        tick_loop.merge_shard(&shard);
+
+        let table = tick_loop.world().archetype(arch_id).unwrap();
+        let row = table.entity_row(entity).unwrap();
+        assert_eq!(
+            table.columns[0].changed_tick(row),
+            Some(tick_loop.world().current_tick())
+        );
+    }
+
+    #[test]
+    fn test_merge_shard_rejects_stale_write_under_last_writer_wins() {
+        use std::collections::BTreeSet;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        tick_loop.tick(); // advance world to Tick(1)
+
+        let comp = ComponentTypeId(42);
+        let mut types = BTreeSet::new();
+        types.insert(comp);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+        let arch_id = tick_loop.world().entity_archetype(entity).unwrap();
+        let current = tick_loop.world().current_tick();
+        tick_loop
+            .world_mut()
+            .archetype_mut(arch_id)
+            .unwrap()
+            .columns[0]
+            .push_raw_at(&[0u8; 4], current);
+
+        // The incoming shard's origin_tick (Tick::ZERO) is older than the
+        // row's current changed_tick, so the write should be dropped.
+        let shard = ComponentShard {
+            component_type: comp,
+            entities: vec![entity],
+            data: vec![serde_bytes::ByteBuf::from(vec![9, 9, 9, 9])],
+            origin_tick: Tick::ZERO,
+            instance_id: "peer-a".to_string(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        };
+        tick_loop.merge_shard(&shard);
+
+        let table = tick_loop.world().archetype(arch_id).unwrap();
+        let row = table.entity_row(entity).unwrap();
+        assert_eq!(table.columns[0].get_raw(row), Some(&[0u8; 4][..]));
+    }
+
+    #[test]
+    fn test_merge_shard_breaks_ties_by_instance_id() {
+        use std::collections::BTreeSet;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        tick_loop.tick();
+
+        let comp = ComponentTypeId(42);
+        let mut types = BTreeSet::new();
+        types.insert(comp);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+        let arch_id = tick_loop.world().entity_archetype(entity).unwrap();
+        let current = tick_loop.world().current_tick();
+        tick_loop
+            .world_mut()
+            .archetype_mut(arch_id)
+            .unwrap()
+            .columns[0]
+            .push_raw_at(&[0u8; 4], current);
+        tick_loop.world_mut().archetype_mut(arch_id).unwrap().columns[0].changed_by[0] =
+            "peer-a".to_string();
+
+        // Same tick, lexicographically smaller instance_id — rejected.
+        let shard = ComponentShard {
+            component_type: comp,
+            entities: vec![entity],
+            data: vec![serde_bytes::ByteBuf::from(vec![1, 1, 1, 1])],
+            origin_tick: current,
+            instance_id: "peer-0".to_string(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        };
+        tick_loop.merge_shard(&shard);
+        let table = tick_loop.world().archetype(arch_id).unwrap();
+        let row = table.entity_row(entity).unwrap();
+        assert_eq!(table.columns[0].get_raw(row), Some(&[0u8; 4][..]));
+
+        // Same tick, lexicographically greater instance_id — accepted.
+        let shard = ComponentShard {
+            component_type: comp,
+            entities: vec![entity],
+            data: vec![serde_bytes::ByteBuf::from(vec![2, 2, 2, 2])],
+            origin_tick: current,
+            instance_id: "peer-z".to_string(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        };
+        tick_loop.merge_shard(&shard);
+        let table = tick_loop.world().archetype(arch_id).unwrap();
+        assert_eq!(table.columns[0].get_raw(row), Some(&[2, 2, 2, 2][..]));
+    }
+
+    #[test]
+    fn test_apply_entity_commands_spawn() {
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        let comp = ComponentTypeId(42);
+
+        let batch = EntityCommandBatch {
+            tick_id: 1,
+            system: "spawner".to_string(),
+            commands: vec![EntityCommand::Spawn {
+                component_types: vec![comp],
+                component_data: vec![serde_bytes::ByteBuf::from(vec![1, 2, 3, 4])],
+                component_sizes: vec![4],
+            }],
+        };
+        tick_loop.apply_entity_commands(&batch);
+
+        let matching = tick_loop.world().matching_archetypes(&[comp]);
+        assert_eq!(matching.len(), 1);
+        let table = tick_loop.world().archetype(matching[0]).unwrap();
+        assert_eq!(table.entities.len(), 1);
+        assert_eq!(table.columns[0].get_raw(0), Some(&[1, 2, 3, 4][..]));
+    }
+
+    #[test]
+    fn test_apply_entity_commands_despawn() {
+        use std::collections::BTreeSet;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        let comp = ComponentTypeId(42);
+        let mut types = BTreeSet::new();
+        types.insert(comp);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+
+        let batch = EntityCommandBatch {
+            tick_id: 1,
+            system: "despawner".to_string(),
+            commands: vec![EntityCommand::Despawn(entity)],
+        };
+        tick_loop.apply_entity_commands(&batch);
+
+        assert!(!tick_loop.world().is_alive(entity));
+    }
+
+    #[test]
+    fn test_apply_entity_commands_preserves_recorded_order() {
+        use std::collections::BTreeSet;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        let comp_a = ComponentTypeId(1);
+        let comp_b = ComponentTypeId(2);
+        let mut types = BTreeSet::new();
+        types.insert(comp_a);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+        tick_loop
+            .world_mut()
+            .archetype_mut(tick_loop.world().entity_archetype(entity).unwrap())
+            .unwrap()
+            .columns[0]
+            .push_raw(&[0u8; 4]);
+
+        // Add comp_b, then remove comp_b — order matters: applying these in
+        // the other order would make the remove a no-op and leave the
+        // entity with comp_b.
+        let batch = EntityCommandBatch {
+            tick_id: 1,
+            system: "migrator".to_string(),
+            commands: vec![
+                EntityCommand::AddComponent {
+                    entity,
+                    component_type: comp_b,
+                    data: serde_bytes::ByteBuf::from(vec![9, 9, 9, 9]),
+                    item_size: 4,
+                },
+                EntityCommand::RemoveComponent {
+                    entity,
+                    component_type: comp_b,
+                },
+            ],
+        };
+        tick_loop.apply_entity_commands(&batch);
+
+        let arch_id = tick_loop.world().entity_archetype(entity).unwrap();
+        let table = tick_loop.world().archetype(arch_id).unwrap();
+        assert!(table.has_component(comp_a));
+        assert!(!table.has_component(comp_b));
+    }
+
+    #[test]
+    fn test_reject_stale_policy_rejects_ties() {
+        use std::collections::BTreeSet;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        tick_loop.set_merge_policy(MergePolicy::RejectStale);
+        tick_loop.tick();
+
+        let comp = ComponentTypeId(42);
+        let mut types = BTreeSet::new();
+        types.insert(comp);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+        let arch_id = tick_loop.world().entity_archetype(entity).unwrap();
+        let current = tick_loop.world().current_tick();
+        tick_loop
+            .world_mut()
+            .archetype_mut(arch_id)
+            .unwrap()
+            .columns[0]
+            .push_raw_at(&[0u8; 4], current);
+
+        // Same tick — RejectStale drops even on a tie, unlike LastWriterWins.
+        let shard = ComponentShard {
+            component_type: comp,
+            entities: vec![entity],
+            data: vec![serde_bytes::ByteBuf::from(vec![1, 1, 1, 1])],
+            origin_tick: current,
+            instance_id: "peer-z".to_string(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        };
+        tick_loop.merge_shard(&shard);
+        let table = tick_loop.world().archetype(arch_id).unwrap();
+        let row = table.entity_row(entity).unwrap();
+        assert_eq!(table.columns[0].get_raw(row), Some(&[0u8; 4][..]));
+    }
+
+    #[test]
+    fn test_custom_merge_policy() {
+        use std::collections::BTreeSet;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        // Always accept, regardless of ticks — a permissive custom policy.
+        tick_loop.set_merge_policy(MergePolicy::Custom(Box::new(|_, _, _, _| true)));
+
+        let comp = ComponentTypeId(42);
+        let mut types = BTreeSet::new();
+        types.insert(comp);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+        let arch_id = tick_loop.world().entity_archetype(entity).unwrap();
+        tick_loop
+            .world_mut()
+            .archetype_mut(arch_id)
+            .unwrap()
+            .columns[0]
+            .push_raw_at(&[0u8; 4], Tick(5));
+
+        // Stale origin_tick, but the custom policy accepts anyway.
+        let shard = ComponentShard {
+            component_type: comp,
+            entities: vec![entity],
+            data: vec![serde_bytes::ByteBuf::from(vec![7, 7, 7, 7])],
+            origin_tick: Tick::ZERO,
+            instance_id: "peer-a".to_string(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        };
+        tick_loop.merge_shard(&shard);
+        let table = tick_loop.world().archetype(arch_id).unwrap();
+        let row = table.entity_row(entity).unwrap();
+        assert_eq!(table.columns[0].get_raw(row), Some(&[7, 7, 7, 7][..]));
+    }
+
+    #[test]
+    fn test_new_registration_gets_last_seen_zero() {
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        tick_loop.enqueue_change(PendingSystemChange::Register(SystemDescriptor {
+            name: "physics".to_string(),
+            query: QueryDescriptor::new()
+                .read(ComponentTypeId(1))
+                .write(ComponentTypeId(2)),
+            instance_id: "inst-1".to_string(),
+        }));
+
+        tick_loop.tick();
+
+        assert_eq!(
+            tick_loop.last_seen.get("physics"),
+            Some(&engine_component::Tick::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_last_seen_removed_when_system_fully_unregistered() {
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        tick_loop.registry_mut().register(SystemDescriptor {
+            name: "physics".to_string(),
+            query: QueryDescriptor::new()
+                .read(ComponentTypeId(1))
+                .write(ComponentTypeId(2)),
+            instance_id: "inst-1".to_string(),
+        });
+        tick_loop.last_seen.insert(
+            "physics".to_string(),
+            engine_component::Tick(5),
+        );
+
+        tick_loop.enqueue_change(PendingSystemChange::Unregister {
+            name: "physics".to_string(),
+            instance_id: "inst-1".to_string(),
+        });
+        tick_loop.tick();
+
+        assert_eq!(tick_loop.last_seen.get("physics"), None);
+    }
+
+    #[test]
+    fn test_pending_register_applied_on_tick() {
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+
+        // Enqueue a registration.
+        tick_loop.enqueue_change(PendingSystemChange::Register(SystemDescriptor {
+            name: "physics".to_string(),
+            query: QueryDescriptor::new()
+                .read(ComponentTypeId(1))
+                .write(ComponentTypeId(2)),
+            instance_id: "inst-1".to_string(),
+        }));
+
+        // Before the tick, registry should still be empty.
+        assert_eq!(tick_loop.registry().system_count(), 0);
 
         // After the tick, the system should be registered.
         tick_loop.tick();
@@ -812,4 +2063,191 @@ mod tests {
         tick_loop.tick();
         assert_eq!(tick_loop.stages.len(), 1);
     }
+
+    #[test]
+    fn test_subscribe_query_asserts_existing_matches() {
+        use std::collections::BTreeSet;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        let comp = ComponentTypeId(1);
+        let mut types = BTreeSet::new();
+        types.insert(comp);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+        let arch_id = tick_loop.world().entity_archetype(entity).unwrap();
+        tick_loop
+            .world_mut()
+            .archetype_mut(arch_id)
+            .unwrap()
+            .columns[0]
+            .push_raw(&[1, 2, 3, 4]);
+
+        let query = QueryDescriptor::new().read(comp);
+        let update = tick_loop.subscribe_query("sub-1".to_string(), query);
+
+        assert_eq!(update.subscription_id, "sub-1");
+        assert_eq!(update.asserted.len(), 1);
+        assert_eq!(update.asserted[0].0, entity);
+        assert_eq!(update.retracted, Vec::new());
+    }
+
+    #[test]
+    fn test_diff_subscriptions_asserts_newly_matching_entity() {
+        use std::collections::BTreeSet;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        let comp = ComponentTypeId(1);
+        let query = QueryDescriptor::new().read(comp);
+
+        // Subscribe before anything matches.
+        let initial = tick_loop.subscribe_query("sub-1".to_string(), query);
+        assert!(initial.asserted.is_empty());
+
+        // Spawn a matching entity — a subsequent diff should assert it.
+        let mut types = BTreeSet::new();
+        types.insert(comp);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+        let arch_id = tick_loop.world().entity_archetype(entity).unwrap();
+        tick_loop
+            .world_mut()
+            .archetype_mut(arch_id)
+            .unwrap()
+            .columns[0]
+            .push_raw(&[1, 2, 3, 4]);
+
+        let updates = tick_loop.diff_subscriptions();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].asserted.len(), 1);
+        assert_eq!(updates[0].asserted[0].0, entity);
+        assert!(updates[0].retracted.is_empty());
+
+        // Nothing changed since — the next diff should produce no update.
+        assert!(tick_loop.diff_subscriptions().is_empty());
+    }
+
+    #[test]
+    fn test_diff_subscriptions_retracts_despawned_entity() {
+        use std::collections::BTreeSet;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        let comp = ComponentTypeId(1);
+        let mut types = BTreeSet::new();
+        types.insert(comp);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+        let arch_id = tick_loop.world().entity_archetype(entity).unwrap();
+        tick_loop
+            .world_mut()
+            .archetype_mut(arch_id)
+            .unwrap()
+            .columns[0]
+            .push_raw(&[1, 2, 3, 4]);
+
+        let query = QueryDescriptor::new().read(comp);
+        tick_loop.subscribe_query("sub-1".to_string(), query);
+
+        tick_loop.world_mut().despawn(entity);
+
+        let updates = tick_loop.diff_subscriptions();
+        assert_eq!(updates.len(), 1);
+        assert!(updates[0].asserted.is_empty());
+        assert_eq!(updates[0].retracted, vec![entity]);
+    }
+
+    #[test]
+    fn test_unsubscribe_query_stops_further_updates() {
+        use std::collections::BTreeSet;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        let comp = ComponentTypeId(1);
+        let query = QueryDescriptor::new().read(comp);
+        tick_loop.subscribe_query("sub-1".to_string(), query);
+
+        assert!(tick_loop.unsubscribe_query("sub-1"));
+        assert!(!tick_loop.unsubscribe_query("sub-1"));
+
+        let mut types = BTreeSet::new();
+        types.insert(comp);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+        let arch_id = tick_loop.world().entity_archetype(entity).unwrap();
+        tick_loop
+            .world_mut()
+            .archetype_mut(arch_id)
+            .unwrap()
+            .columns[0]
+            .push_raw(&[1, 2, 3, 4]);
+
+        assert!(tick_loop.diff_subscriptions().is_empty());
+    }
+
+    #[test]
+    fn test_submit_apply_shard_defers_until_poll_completions() {
+        use std::collections::BTreeSet;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+
+        let comp = ComponentTypeId(42);
+        let mut types = BTreeSet::new();
+        types.insert(comp);
+        let entity = tick_loop.world_mut().spawn(types, &[4]);
+        let arch_id = tick_loop.world().entity_archetype(entity).unwrap();
+        tick_loop
+            .world_mut()
+            .archetype_mut(arch_id)
+            .unwrap()
+            .columns[0]
+            .push_raw(&[0u8; 4]);
+
+        let shard = ComponentShard {
+            component_type: comp,
+            entities: vec![entity],
+            data: vec![serde_bytes::ByteBuf::from(vec![1, 2, 3, 4])],
+            origin_tick: Tick(1),
+            instance_id: "peer-a".to_string(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        };
+        let mut handle = tick_loop.submit(Op::ApplyShard(shard), Box::new(|rows_merged| rows_merged));
+
+        // Submitting does not apply the op.
+        let table = tick_loop.world().archetype(arch_id).unwrap();
+        let row = table.entity_row(entity).unwrap();
+        assert_eq!(table.columns[0].get_raw(row), Some(&[0u8; 4][..]));
+        assert!(handle.rx.try_recv().is_err());
+
+        assert_eq!(tick_loop.poll_completions(), 1);
+
+        let table = tick_loop.world().archetype(arch_id).unwrap();
+        let row = table.entity_row(entity).unwrap();
+        assert_eq!(table.columns[0].get_raw(row), Some(&[1, 2, 3, 4][..]));
+        assert_eq!(handle.rx.try_recv().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_submit_system_change_applies_in_submission_order() {
+        use engine_net::messages::SystemDescriptor;
+
+        let mut tick_loop = TickLoop::new(TickConfig::default());
+        let descriptor = |name: &str, instance: &str| SystemDescriptor {
+            name: name.to_string(),
+            query: QueryDescriptor::new().read(ComponentTypeId(1)),
+            instance_id: instance.to_string(),
+        };
+
+        let mut first = tick_loop.submit(
+            Op::SystemChange(PendingSystemChange::Register(descriptor("physics", "inst-1"))),
+            Box::new(|count| count),
+        );
+        let mut second = tick_loop.submit(
+            Op::SystemChange(PendingSystemChange::Register(descriptor("ai", "inst-2"))),
+            Box::new(|count| count),
+        );
+
+        assert_eq!(tick_loop.registry().system_count(), 0);
+        assert_eq!(tick_loop.poll_completions(), 2);
+
+        assert_eq!(tick_loop.registry().system_count(), 2);
+        assert_eq!(first.rx.try_recv().unwrap(), 1);
+        assert_eq!(second.rx.try_recv().unwrap(), 2);
+    }
 }
@@ -9,7 +9,34 @@
 
 use std::collections::{BTreeSet, HashMap};
 
-use engine_component::{ArchetypeId, ArchetypeTable, ComponentTypeId, Entity, EntityAllocator};
+use engine_component::{ArchetypeId, ArchetypeTable, ComponentTypeId, Entity, EntityAllocator, Tick};
+use engine_net::messages::ComponentShard;
+
+/// A single component column value captured at one row, used to carry a
+/// row's data across an archetype migration (`World::add_component` /
+/// `World::remove_component`).
+struct RowValue {
+    type_id: ComponentTypeId,
+    item_size: usize,
+    bytes: Vec<u8>,
+    added_tick: Tick,
+    changed_tick: Tick,
+    changed_by: String,
+}
+
+/// An entity's cached position in archetype storage.
+///
+/// Following the approach Bevy took when it extended `EntityLocation` with a
+/// table row, caching `row` alongside the archetype turns despawn and
+/// per-entity reads from an `O(n)` `entity_row` scan into an `O(1)` lookup.
+/// Every operation that moves a row (`despawn`'s swap-remove, `migrate_row`)
+/// must rewrite the `EntityLocation` of whichever entity ends up at the
+/// affected row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EntityLocation {
+    archetype: ArchetypeId,
+    row: usize,
+}
 
 /// The canonical world state managed by the coordinator.
 ///
@@ -21,10 +48,13 @@ pub struct World {
     allocator: EntityAllocator,
     /// All archetype tables, keyed by archetype ID.
     archetypes: HashMap<ArchetypeId, ArchetypeTable>,
-    /// Maps each entity to the archetype it belongs to.
-    entity_archetype: HashMap<Entity, ArchetypeId>,
+    /// Maps each entity to its cached archetype and row.
+    entity_location: HashMap<Entity, EntityLocation>,
     /// Maps component type sets to archetype IDs, for fast lookup.
     type_set_to_archetype: HashMap<BTreeSet<ComponentTypeId>, ArchetypeId>,
+    /// The current tick, advanced once per coordinator tick. Used to stamp
+    /// column writes for change detection.
+    current_tick: Tick,
 }
 
 impl World {
@@ -34,9 +64,37 @@ impl World {
         Self {
             allocator: EntityAllocator::new(),
             archetypes: HashMap::new(),
-            entity_archetype: HashMap::new(),
+            entity_location: HashMap::new(),
             type_set_to_archetype: HashMap::new(),
+            current_tick: Tick::ZERO,
+        }
+    }
+
+    /// Returns the world's current tick.
+    #[must_use]
+    pub fn current_tick(&self) -> Tick {
+        self.current_tick
+    }
+
+    /// Advance to the next tick, clamping any changed-ticks that have
+    /// drifted more than half the `u32` value space behind the new current
+    /// tick so wraparound comparisons stay unambiguous.
+    ///
+    /// Returns the new current tick.
+    pub fn advance_tick(&mut self) -> Tick {
+        self.current_tick = self.current_tick.next();
+        let floor = self.current_tick.wrap_floor();
+        for table in self.archetypes.values_mut() {
+            for col in &mut table.columns {
+                for t in &mut col.added_ticks {
+                    *t = t.clamped_to(floor);
+                }
+                for t in &mut col.changed_ticks {
+                    *t = t.clamped_to(floor);
+                }
+            }
         }
+        self.current_tick
     }
 
     /// Allocate a new entity without any components.
@@ -61,11 +119,18 @@ impl World {
         let archetype_id = self.get_or_create_archetype(component_types.clone(), item_sizes);
 
         // Add the entity to the archetype table's entity list.
+        let mut row = 0;
         if let Some(table) = self.archetypes.get_mut(&archetype_id) {
-            table.entities.push(entity);
+            row = table.push_entity_row(entity);
         }
 
-        self.entity_archetype.insert(entity, archetype_id);
+        self.entity_location.insert(
+            entity,
+            EntityLocation {
+                archetype: archetype_id,
+                row,
+            },
+        );
         entity
     }
 
@@ -93,53 +158,402 @@ impl World {
         let entity = self.allocator.allocate();
         let archetype_id = self.get_or_create_archetype(type_set, component_sizes);
 
+        let mut row = 0;
         if let Some(table) = self.archetypes.get_mut(&archetype_id) {
-            table.entities.push(entity);
+            row = table.push_entity_row(entity);
 
-            // Write each component's data into the matching column.
+            // Write each component's data into the matching column, stamped
+            // with the tick it was created at.
             for (ty, data) in component_types.iter().zip(component_data.iter()) {
                 if let Some(col_idx) = table.column_index(*ty) {
-                    table.columns[col_idx].push_raw(data);
+                    table.columns[col_idx].push_raw_at(data, self.current_tick);
                 }
             }
         }
 
-        self.entity_archetype.insert(entity, archetype_id);
+        self.entity_location.insert(
+            entity,
+            EntityLocation {
+                archetype: archetype_id,
+                row,
+            },
+        );
         Some(entity)
     }
 
-    /// Destroy an entity, removing it from its archetype.
+    /// Allocate `rows.len()` entities sharing a single archetype in one call.
+    ///
+    /// `component_types` and `component_sizes` describe the shared archetype
+    /// (parallel slices, one entry per component type); `rows` holds one
+    /// `Vec<Vec<u8>>` per entity, itself parallel to `component_types`. The
+    /// archetype is resolved once and every column reserves capacity for the
+    /// whole batch up front, so this is far cheaper per entity than calling
+    /// `spawn_with_data` in a loop — mirroring Bevy's `spawn_batch`.
+    ///
+    /// Returns one entity per row, in order. Returns an empty `Vec` if any
+    /// row's length doesn't match `component_types`.
+    pub fn spawn_batch(
+        &mut self,
+        component_types: &[ComponentTypeId],
+        component_sizes: &[usize],
+        rows: &[Vec<Vec<u8>>],
+    ) -> Vec<Entity> {
+        if component_types.len() != component_sizes.len()
+            || rows.iter().any(|row| row.len() != component_types.len())
+        {
+            return Vec::new();
+        }
+
+        let type_set: BTreeSet<ComponentTypeId> = component_types.iter().copied().collect();
+        let archetype_id = self.get_or_create_archetype(type_set, component_sizes);
+
+        let Some(table) = self.archetypes.get_mut(&archetype_id) else {
+            return Vec::new();
+        };
+
+        table.reserve_rows(rows.len());
+        for ty in component_types {
+            if let Some(col_idx) = table.column_index(*ty) {
+                let col = &mut table.columns[col_idx];
+                col.reserve(rows.len());
+                col.added_ticks.reserve(rows.len());
+                col.changed_ticks.reserve(rows.len());
+                col.changed_by.reserve(rows.len());
+            }
+        }
+
+        let mut entities = Vec::with_capacity(rows.len());
+        for row_data in rows {
+            let entity = self.allocator.allocate();
+            let row = table.push_entity_row(entity);
+            for (ty, data) in component_types.iter().zip(row_data.iter()) {
+                if let Some(col_idx) = table.column_index(*ty) {
+                    table.columns[col_idx].push_raw_at(data, self.current_tick);
+                }
+            }
+            self.entity_location.insert(
+                entity,
+                EntityLocation {
+                    archetype: archetype_id,
+                    row,
+                },
+            );
+            entities.push(entity);
+        }
+
+        entities
+    }
+
+    /// Destroy an entity, removing it from its archetype and freeing its
+    /// index in the allocator so a later `spawn` can reuse it under a new
+    /// generation.
     ///
-    /// Returns `true` if the entity existed and was removed.
+    /// Returns `true` if the entity existed and was removed. `entity_location`
+    /// is keyed by the full `Entity` (index and generation), so a stale
+    /// `Entity` from a generation that has already been despawned and
+    /// recycled is not found here and this returns `false` without
+    /// disturbing the slot's current occupant.
     pub fn despawn(&mut self, entity: Entity) -> bool {
-        if let Some(archetype_id) = self.entity_archetype.remove(&entity) {
-            if let Some(table) = self.archetypes.get_mut(&archetype_id)
-                && let Some(pos) = table.entities.iter().position(|&e| e == entity)
-            {
-                table.entities.swap_remove(pos);
-                // Also swap-remove from each column.
-                for col in &mut table.columns {
-                    if col.len() > pos {
-                        let last = col.len() - 1;
-                        if pos != last {
-                            let item_size = col.item_size;
-                            let last_start = last * item_size;
-                            let pos_start = pos * item_size;
-                            // Copy last element into the removed position.
-                            for i in 0..item_size {
-                                col.data[pos_start + i] = col.data[last_start + i];
-                            }
-                        }
-                        col.data.truncate(last * col.item_size);
-                    }
+        if let Some(location) = self.entity_location.remove(&entity) {
+            if let Some(table) = self.archetypes.get_mut(&location.archetype) {
+                // The entity previously at the last row was swapped into
+                // `location.row`; rewrite its cached row so it stays
+                // reachable in O(1).
+                if let Some(moved) = table.swap_remove_row(location.row) {
+                    self.entity_location.insert(
+                        moved,
+                        EntityLocation {
+                            archetype: location.archetype,
+                            row: location.row,
+                        },
+                    );
                 }
             }
+            self.allocator.free(entity);
             true
         } else {
             false
         }
     }
 
+    /// Returns `true` if `entity` was allocated and has not since been
+    /// despawned (i.e. its generation has not been recycled).
+    #[must_use]
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.allocator.is_alive(entity)
+    }
+
+    /// Add a component to a live entity, migrating it from its current
+    /// archetype (or from no archetype, for an entity spawned via
+    /// `spawn_empty`) into one that also contains `type_id`.
+    ///
+    /// If the source archetype already has a cached
+    /// [`add_edge`](ArchetypeTable::add_edge) for `type_id` — i.e. some
+    /// other entity already took this exact transition — the destination
+    /// archetype is read off the edge directly, skipping the
+    /// `component_types` clone and the `type_set_to_archetype` rehash a
+    /// cold transition requires. A cold transition caches the edge (in both
+    /// directions) once it resolves, so repeats of the same add are O(1).
+    ///
+    /// Returns `false` if `entity` is not alive or already has `type_id`.
+    pub fn add_component(
+        &mut self,
+        entity: Entity,
+        type_id: ComponentTypeId,
+        data: &[u8],
+        item_size: usize,
+    ) -> bool {
+        if !self.allocator.is_alive(entity) {
+            return false;
+        }
+
+        let old_archetype_id = self.entity_location.get(&entity).map(|loc| loc.archetype);
+
+        let new_archetype_id = match old_archetype_id {
+            Some(old_id) => {
+                let Some(old_table) = self.archetypes.get(&old_id) else {
+                    return false;
+                };
+                if old_table.has_component(type_id) {
+                    return false;
+                }
+                let Some(row) = self.entity_location.get(&entity).map(|loc| loc.row) else {
+                    return false;
+                };
+                let cached_dest = old_table.add_edge(type_id);
+                let surviving = Self::snapshot_row(old_table, row);
+
+                if let Some(new_id) = cached_dest {
+                    self.migrate_row_to(entity, Some(old_id), new_id, surviving);
+                    new_id
+                } else {
+                    let mut new_types = old_table.component_types.clone();
+                    new_types.insert(type_id);
+                    let item_sizes: Vec<usize> = new_types
+                        .iter()
+                        .map(|&t| {
+                            if t == type_id {
+                                item_size
+                            } else {
+                                surviving
+                                    .iter()
+                                    .find(|row| row.type_id == t)
+                                    .map_or(0, |row| row.item_size)
+                            }
+                        })
+                        .collect();
+                    let new_id =
+                        self.migrate_row(entity, Some(old_id), new_types, &item_sizes, surviving);
+                    self.cache_add_edge(old_id, new_id, type_id);
+                    new_id
+                }
+            }
+            None => {
+                let mut new_types = BTreeSet::new();
+                new_types.insert(type_id);
+                self.migrate_row(entity, None, new_types, &[item_size], Vec::new())
+            }
+        };
+
+        if let Some(new_table) = self.archetypes.get_mut(&new_archetype_id)
+            && let Some(idx) = new_table.column_index(type_id)
+        {
+            new_table.columns[idx].push_raw_at(data, self.current_tick);
+        }
+
+        true
+    }
+
+    /// Attach a relation component — `kind` parameterised by `target`, e.g.
+    /// `ChildOf(parent)` — to `entity`.
+    ///
+    /// A thin wrapper over [`add_component`](Self::add_component) that
+    /// stores the value under the derived
+    /// [`ComponentTypeId::relation`](engine_component::ComponentTypeId::relation)
+    /// id (so a different `target` lands the entity in a different
+    /// archetype) and additionally records the `(type_id, kind)` pair on the
+    /// destination archetype via
+    /// [`ArchetypeTable::register_relation`](engine_component::ArchetypeTable::register_relation),
+    /// since that id's hash can't be inverted back to `kind` later. Without
+    /// this, `has_relation`/`relation_columns` would never see anything
+    /// `add_component` alone wrote.
+    pub fn add_relation(
+        &mut self,
+        entity: Entity,
+        kind: ComponentTypeId,
+        target: Entity,
+        data: &[u8],
+        item_size: usize,
+    ) -> bool {
+        let type_id = ComponentTypeId::relation(kind, target);
+        if !self.add_component(entity, type_id, data, item_size) {
+            return false;
+        }
+        if let Some(location) = self.entity_location.get(&entity)
+            && let Some(table) = self.archetypes.get_mut(&location.archetype)
+        {
+            table.register_relation(type_id, kind);
+        }
+        true
+    }
+
+    /// Remove a component from a live entity, migrating it to an archetype
+    /// without `type_id`.
+    ///
+    /// Uses the source archetype's cached
+    /// [`remove_edge`](ArchetypeTable::remove_edge) for `type_id` when one
+    /// exists, the same fast path `add_component` takes; a cold transition
+    /// caches the edge (in both directions) once resolved.
+    ///
+    /// Returns the removed component's raw bytes, or `None` if `entity` is
+    /// not alive or does not have `type_id`.
+    pub fn remove_component(&mut self, entity: Entity, type_id: ComponentTypeId) -> Option<Vec<u8>> {
+        if !self.allocator.is_alive(entity) {
+            return None;
+        }
+
+        let location = *self.entity_location.get(&entity)?;
+        let old_archetype_id = location.archetype;
+        let old_table = self.archetypes.get(&old_archetype_id)?;
+        if !old_table.has_component(type_id) {
+            return None;
+        }
+        let row = location.row;
+
+        let removed = old_table
+            .columns
+            .iter()
+            .find(|col| col.type_id == type_id)
+            .and_then(|col| col.get_raw(row))
+            .map(<[u8]>::to_vec)?;
+
+        let cached_dest = old_table.remove_edge(type_id);
+        let surviving: Vec<RowValue> = Self::snapshot_row(old_table, row)
+            .into_iter()
+            .filter(|v| v.type_id != type_id)
+            .collect();
+
+        if let Some(new_id) = cached_dest {
+            self.migrate_row_to(entity, Some(old_archetype_id), new_id, surviving);
+        } else {
+            let mut new_types = old_table.component_types.clone();
+            new_types.remove(&type_id);
+            let item_sizes: Vec<usize> = new_types
+                .iter()
+                .map(|&t| {
+                    surviving
+                        .iter()
+                        .find(|row| row.type_id == t)
+                        .map_or(0, |row| row.item_size)
+                })
+                .collect();
+            let new_id = self.migrate_row(
+                entity,
+                Some(old_archetype_id),
+                new_types,
+                &item_sizes,
+                surviving,
+            );
+            self.cache_remove_edge(old_archetype_id, new_id, type_id);
+        }
+
+        Some(removed)
+    }
+
+    /// Snapshot every column's value at `row` in `table`, for carrying a
+    /// row's data across an archetype migration.
+    fn snapshot_row(table: &ArchetypeTable, row: usize) -> Vec<RowValue> {
+        table
+            .columns
+            .iter()
+            .filter_map(|col| {
+                Some(RowValue {
+                    type_id: col.type_id,
+                    item_size: col.item_size,
+                    bytes: col.get_raw(row)?.to_vec(),
+                    added_tick: col.added_tick(row)?,
+                    changed_tick: col.changed_tick(row)?,
+                    changed_by: col.changed_by(row).unwrap_or("").to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Resolve the destination archetype for `new_types` (creating it if
+    /// needed) and move `entity` into it via [`migrate_row_to`](Self::migrate_row_to).
+    ///
+    /// Returns the new archetype's ID. Does not populate any column not
+    /// present in `surviving` — callers adding a brand new component type
+    /// must write its value in after this returns.
+    fn migrate_row(
+        &mut self,
+        entity: Entity,
+        old_archetype_id: Option<ArchetypeId>,
+        new_types: BTreeSet<ComponentTypeId>,
+        item_sizes: &[usize],
+        surviving: Vec<RowValue>,
+    ) -> ArchetypeId {
+        let new_archetype_id = self.get_or_create_archetype(new_types, item_sizes);
+        self.migrate_row_to(entity, old_archetype_id, new_archetype_id, surviving);
+        new_archetype_id
+    }
+
+    /// Move `entity` from `old_archetype_id` (if any) into the
+    /// already-resolved `new_archetype_id`, carrying over `surviving`'s
+    /// column values, and swap-remove it from the old table. The row is
+    /// read from `entity_location` rather than scanned for, and whichever
+    /// entity gets swapped into the vacated slot has its own cached row
+    /// rewritten to match.
+    ///
+    /// Split out from [`migrate_row`](Self::migrate_row) so the
+    /// archetype-graph fast paths in `add_component`/`remove_component` can
+    /// move a row straight to a cached edge's destination without first
+    /// resolving it through `get_or_create_archetype`.
+    fn migrate_row_to(
+        &mut self,
+        entity: Entity,
+        old_archetype_id: Option<ArchetypeId>,
+        new_archetype_id: ArchetypeId,
+        surviving: Vec<RowValue>,
+    ) {
+        if let Some(old_id) = old_archetype_id
+            && let Some(row) = self.entity_location.get(&entity).map(|loc| loc.row)
+            && let Some(old_table) = self.archetypes.get_mut(&old_id)
+            && let Some(moved) = old_table.swap_remove_row(row)
+        {
+            self.entity_location.insert(
+                moved,
+                EntityLocation {
+                    archetype: old_id,
+                    row,
+                },
+            );
+        }
+
+        let mut new_row = 0;
+        if let Some(new_table) = self.archetypes.get_mut(&new_archetype_id) {
+            new_row = new_table.push_entity_row(entity);
+            for value in surviving {
+                if let Some(idx) = new_table.column_index(value.type_id) {
+                    new_table.columns[idx].push_raw_full(
+                        &value.bytes,
+                        value.added_tick,
+                        value.changed_tick,
+                        value.changed_by,
+                    );
+                }
+            }
+        }
+
+        self.entity_location.insert(
+            entity,
+            EntityLocation {
+                archetype: new_archetype_id,
+                row: new_row,
+            },
+        );
+    }
+
     /// Get or create an archetype for the given set of component types.
     fn get_or_create_archetype(
         &mut self,
@@ -157,6 +571,34 @@ impl World {
         id
     }
 
+    /// Cache the add/remove edge pair for `type_id` between `old_id` and
+    /// `new_id`: `old_id` gets an `add_edge` to `new_id`, and `new_id` gets
+    /// the reverse `remove_edge` back to `old_id`. Called once a cold
+    /// `add_component` transition resolves its destination archetype, so
+    /// the next entity taking the same transition (in either direction)
+    /// hits the cache instead.
+    fn cache_add_edge(&mut self, old_id: ArchetypeId, new_id: ArchetypeId, type_id: ComponentTypeId) {
+        if let Some(old_table) = self.archetypes.get_mut(&old_id) {
+            old_table.add_edges.insert(type_id, new_id);
+        }
+        if let Some(new_table) = self.archetypes.get_mut(&new_id) {
+            new_table.remove_edges.insert(type_id, old_id);
+        }
+    }
+
+    /// Cache the add/remove edge pair for `type_id` between `old_id` and
+    /// `new_id`: `old_id` gets a `remove_edge` to `new_id`, and `new_id`
+    /// gets the reverse `add_edge` back to `old_id`. Called once a cold
+    /// `remove_component` transition resolves its destination archetype.
+    fn cache_remove_edge(&mut self, old_id: ArchetypeId, new_id: ArchetypeId, type_id: ComponentTypeId) {
+        if let Some(old_table) = self.archetypes.get_mut(&old_id) {
+            old_table.remove_edges.insert(type_id, new_id);
+        }
+        if let Some(new_table) = self.archetypes.get_mut(&new_id) {
+            new_table.add_edges.insert(type_id, old_id);
+        }
+    }
+
     /// Returns a reference to an archetype table by ID.
     #[must_use]
     pub fn archetype(&self, id: ArchetypeId) -> Option<&ArchetypeTable> {
@@ -172,7 +614,21 @@ impl World {
     /// Returns the archetype ID for a given entity.
     #[must_use]
     pub fn entity_archetype(&self, entity: Entity) -> Option<ArchetypeId> {
-        self.entity_archetype.get(&entity).copied()
+        self.entity_location.get(&entity).map(|loc| loc.archetype)
+    }
+
+    /// Returns a direct slice into `entity`'s raw bytes for component
+    /// `type_id`, with no archetype or row scan.
+    ///
+    /// This resolves straight through the cached `EntityLocation` to
+    /// `columns[col].get_raw(row)`, so it stays `O(1)` no matter how many
+    /// entities share the archetype.
+    #[must_use]
+    pub fn component_ptr(&self, entity: Entity, type_id: ComponentTypeId) -> Option<&[u8]> {
+        let location = self.entity_location.get(&entity)?;
+        let table = self.archetypes.get(&location.archetype)?;
+        let col_idx = table.column_index(type_id)?;
+        table.columns[col_idx].get_raw(location.row)
     }
 
     /// Returns an iterator over all archetype tables.
@@ -183,7 +639,7 @@ impl World {
     /// Returns the total number of entities in the world.
     #[must_use]
     pub fn entity_count(&self) -> usize {
-        self.entity_archetype.len()
+        self.entity_location.len()
     }
 
     /// Returns the number of archetypes in the world.
@@ -201,6 +657,57 @@ impl World {
             .map(|table| table.id)
             .collect()
     }
+
+    /// Walk every archetype and emit one [`ComponentShard`] per component
+    /// type containing only the rows whose `changed_tick` is newer than
+    /// `last_run`.
+    ///
+    /// This is the basis for incremental shard sync: a peer that last saw
+    /// `last_run` gets exactly the rows that changed since then instead of a
+    /// full snapshot, with bandwidth proportional to churn rather than world
+    /// size. Pass [`Tick::ZERO`] to get a full snapshot: every row is
+    /// included, whatever its changed tick.
+    #[must_use]
+    pub fn changed_since(&self, last_run: Tick) -> Vec<ComponentShard> {
+        let mut shards: HashMap<ComponentTypeId, ComponentShard> = HashMap::new();
+
+        for table in self.archetypes.values() {
+            for col in &table.columns {
+                for (row, &entity) in table.entities.iter().enumerate() {
+                    let changed = last_run == Tick::ZERO
+                        || col
+                            .changed_tick(row)
+                            .is_some_and(|t| t.is_newer_than(last_run));
+                    if !changed {
+                        continue;
+                    }
+                    let Some(bytes) = col.get_raw(row) else {
+                        continue;
+                    };
+                    let shard = shards
+                        .entry(col.type_id)
+                        .or_insert_with(|| ComponentShard {
+                            component_type: col.type_id,
+                            entities: Vec::new(),
+                            data: Vec::new(),
+                            origin_tick: self.current_tick,
+                            instance_id: String::new(),
+                            changed_ticks: Vec::new(),
+                            added_ticks: Vec::new(),
+                            layout_version: 0,
+                            producing_system: String::new(),
+                        });
+                    shard.entities.push(entity);
+                    shard.data.push(serde_bytes::ByteBuf::from(bytes.to_vec()));
+                    shard
+                        .changed_ticks
+                        .push(col.changed_tick(row).unwrap_or(Tick::ZERO));
+                }
+            }
+        }
+
+        shards.into_values().collect()
+    }
 }
 
 impl Default for World {
@@ -234,6 +741,37 @@ mod tests {
         assert_eq!(world.archetype_count(), 1);
     }
 
+    #[test]
+    fn test_spawn_batch_creates_one_archetype_and_all_entities() {
+        let mut world = World::new();
+        let rows = vec![
+            vec![vec![1, 0, 0, 0]],
+            vec![vec![2, 0, 0, 0]],
+            vec![vec![3, 0, 0, 0]],
+        ];
+
+        let entities = world.spawn_batch(&[ComponentTypeId(1)], &[4], &rows);
+
+        assert_eq!(entities.len(), 3);
+        assert_eq!(world.entity_count(), 3);
+        assert_eq!(world.archetype_count(), 1);
+        assert_eq!(
+            world.component_ptr(entities[1], ComponentTypeId(1)),
+            Some(&[2, 0, 0, 0][..])
+        );
+    }
+
+    #[test]
+    fn test_spawn_batch_rejects_mismatched_row_length() {
+        let mut world = World::new();
+        let rows = vec![vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8]]];
+
+        let entities = world.spawn_batch(&[ComponentTypeId(1)], &[4], &rows);
+
+        assert!(entities.is_empty());
+        assert_eq!(world.entity_count(), 0);
+    }
+
     #[test]
     fn test_despawn() {
         let mut world = World::new();
@@ -245,6 +783,25 @@ mod tests {
         assert_eq!(world.entity_count(), 0);
     }
 
+    #[test]
+    fn test_despawn_recycles_index_and_rejects_stale_entity() {
+        let mut world = World::new();
+        let mut types = BTreeSet::new();
+        types.insert(ComponentTypeId(1));
+
+        let e1 = world.spawn(types.clone(), &[4]);
+        assert!(world.is_alive(e1));
+        assert!(world.despawn(e1));
+        assert!(!world.is_alive(e1));
+
+        // Recycling the same index must not resolve the stale entity.
+        let e2 = world.spawn(types, &[4]);
+        assert_eq!(e1.index(), e2.index());
+        assert!(world.is_alive(e2));
+        assert!(!world.despawn(e1));
+        assert_eq!(world.entity_count(), 1);
+    }
+
     #[test]
     fn test_matching_archetypes() {
         let mut world = World::new();
@@ -266,4 +823,292 @@ mod tests {
         let matches = world.matching_archetypes(&[ComponentTypeId(1), ComponentTypeId(2)]);
         assert_eq!(matches.len(), 1);
     }
+
+    #[test]
+    fn test_spawn_with_data_stamps_current_tick() {
+        let mut world = World::new();
+        world.advance_tick();
+        world.advance_tick();
+
+        let entity = world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![1, 2, 3, 4]], &[4])
+            .unwrap();
+
+        let arch_id = world.entity_archetype(entity).unwrap();
+        let table = world.archetype(arch_id).unwrap();
+        let row = table.entity_row(entity).unwrap();
+        assert_eq!(table.columns[0].changed_tick(row), Some(world.current_tick()));
+    }
+
+    #[test]
+    fn test_advance_tick_clamps_stale_changed_ticks() {
+        use engine_component::Tick;
+
+        let mut world = World::new();
+        let mut types = BTreeSet::new();
+        types.insert(ComponentTypeId(1));
+        let entity = world.spawn(types, &[4]);
+        let arch_id = world.entity_archetype(entity).unwrap();
+        world.archetype_mut(arch_id).unwrap().columns[0].push_raw_at(&[0; 4], Tick(0));
+
+        // Jump the clock far enough that Tick(0) is more than half the
+        // value space behind — it should get clamped up to the wrap floor.
+        world.current_tick = Tick(u32::MAX / 2 + 10);
+        let new_tick = world.advance_tick();
+        let floor = new_tick.wrap_floor();
+
+        let stamped = world.archetype(arch_id).unwrap().columns[0]
+            .changed_tick(0)
+            .unwrap();
+        assert_eq!(stamped, floor);
+    }
+
+    #[test]
+    fn test_changed_since_returns_full_snapshot_from_zero() {
+        let mut world = World::new();
+        world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![1, 2, 3, 4]], &[4])
+            .unwrap();
+
+        let shards = world.changed_since(Tick::ZERO);
+        assert_eq!(shards.len(), 1);
+        assert_eq!(shards[0].entities.len(), 1);
+    }
+
+    #[test]
+    fn test_changed_since_excludes_rows_not_newer_than_last_run() {
+        let mut world = World::new();
+        world.advance_tick();
+        let unchanged = world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![1, 2, 3, 4]], &[4])
+            .unwrap();
+        let last_run = world.current_tick();
+
+        world.advance_tick();
+        let changed = world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![5, 6, 7, 8]], &[4])
+            .unwrap();
+
+        let shards = world.changed_since(last_run);
+        assert_eq!(shards.len(), 1);
+        assert_eq!(shards[0].entities, vec![changed]);
+        assert!(!shards[0].entities.contains(&unchanged));
+    }
+
+    #[test]
+    fn test_add_component_migrates_entity_and_preserves_existing_data() {
+        let mut world = World::new();
+        let entity = world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![1, 2, 3, 4]], &[4])
+            .unwrap();
+
+        assert!(world.add_component(entity, ComponentTypeId(2), &[9, 9, 9, 9, 9, 9, 9, 9], 8));
+
+        let archetype_id = world.entity_archetype(entity).unwrap();
+        let table = world.archetype(archetype_id).unwrap();
+        assert_eq!(table.component_types.len(), 2);
+
+        let row = table.entity_row(entity).unwrap();
+        let col1 = table.column_index(ComponentTypeId(1)).unwrap();
+        let col2 = table.column_index(ComponentTypeId(2)).unwrap();
+        assert_eq!(table.columns[col1].get_raw(row), Some(&[1, 2, 3, 4][..]));
+        assert_eq!(
+            table.columns[col2].get_raw(row),
+            Some(&[9, 9, 9, 9, 9, 9, 9, 9][..])
+        );
+    }
+
+    #[test]
+    fn test_add_component_on_empty_entity() {
+        let mut world = World::new();
+        let entity = world.spawn_empty();
+
+        assert!(world.add_component(entity, ComponentTypeId(1), &[1, 2, 3, 4], 4));
+        assert_eq!(world.entity_count(), 1);
+    }
+
+    #[test]
+    fn test_add_component_rejects_already_present() {
+        let mut world = World::new();
+        let entity = world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![1, 2, 3, 4]], &[4])
+            .unwrap();
+
+        assert!(!world.add_component(entity, ComponentTypeId(1), &[0, 0, 0, 0], 4));
+    }
+
+    #[test]
+    fn test_add_component_rejects_dead_entity() {
+        let mut world = World::new();
+        let entity = world.spawn_empty();
+        // Entity was never placed into an archetype, so it has no row to
+        // despawn, but a stale/unknown generation must still be rejected.
+        let stale = Entity::new(entity.index(), entity.generation().wrapping_add(1));
+        assert!(!world.add_component(stale, ComponentTypeId(1), &[0, 0, 0, 0], 4));
+    }
+
+    #[test]
+    fn test_add_component_caches_edge_pair_between_archetypes() {
+        let mut world = World::new();
+        let entity = world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![1, 2, 3, 4]], &[4])
+            .unwrap();
+        let old_archetype_id = world.entity_archetype(entity).unwrap();
+
+        assert!(world.add_component(entity, ComponentTypeId(2), &[0; 8], 8));
+        let new_archetype_id = world.entity_archetype(entity).unwrap();
+
+        let old_table = world.archetype(old_archetype_id).unwrap();
+        assert_eq!(old_table.add_edge(ComponentTypeId(2)), Some(new_archetype_id));
+
+        let new_table = world.archetype(new_archetype_id).unwrap();
+        assert_eq!(new_table.remove_edge(ComponentTypeId(2)), Some(old_archetype_id));
+    }
+
+    #[test]
+    fn test_add_component_reuses_cached_edge_for_second_entity() {
+        let mut world = World::new();
+        let e1 = world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![1, 1, 1, 1]], &[4])
+            .unwrap();
+        let e2 = world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![2, 2, 2, 2]], &[4])
+            .unwrap();
+
+        assert!(world.add_component(e1, ComponentTypeId(2), &[0; 8], 8));
+        let first_dest = world.entity_archetype(e1).unwrap();
+
+        // e2 takes the exact same transition; it must land in the same
+        // archetype by following the edge cached by e1's transition, rather
+        // than computing a fresh one.
+        assert!(world.add_component(e2, ComponentTypeId(2), &[9; 8], 8));
+        assert_eq!(world.entity_archetype(e2), Some(first_dest));
+        assert_eq!(world.archetype_count(), 2);
+    }
+
+    #[test]
+    fn test_add_relation_registers_kind_on_destination_archetype() {
+        let mut world = World::new();
+        let child = world.spawn_empty();
+        let parent = world.spawn_empty();
+        let child_of = ComponentTypeId(1);
+
+        assert!(world.add_relation(child, child_of, parent, &[0; 4], 4));
+
+        let archetype_id = world.entity_archetype(child).unwrap();
+        let table = world.archetype(archetype_id).unwrap();
+        assert!(table.has_relation(child_of));
+        assert!(!table.has_relation(ComponentTypeId(2)));
+    }
+
+    #[test]
+    fn test_remove_component_migrates_entity_and_returns_old_value() {
+        let mut world = World::new();
+        let entity = world
+            .spawn_with_data(
+                &[ComponentTypeId(1), ComponentTypeId(2)],
+                &[vec![1, 2, 3, 4], vec![5, 6, 7, 8]],
+                &[4, 4],
+            )
+            .unwrap();
+
+        let removed = world.remove_component(entity, ComponentTypeId(2));
+        assert_eq!(removed, Some(vec![5, 6, 7, 8]));
+
+        let archetype_id = world.entity_archetype(entity).unwrap();
+        let table = world.archetype(archetype_id).unwrap();
+        assert_eq!(table.component_types.len(), 1);
+        assert!(table.has_component(ComponentTypeId(1)));
+        assert!(!table.has_component(ComponentTypeId(2)));
+
+        let row = table.entity_row(entity).unwrap();
+        let col1 = table.column_index(ComponentTypeId(1)).unwrap();
+        assert_eq!(table.columns[col1].get_raw(row), Some(&[1, 2, 3, 4][..]));
+    }
+
+    #[test]
+    fn test_remove_component_rejects_absent() {
+        let mut world = World::new();
+        let entity = world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![1, 2, 3, 4]], &[4])
+            .unwrap();
+
+        assert_eq!(world.remove_component(entity, ComponentTypeId(2)), None);
+    }
+
+    #[test]
+    fn test_remove_component_fixes_up_displaced_entity() {
+        let mut world = World::new();
+        let e1 = world
+            .spawn_with_data(
+                &[ComponentTypeId(1), ComponentTypeId(2)],
+                &[vec![1, 1, 1, 1], vec![2, 2, 2, 2]],
+                &[4, 4],
+            )
+            .unwrap();
+        let e2 = world
+            .spawn_with_data(
+                &[ComponentTypeId(1), ComponentTypeId(2)],
+                &[vec![3, 3, 3, 3], vec![4, 4, 4, 4]],
+                &[4, 4],
+            )
+            .unwrap();
+
+        world.remove_component(e1, ComponentTypeId(2));
+
+        // e2 must still be reachable in its original archetype after e1's
+        // row was swap-removed out from under it.
+        let archetype_id = world.entity_archetype(e2).unwrap();
+        let table = world.archetype(archetype_id).unwrap();
+        let row = table.entity_row(e2).unwrap();
+        let col1 = table.column_index(ComponentTypeId(1)).unwrap();
+        assert_eq!(table.columns[col1].get_raw(row), Some(&[3, 3, 3, 3][..]));
+    }
+
+    #[test]
+    fn test_despawn_fixes_up_displaced_entity_location() {
+        let mut world = World::new();
+        let e1 = world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![1, 1, 1, 1]], &[4])
+            .unwrap();
+        let e2 = world
+            .spawn_with_data(&[ComponentTypeId(1)], &[vec![2, 2, 2, 2]], &[4])
+            .unwrap();
+
+        assert!(world.despawn(e1));
+
+        // e2 was swapped into e1's vacated row; its cached location must
+        // have been rewritten so component_ptr still resolves to its data.
+        assert_eq!(
+            world.component_ptr(e2, ComponentTypeId(1)),
+            Some(&[2, 2, 2, 2][..])
+        );
+    }
+
+    #[test]
+    fn test_component_ptr_resolves_without_scan() {
+        let mut world = World::new();
+        let entity = world
+            .spawn_with_data(
+                &[ComponentTypeId(1), ComponentTypeId(2)],
+                &[vec![1, 2, 3, 4], vec![5, 6, 7, 8]],
+                &[4, 4],
+            )
+            .unwrap();
+
+        assert_eq!(
+            world.component_ptr(entity, ComponentTypeId(2)),
+            Some(&[5, 6, 7, 8][..])
+        );
+        assert_eq!(world.component_ptr(entity, ComponentTypeId(3)), None);
+    }
+
+    #[test]
+    fn test_component_ptr_unknown_entity_returns_none() {
+        let world = World::new();
+        assert_eq!(
+            world.component_ptr(Entity::new(0, 1), ComponentTypeId(1)),
+            None
+        );
+    }
 }
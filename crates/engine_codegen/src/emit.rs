@@ -0,0 +1,353 @@
+//! Emits Rust source for a parsed IDL [`File`].
+
+use engine_schema::{
+    BinaryOp, EnumDef, Expr, File, FlagsDef, Import, IncludeStmt, PackageDecl, RecordDef,
+    TopLevelItem, UnaryOp, VariantDef, WorldDef,
+};
+
+use crate::mapping::rust_type;
+use crate::naming::{pascal_case, shouty_snake_case};
+use crate::schema_id;
+
+/// Generates Rust source for every `record`, `enum`, `variant`, and `flags`
+/// item in `file` (recursing into any `world` blocks), derived for
+/// `engine_net::codec`'s named-MessagePack `encode`/`decode`.
+#[must_use]
+pub fn generate(file: &File) -> String {
+    let mut out = String::new();
+    out.push_str("// @generated by engine_codegen — do not edit by hand.\n");
+    out.push_str(&format!(
+        "// source package: {}:{}{}\n\n",
+        file.package.namespace,
+        file.package.name,
+        file.package
+            .version
+            .as_deref()
+            .map(|v| format!("@{v}"))
+            .unwrap_or_default()
+    ));
+    out.push_str("use serde::{Deserialize, Serialize};\n");
+    out.push_str("use std::collections::{BTreeMap, BTreeSet};\n");
+
+    if !file.imports.is_empty() {
+        out.push('\n');
+        emit_imports(&file.imports, &mut out);
+    }
+    out.push('\n');
+
+    emit_items(&file.items, &file.package, &mut out);
+    out
+}
+
+/// Emits the `use` lines that let a `TypeExpr::Named` reference in this file
+/// resolve to the same Rust path its IDL author chose via `import ... as ...`
+/// — the generated module for an imported package lives at one path per
+/// package, named after that package's `namespace_name`.
+fn emit_imports(imports: &[Import], out: &mut String) {
+    for import in imports {
+        let module = format!("{}_{}", import.package.namespace, import.package.name);
+        let items: Vec<String> = import
+            .items
+            .iter()
+            .map(|item| match &item.alias {
+                Some(alias) => format!("{} as {alias}", item.name),
+                None => item.name.clone(),
+            })
+            .collect();
+        out.push_str(&format!("use {module}::{{{}}};\n", items.join(", ")));
+    }
+}
+
+/// As [`emit_imports`], but for a `world`'s `include` statements, which pull
+/// in either one named item or an entire package with no rename.
+fn emit_includes(includes: &[IncludeStmt], out: &mut String) {
+    for include in includes {
+        let module = format!("{}_{}", include.package.namespace, include.package.name);
+        match &include.item {
+            Some(item) => out.push_str(&format!("use {module}::{item};\n")),
+            None => out.push_str(&format!("use {module}::*;\n")),
+        }
+    }
+}
+
+fn emit_items(items: &[TopLevelItem], package: &PackageDecl, out: &mut String) {
+    for item in items {
+        match item {
+            TopLevelItem::Record(record) => emit_record(record, package, out),
+            TopLevelItem::Enum(def) => emit_enum(def, package, out),
+            TopLevelItem::Variant(def) => emit_variant(def, package, out),
+            TopLevelItem::Flags(def) => emit_flags(def, package, out),
+            TopLevelItem::World(world) => emit_world(world, package, out),
+            // Type aliases, systems, and phases aren't wire types — there's
+            // nothing for this codegen backend to emit for them.
+            TopLevelItem::TypeAlias(_) | TopLevelItem::System(_) | TopLevelItem::Phase(_) => {}
+        }
+    }
+}
+
+fn emit_world(world: &WorldDef, package: &PackageDecl, out: &mut String) {
+    emit_includes(&world.includes, out);
+    emit_items(&world.items, package, out);
+}
+
+/// Emits the `pub const {SHOUTY_NAME}_SCHEMA_ID: u64` constant that lets two
+/// processes built from the same `.ecs` source check wire compatibility for
+/// `name` without a central schema registry.
+fn emit_schema_id(
+    package: &PackageDecl,
+    name: &str,
+    members: &[(String, String)],
+    out: &mut String,
+) {
+    let id = schema_id::compute(package, name, members);
+    out.push_str(&format!(
+        "pub const {}_SCHEMA_ID: u64 = {id};\n",
+        shouty_snake_case(name)
+    ));
+}
+
+fn emit_record(record: &RecordDef, package: &PackageDecl, out: &mut String) {
+    let members: Vec<(String, String)> = record
+        .fields
+        .iter()
+        .map(|f| (f.name.clone(), format!("{:?}", f.ty)))
+        .collect();
+    emit_schema_id(package, &record.name, &members, out);
+
+    if record.is_tag() {
+        out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]\n");
+        out.push_str(&format!("pub struct {};\n\n", pascal_case(&record.name)));
+        return;
+    }
+
+    out.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+    out.push_str(&format!("pub struct {} {{\n", pascal_case(&record.name)));
+    for field in &record.fields {
+        out.push_str(&format!(
+            "    pub {}: {},\n",
+            field.name,
+            field_type_with_default(&rust_type(&field.ty), field.default.as_ref())
+        ));
+    }
+    out.push_str("}\n\n");
+}
+
+/// A field's Rust type, annotated with its declared `default` if any. The
+/// default doesn't change the type — wiring it into `#[serde(default)]`/a
+/// constructor is left to a later pass — but it's worth surfacing here so
+/// the generated struct doesn't silently drop information the IDL declared.
+fn field_type_with_default(ty: &str, default: Option<&Expr>) -> String {
+    match default {
+        Some(default) => format!("{ty} /* default: {default:?} */"),
+        None => ty.to_string(),
+    }
+}
+
+fn emit_enum(def: &EnumDef, package: &PackageDecl, out: &mut String) {
+    let members: Vec<(String, String)> = def
+        .variants
+        .iter()
+        .map(|m| (m.name.clone(), format!("{:?}", m.value)))
+        .collect();
+    emit_schema_id(package, &def.name, &members, out);
+
+    out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]\n");
+    out.push_str(&format!("pub enum {} {{\n", pascal_case(&def.name)));
+    for member in &def.variants {
+        match member.value.as_ref().and_then(eval_const_int) {
+            Some(n) => out.push_str(&format!("    {} = {n},\n", pascal_case(&member.name))),
+            None => out.push_str(&format!("    {},\n", pascal_case(&member.name))),
+        }
+    }
+    out.push_str("}\n\n");
+}
+
+fn emit_variant(def: &VariantDef, package: &PackageDecl, out: &mut String) {
+    let members: Vec<(String, String)> = def
+        .cases
+        .iter()
+        .map(|c| (c.name.clone(), format!("{:?}", c.payload)))
+        .collect();
+    emit_schema_id(package, &def.name, &members, out);
+
+    out.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+    out.push_str(&format!("pub enum {} {{\n", pascal_case(&def.name)));
+    for case in &def.cases {
+        match &case.payload {
+            Some(types) if !types.is_empty() => {
+                let rendered: Vec<String> = types.iter().map(rust_type).collect();
+                out.push_str(&format!(
+                    "    {}({}),\n",
+                    pascal_case(&case.name),
+                    rendered.join(", ")
+                ));
+            }
+            _ => out.push_str(&format!("    {},\n", pascal_case(&case.name))),
+        }
+    }
+    out.push_str("}\n\n");
+}
+
+/// Emits a `flags` definition as a bitflags-style newtype over `u32` with one
+/// associated constant per member, rather than depending on the `bitflags`
+/// crate (unavailable in this no-manifest repo). A member with no explicit
+/// value gets the next unused bit in declaration order, matching
+/// `bitflags!`'s own convention for implicit members.
+fn emit_flags(def: &FlagsDef, package: &PackageDecl, out: &mut String) {
+    let members: Vec<(String, String)> = def
+        .flags
+        .iter()
+        .map(|m| (m.name.clone(), format!("{:?}", m.value)))
+        .collect();
+    emit_schema_id(package, &def.name, &members, out);
+
+    let type_name = pascal_case(&def.name);
+    out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]\n");
+    out.push_str(&format!("pub struct {type_name}(pub u32);\n\n"));
+
+    // Bits already spoken for by an explicit value, so auto-assignment below
+    // skips over them instead of handing out a bit an explicit member
+    // already claimed.
+    let used_bits: u32 = def
+        .flags
+        .iter()
+        .filter_map(|m| m.value.as_ref().and_then(eval_const_int))
+        .fold(0, |acc, n| acc | n as u32);
+
+    out.push_str(&format!("impl {type_name} {{\n"));
+    let mut next_bit = 0u32;
+    for member in &def.flags {
+        let bit = match member.value.as_ref().and_then(eval_const_int) {
+            Some(n) => n as u32,
+            None => {
+                while used_bits & (1u32 << next_bit) != 0 {
+                    next_bit += 1;
+                }
+                let bit = 1u32 << next_bit;
+                next_bit += 1;
+                bit
+            }
+        };
+        out.push_str(&format!(
+            "    pub const {}: {type_name} = {type_name}({bit});\n",
+            shouty_snake_case(&member.name)
+        ));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!("impl std::ops::BitOr for {type_name} {{\n"));
+    out.push_str(&format!("    type Output = {type_name};\n"));
+    out.push_str("    fn bitor(self, rhs: Self) -> Self::Output {\n");
+    out.push_str(&format!("        {type_name}(self.0 | rhs.0)\n"));
+    out.push_str("    }\n");
+    out.push_str("}\n\n");
+}
+
+/// Folds a constant-value [`Expr`] down to an `i64`, for discriminants and
+/// flag bits that must be Rust const-evaluable. `Ident` references aren't
+/// resolved here — cross-referencing another member's value is left to a
+/// later pass, same as the parser leaves it unresolved.
+fn eval_const_int(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::Int(n) => Some(*n),
+        Expr::Unary(UnaryOp::Neg, inner) => eval_const_int(inner).map(|n| -n),
+        Expr::Binary(op, lhs, rhs) => {
+            let lhs = eval_const_int(lhs)?;
+            let rhs = eval_const_int(rhs)?;
+            Some(match op {
+                BinaryOp::Or => lhs | rhs,
+                BinaryOp::And => lhs & rhs,
+                BinaryOp::Shl => lhs << rhs,
+                BinaryOp::Add => lhs + rhs,
+                BinaryOp::Sub => lhs - rhs,
+                BinaryOp::Mul => lhs * rhs,
+                BinaryOp::Div => lhs / rhs,
+            })
+        }
+        Expr::Float(_) | Expr::Bool(_) | Expr::Str(_) | Expr::Ident(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine_schema::parser::Parser;
+
+    #[test]
+    fn test_eval_const_int_literal_and_arithmetic() {
+        assert_eq!(eval_const_int(&Expr::Int(5)), Some(5));
+        assert_eq!(
+            eval_const_int(&Expr::Unary(UnaryOp::Neg, Box::new(Expr::Int(3)))),
+            Some(-3)
+        );
+        assert_eq!(
+            eval_const_int(&Expr::Binary(
+                BinaryOp::Shl,
+                Box::new(Expr::Int(1)),
+                Box::new(Expr::Int(4)),
+            )),
+            Some(16)
+        );
+        assert_eq!(eval_const_int(&Expr::Ident("other".into())), None);
+    }
+
+    #[test]
+    fn test_generate_tag_record_is_a_unit_struct() {
+        let file = Parser::parse("package test:game@0.1.0\n\nrecord dead {}\n").unwrap();
+        let source = generate(&file);
+        assert!(source.contains("pub struct Dead;"));
+        assert!(source.contains("pub const DEAD_SCHEMA_ID: u64"));
+    }
+
+    #[test]
+    fn test_generate_record_with_fields() {
+        let file =
+            Parser::parse("package test:game@0.1.0\n\nrecord health { current: u32, max: u32 }\n")
+                .unwrap();
+        let source = generate(&file);
+        assert!(source.contains("pub struct Health {"));
+        assert!(source.contains("pub current: u32,"));
+        assert!(source.contains("pub max: u32,"));
+    }
+
+    #[test]
+    fn test_generate_enum_with_explicit_and_implicit_discriminants() {
+        let file = Parser::parse(
+            "package test:game@0.1.0\n\nenum state { idle = 0, running, stopped = 5 }\n",
+        )
+        .unwrap();
+        let source = generate(&file);
+        assert!(source.contains("pub enum State {"));
+        assert!(source.contains("Idle = 0,"));
+        assert!(source.contains("Running,"));
+        assert!(source.contains("Stopped = 5,"));
+    }
+
+    #[test]
+    fn test_generate_variant_with_payload() {
+        let file = Parser::parse(
+            "package test:game@0.1.0\n\nvariant shape { circle(f32), point }\n",
+        )
+        .unwrap();
+        let source = generate(&file);
+        assert!(source.contains("pub enum Shape {"));
+        assert!(source.contains("Circle(f32),"));
+        assert!(source.contains("Point,"));
+    }
+
+    #[test]
+    fn test_generate_flags_with_explicit_and_auto_bits() {
+        let file = Parser::parse(
+            "package test:game@0.1.0\n\nflags layers { terrain = 0x1, objects = 0x2, fog }\n",
+        )
+        .unwrap();
+        let source = generate(&file);
+        assert!(source.contains("pub struct Layers(pub u32);"));
+        assert!(source.contains("pub const TERRAIN: Layers = Layers(1);"));
+        assert!(source.contains("pub const OBJECTS: Layers = Layers(2);"));
+        // `fog` has no explicit value; bits 0 and 1 are already claimed by
+        // `terrain`/`objects`, so it must auto-assign bit 2 rather than
+        // colliding with `terrain`.
+        assert!(source.contains("pub const FOG: Layers = Layers(4);"));
+    }
+}
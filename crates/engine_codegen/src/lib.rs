@@ -0,0 +1,19 @@
+//! # engine_codegen
+//!
+//! Turns a parsed [`engine_schema`] IDL [`File`] into Rust source: one
+//! struct per `record` (a unit struct for a zero-field tag), a bitflags-style
+//! newtype per `flags`, a payload-carrying enum per `variant`, and a plain
+//! enum per `enum`. Everything is derived to round-trip through
+//! `engine_net::codec`'s named-MessagePack `encode`/`decode` helpers.
+//!
+//! Each generated type also gets a [`schema_id`] constant — a stable,
+//! content-addressed hash of its package, name, and field layout — so two
+//! processes built from the same `.ecs` source can check wire compatibility
+//! before exchanging `messages`, without a central schema registry.
+
+pub mod emit;
+pub mod mapping;
+pub mod naming;
+pub mod schema_id;
+
+pub use emit::generate;
@@ -0,0 +1,113 @@
+//! Maps IDL [`TypeExpr`]s to the Rust type syntax the emitter writes field
+//! and payload types as.
+//!
+//! Collections map to their deterministically-ordered `std` equivalents
+//! (`BTreeMap`/`BTreeSet` rather than the hash-based ones) so two processes
+//! that `encode` the same logical value always produce the same MessagePack
+//! bytes, not just the same decoded value.
+
+use engine_schema::TypeExpr;
+
+use crate::naming::pascal_case;
+
+/// Renders `ty` as the Rust type it should be generated as.
+#[must_use]
+pub fn rust_type(ty: &TypeExpr) -> String {
+    match ty {
+        TypeExpr::Primitive(name) => primitive_rust_type(name).to_string(),
+        TypeExpr::Named(name) => pascal_case(name),
+        TypeExpr::List(inner) => format!("Vec<{}>", rust_type(inner)),
+        TypeExpr::Option(inner) => format!("Option<{}>", rust_type(inner)),
+        TypeExpr::Set(inner) => format!("BTreeSet<{}>", rust_type(inner)),
+        TypeExpr::Map(key, value) => {
+            format!("BTreeMap<{}, {}>", rust_type(key), rust_type(value))
+        }
+        TypeExpr::Tuple(types) => render_tuple(types),
+    }
+}
+
+fn render_tuple(types: &[TypeExpr]) -> String {
+    let rendered: Vec<String> = types.iter().map(rust_type).collect();
+    match rendered.len() {
+        // A single-element Rust tuple needs its trailing comma to not be
+        // parsed as a parenthesised expression.
+        1 => format!("({},)", rendered[0]),
+        _ => format!("({})", rendered.join(", ")),
+    }
+}
+
+fn primitive_rust_type(name: &str) -> &'static str {
+    match name {
+        "bool" => "bool",
+        "u8" => "u8",
+        "u16" => "u16",
+        "u32" => "u32",
+        "u64" => "u64",
+        "i8" => "i8",
+        "i16" => "i16",
+        "i32" => "i32",
+        "i64" => "i64",
+        "f32" => "f32",
+        "f64" => "f64",
+        "string" => "String",
+        "bytes" => "Vec<u8>",
+        other => unreachable!("'{other}' is not a recognised IDL primitive"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitive_and_named_types() {
+        assert_eq!(rust_type(&TypeExpr::Primitive("u32".into())), "u32");
+        assert_eq!(rust_type(&TypeExpr::Primitive("string".into())), "String");
+        assert_eq!(rust_type(&TypeExpr::Primitive("bytes".into())), "Vec<u8>");
+        assert_eq!(rust_type(&TypeExpr::Named("health".into())), "Health");
+    }
+
+    #[test]
+    fn test_collection_types() {
+        assert_eq!(
+            rust_type(&TypeExpr::List(Box::new(TypeExpr::Primitive("u32".into())))),
+            "Vec<u32>"
+        );
+        assert_eq!(
+            rust_type(&TypeExpr::Option(Box::new(TypeExpr::Primitive(
+                "f32".into()
+            )))),
+            "Option<f32>"
+        );
+        assert_eq!(
+            rust_type(&TypeExpr::Set(Box::new(TypeExpr::Primitive(
+                "string".into()
+            )))),
+            "BTreeSet<String>"
+        );
+        assert_eq!(
+            rust_type(&TypeExpr::Map(
+                Box::new(TypeExpr::Primitive("string".into())),
+                Box::new(TypeExpr::Primitive("u32".into())),
+            )),
+            "BTreeMap<String, u32>"
+        );
+    }
+
+    #[test]
+    fn test_tuple_types() {
+        assert_eq!(
+            rust_type(&TypeExpr::Tuple(vec![TypeExpr::Primitive(
+                "f32".into()
+            )])),
+            "(f32,)"
+        );
+        assert_eq!(
+            rust_type(&TypeExpr::Tuple(vec![
+                TypeExpr::Primitive("f32".into()),
+                TypeExpr::Primitive("f32".into()),
+            ])),
+            "(f32, f32)"
+        );
+    }
+}
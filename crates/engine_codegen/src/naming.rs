@@ -0,0 +1,44 @@
+//! Case-conversion helpers for turning `snake_case` IDL identifiers into the
+//! casing Rust expects for each kind of item.
+
+/// `snake_case`/`lower` identifier -> `PascalCase`, for struct/enum names.
+#[must_use]
+pub fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let mut chars = segment.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// `snake_case`/`lower` identifier -> `SHOUTY_SNAKE_CASE`, for the schema-id
+/// constant emitted alongside each type.
+#[must_use]
+pub fn shouty_snake_case(name: &str) -> String {
+    name.to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pascal_case_single_word() {
+        assert_eq!(pascal_case("transform"), "Transform");
+    }
+
+    #[test]
+    fn test_pascal_case_multiple_words() {
+        assert_eq!(pascal_case("player_health"), "PlayerHealth");
+    }
+
+    #[test]
+    fn test_shouty_snake_case() {
+        assert_eq!(shouty_snake_case("player_health"), "PLAYER_HEALTH");
+    }
+}
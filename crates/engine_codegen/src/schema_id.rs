@@ -0,0 +1,90 @@
+//! Deterministic, content-addressed schema ids.
+//!
+//! Hashes a generated type's package, name, and member layout with the same
+//! FNV-1a 64-bit algorithm [`engine_schema::RecordDef::layout_version`] and
+//! `engine_component::ComponentTypeId::from_name` use elsewhere in this
+//! workspace, so two processes compiled from the same `.ecs` source agree on
+//! a type's id without a central registry — and disagree the moment its
+//! layout actually changes.
+
+use engine_schema::PackageDecl;
+
+/// FNV-1a 64-bit offset basis — matches `RecordDef::FNV_OFFSET_BASIS`.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64-bit prime — matches `RecordDef::FNV_PRIME`.
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+fn fnv1a(seed: u64, bytes: &[u8]) -> u64 {
+    let mut hash = seed;
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// A stable id for `name`, declared in `package`, with `members` (e.g. a
+/// record's `(field_name, field_type_debug)` pairs, or an enum's
+/// `(member_name, discriminant_debug)` pairs) folded in so any layout change
+/// changes the id too.
+#[must_use]
+pub fn compute(package: &PackageDecl, name: &str, members: &[(String, String)]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    let header = match &package.version {
+        Some(version) => format!("{}:{}@{version}", package.namespace, package.name),
+        None => format!("{}:{}", package.namespace, package.name),
+    };
+    hash = fnv1a(hash, header.as_bytes());
+    hash = fnv1a(hash, b":");
+    hash = fnv1a(hash, name.as_bytes());
+    for (member_name, member_shape) in members {
+        hash = fnv1a(hash, member_name.as_bytes());
+        hash = fnv1a(hash, member_shape.as_bytes());
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pkg(version: Option<&str>) -> PackageDecl {
+        PackageDecl {
+            namespace: "test".to_string(),
+            name: "game".to_string(),
+            version: version.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_same_input_is_deterministic() {
+        let members = vec![("x".to_string(), "f32".to_string())];
+        let a = compute(&pkg(Some("0.1.0")), "transform", &members);
+        let b = compute(&pkg(Some("0.1.0")), "transform", &members);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_changing_a_field_changes_the_id() {
+        let original = compute(
+            &pkg(Some("0.1.0")),
+            "transform",
+            &[("x".to_string(), "f32".to_string())],
+        );
+        let renamed = compute(
+            &pkg(Some("0.1.0")),
+            "transform",
+            &[("y".to_string(), "f32".to_string())],
+        );
+        assert_ne!(original, renamed);
+    }
+
+    #[test]
+    fn test_changing_the_version_changes_the_id() {
+        let members = vec![("x".to_string(), "f32".to_string())];
+        let v1 = compute(&pkg(Some("0.1.0")), "transform", &members);
+        let v2 = compute(&pkg(Some("0.2.0")), "transform", &members);
+        assert_ne!(v1, v2);
+    }
+}
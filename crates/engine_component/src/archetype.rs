@@ -4,12 +4,13 @@
 //! the same set of components are grouped into the same archetype for
 //! cache-friendly iteration and efficient shard distribution.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
 use serde::{Deserialize, Serialize};
 
 use crate::component::ComponentTypeId;
 use crate::entity::Entity;
+use crate::tick::Tick;
 
 /// A unique identifier for an archetype, computed from its sorted set of
 /// [`ComponentTypeId`]s.
@@ -17,63 +18,292 @@ use crate::entity::Entity;
 pub struct ArchetypeId(pub u64);
 
 impl ArchetypeId {
+    /// FNV-1a 64-bit offset basis. Mirrors `ComponentTypeId::FNV_OFFSET_BASIS`.
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+    /// FNV-1a 64-bit prime. Mirrors `ComponentTypeId::FNV_PRIME`.
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
     /// Compute the archetype ID from a set of component type IDs.
     ///
+    /// Hashes each `ComponentTypeId`'s little-endian bytes, in `BTreeSet`
+    /// (ascending) order, with FNV-1a 64-bit — the same fixed algorithm
+    /// [`ComponentTypeId::from_name`](crate::ComponentTypeId::from_name)
+    /// uses. This replaces `std::collections::hash_map::DefaultHasher`,
+    /// whose output is explicitly *not* stable across Rust versions or
+    /// platforms: since `ArchetypeId` is serialised and exchanged between
+    /// distributed nodes that may run different toolchains, two nodes must
+    /// compute the exact same ID for the same component set or component
+    /// data silently stops routing between them.
+    ///
     /// The result is deterministic: the same set of types always produces the
-    /// same archetype ID regardless of insertion order.
+    /// same archetype ID regardless of insertion order, and is reproducible
+    /// byte-for-byte on any platform or toolchain.
     #[must_use]
     pub fn from_component_types(types: &BTreeSet<ComponentTypeId>) -> Self {
-        use std::hash::{Hash, Hasher};
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        let mut hash = Self::FNV_OFFSET_BASIS;
         for ty in types {
-            ty.hash(&mut hasher);
+            for byte in ty.0.to_le_bytes() {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(Self::FNV_PRIME);
+            }
+        }
+        Self(hash)
+    }
+}
+
+/// Largest power-of-two alignment implied by `item_size`, capped at
+/// `MAX_ALIGN`.
+///
+/// `Column` is built from a raw byte size with no access to the real
+/// `std::alloc::Layout` of the Rust type it will store — that information
+/// already gets discarded one level up (see `ComponentMeta::layout`, whose
+/// `.align()` is dropped wherever an `item_size` list is assembled). Every
+/// type's size is a multiple of its own alignment, though, so the largest
+/// power of two dividing `item_size` is always a sound alignment to
+/// allocate for, even if occasionally more conservative than the type's
+/// true alignment.
+fn natural_align(item_size: usize) -> usize {
+    const MAX_ALIGN: usize = 16;
+    if item_size == 0 {
+        return 1;
+    }
+    (1usize << item_size.trailing_zeros()).min(MAX_ALIGN)
+}
+
+/// A manually-managed byte buffer aligned to a [`Column`]'s `align`.
+///
+/// `Vec<u8>` only guarantees 1-byte alignment, so storing components whose
+/// alignment exceeds 1 in one and later casting a pointer into it to
+/// `*const T`/`*mut T` (as [`Column::get`]/[`Column::get_mut`] do) is
+/// undefined behavior. This buffer instead allocates directly through
+/// `std::alloc` using a `Layout` built from `align`, growing by doubling
+/// like `Vec` does.
+struct AlignedBuffer {
+    ptr: std::ptr::NonNull<u8>,
+    /// Capacity, in bytes.
+    cap_bytes: usize,
+    align: usize,
+}
+
+impl AlignedBuffer {
+    fn new(align: usize) -> Self {
+        Self {
+            ptr: std::ptr::NonNull::dangling(),
+            cap_bytes: 0,
+            align,
+        }
+    }
+
+    fn layout(&self, size: usize) -> std::alloc::Layout {
+        std::alloc::Layout::from_size_align(size, self.align)
+            .expect("column buffer size overflows isize when rounded to its alignment")
+    }
+
+    /// Grow the buffer so it can hold at least `needed_bytes`, doubling
+    /// capacity each time it must reallocate. No-op if already large enough.
+    fn ensure_capacity(&mut self, needed_bytes: usize) {
+        if needed_bytes <= self.cap_bytes {
+            return;
+        }
+        let new_cap = needed_bytes.max(self.cap_bytes.saturating_mul(2));
+        let new_layout = self.layout(new_cap);
+        let new_ptr = if self.cap_bytes == 0 {
+            // SAFETY: `new_layout` has a nonzero size, since `needed_bytes`
+            // (and therefore `new_cap`) is only ever nonzero here.
+            unsafe { std::alloc::alloc(new_layout) }
+        } else {
+            let old_layout = self.layout(self.cap_bytes);
+            // SAFETY: `self.ptr` was allocated with `old_layout` by this same
+            // allocator and hasn't been freed; `new_layout`'s size is nonzero.
+            unsafe { std::alloc::realloc(self.ptr.as_ptr(), old_layout, new_layout.size()) }
+        };
+        self.ptr = std::ptr::NonNull::new(new_ptr)
+            .unwrap_or_else(|| std::alloc::handle_alloc_error(new_layout));
+        self.cap_bytes = new_cap;
+    }
+
+    fn as_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.ptr.as_ptr()
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        if self.cap_bytes > 0 {
+            // SAFETY: `self.ptr` was allocated with exactly this layout and
+            // is dropped at most once.
+            unsafe { std::alloc::dealloc(self.ptr.as_ptr(), self.layout(self.cap_bytes)) };
+        }
+    }
+}
+
+impl Clone for AlignedBuffer {
+    fn clone(&self) -> Self {
+        let mut new_buf = AlignedBuffer::new(self.align);
+        if self.cap_bytes > 0 {
+            new_buf.ensure_capacity(self.cap_bytes);
+            // SAFETY: both `self.ptr` and `new_buf.ptr` are valid for
+            // `self.cap_bytes` bytes and don't overlap (distinct allocations).
+            unsafe {
+                std::ptr::copy_nonoverlapping(self.as_ptr(), new_buf.as_mut_ptr(), self.cap_bytes);
+            }
         }
-        Self(hasher.finish())
+        new_buf
+    }
+}
+
+impl std::fmt::Debug for AlignedBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlignedBuffer")
+            .field("cap_bytes", &self.cap_bytes)
+            .field("align", &self.align)
+            .finish()
     }
 }
 
+// SAFETY: `AlignedBuffer` exclusively owns its allocation, exactly like
+// `Vec<u8>` does; there is no shared mutable state that would make sending
+// or sharing it across threads unsound.
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
+
 /// A column in an archetype table, storing components of a single type.
 ///
-/// Components are stored as raw bytes for type-erased access. Each element is
-/// `item_size` bytes, laid out contiguously.
+/// Components are stored as raw bytes for type-erased access, backed by an
+/// [`AlignedBuffer`] over-aligned to `align` rather than a plain `Vec<u8>`
+/// (which only guarantees 1-byte alignment) — this is what makes the typed
+/// `unsafe fn get`/`get_mut`/`push` below sound. Row `i` lives at byte
+/// offset `i * stride`, where `stride` is `item_size` rounded up to a
+/// multiple of `align`.
 #[derive(Debug, Clone)]
 pub struct Column {
     /// The component type stored in this column.
     pub type_id: ComponentTypeId,
-    /// Size of a single component instance in bytes.
+    /// Size of a single component instance in bytes, as requested at
+    /// construction. May be smaller than `stride` if `align` required
+    /// padding.
     pub item_size: usize,
-    /// Raw byte storage. Length is always `item_size * entity_count`.
-    pub data: Vec<u8>,
+    /// Byte alignment this column's storage is allocated for. See
+    /// [`natural_align`] for how it's derived from `item_size`.
+    align: usize,
+    /// `item_size` rounded up to a multiple of `align`: the actual number of
+    /// bytes between the start of row `i` and row `i + 1`.
+    stride: usize,
+    /// Aligned raw byte storage. In use for `stride * len()` bytes.
+    buf: AlignedBuffer,
+    /// Bytes currently in use (`stride * len()`).
+    len_bytes: usize,
+    /// The tick each row was created at, parallel to `entities`/rows. Unlike
+    /// `changed_ticks`, this is stamped once on insertion and never updated
+    /// by subsequent writes — it lets a query distinguish "this row is new"
+    /// from "this row was merely modified".
+    pub added_ticks: Vec<Tick>,
+    /// The tick each row was last written, parallel to `entities`/rows.
+    pub changed_ticks: Vec<Tick>,
+    /// The instance ID that produced the last write to each row, parallel to
+    /// `entities`/rows. Empty for rows only ever written locally. Used to
+    /// break `changed_tick` ties deterministically when resolving
+    /// conflicting writes from multiple peers.
+    pub changed_by: Vec<String>,
 }
 
 impl Column {
     /// Create a new empty column for the given component type.
     #[must_use]
     pub fn new(type_id: ComponentTypeId, item_size: usize) -> Self {
+        let align = natural_align(item_size);
+        let stride = item_size.div_ceil(align) * align;
         Self {
             type_id,
             item_size,
-            data: Vec::new(),
+            align,
+            stride,
+            buf: AlignedBuffer::new(align),
+            len_bytes: 0,
+            added_ticks: Vec::new(),
+            changed_ticks: Vec::new(),
+            changed_by: Vec::new(),
         }
     }
 
     /// Returns the number of component instances stored.
     #[must_use]
     pub fn len(&self) -> usize {
-        if self.item_size == 0 {
+        if self.stride == 0 {
             return 0;
         }
-        self.data.len() / self.item_size
+        self.len_bytes / self.stride
     }
 
     /// Returns `true` if this column contains no components.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.len_bytes == 0
+    }
+
+    /// Reserve storage for `additional` more rows, ahead of a batch of
+    /// pushes.
+    pub fn reserve(&mut self, additional: usize) {
+        self.buf.ensure_capacity(self.len_bytes + additional * self.stride);
     }
 
-    /// Push a component's raw bytes into the column.
+    /// Grow the buffer (if needed) and append one uninitialized row,
+    /// returning its index. Callers must immediately fill the row's bytes.
+    fn push_uninit_row(&mut self) -> usize {
+        self.buf.ensure_capacity(self.len_bytes + self.stride);
+        let row = self.len();
+        self.len_bytes += self.stride;
+        row
+    }
+
+    fn write_row(&mut self, bytes: &[u8]) {
+        let row = self.push_uninit_row();
+        let start = row * self.stride;
+        // SAFETY: `push_uninit_row` just grew the buffer to cover
+        // `[start, start + stride)`, and `bytes.len() == item_size <= stride`.
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), self.buf.as_mut_ptr().add(start), bytes.len());
+        }
+    }
+
+    /// Push a component's raw bytes into the column, stamped with
+    /// [`Tick::ZERO`] (meaning "never observed as changed").
     pub fn push_raw(&mut self, bytes: &[u8]) {
+        self.push_raw_at(bytes, Tick::ZERO);
+    }
+
+    /// Push a component's raw bytes into the column, stamping the new row
+    /// with `tick`.
+    pub fn push_raw_at(&mut self, bytes: &[u8], tick: Tick) {
+        assert_eq!(
+            bytes.len(),
+            self.item_size,
+            "byte slice size mismatch: expected {}, got {}",
+            self.item_size,
+            bytes.len()
+        );
+        self.write_row(bytes);
+        self.added_ticks.push(tick);
+        self.changed_ticks.push(tick);
+        self.changed_by.push(String::new());
+    }
+
+    /// Push a component's raw bytes into the column with explicit
+    /// `added_tick`/`changed_tick`/`changed_by`, for carrying a row's full
+    /// metadata across an archetype migration rather than stamping it as a
+    /// fresh local write.
+    pub fn push_raw_full(
+        &mut self,
+        bytes: &[u8],
+        added_tick: Tick,
+        changed_tick: Tick,
+        changed_by: String,
+    ) {
         assert_eq!(
             bytes.len(),
             self.item_size,
@@ -81,29 +311,113 @@ impl Column {
             self.item_size,
             bytes.len()
         );
-        self.data.extend_from_slice(bytes);
+        self.write_row(bytes);
+        self.added_ticks.push(added_tick);
+        self.changed_ticks.push(changed_tick);
+        self.changed_by.push(changed_by);
     }
 
     /// Get a reference to the raw bytes of the component at `index`.
     #[must_use]
     pub fn get_raw(&self, index: usize) -> Option<&[u8]> {
-        let start = index * self.item_size;
-        let end = start + self.item_size;
-        if end > self.data.len() {
+        if index >= self.len() {
             return None;
         }
-        Some(&self.data[start..end])
+        let start = index * self.stride;
+        // SAFETY: `index < len()` guarantees `[start, start + item_size)` is
+        // within the `len_bytes` prefix of the buffer that's been written.
+        Some(unsafe { std::slice::from_raw_parts(self.buf.as_ptr().add(start), self.item_size) })
+    }
+
+    /// Returns the tick `index` was created at, if the row exists.
+    #[must_use]
+    pub fn added_tick(&self, index: usize) -> Option<Tick> {
+        self.added_ticks.get(index).copied()
+    }
+
+    /// Returns the tick `index` was last written at, if the row exists.
+    #[must_use]
+    pub fn changed_tick(&self, index: usize) -> Option<Tick> {
+        self.changed_ticks.get(index).copied()
+    }
+
+    /// Returns the instance ID that produced the last write to `index`, if
+    /// the row exists. Empty if the row has only ever been written locally.
+    #[must_use]
+    pub fn changed_by(&self, index: usize) -> Option<&str> {
+        self.changed_by.get(index).map(String::as_str)
+    }
+
+    /// Overwrite the bytes of row `index` and stamp its `changed_tick` with
+    /// `tick`. The row's `added_tick` is left untouched — overwriting is not
+    /// the same as creating a new row. Clears `changed_by`, since this is a
+    /// local write rather than a merge from a peer.
+    ///
+    /// Returns `false` if `index` is out of bounds or `bytes` has the wrong
+    /// length.
+    pub fn set_raw_at(&mut self, index: usize, bytes: &[u8], tick: Tick) -> bool {
+        self.merge_raw_at(index, bytes, tick, "")
+    }
+
+    /// Overwrite the bytes of row `index`, stamping its `changed_tick` with
+    /// `tick` and `changed_by` with `instance_id`. Used when applying a
+    /// write attributed to a specific peer, as opposed to a local write.
+    ///
+    /// Returns `false` if `index` is out of bounds or `bytes` has the wrong
+    /// length.
+    pub fn merge_raw_at(&mut self, index: usize, bytes: &[u8], tick: Tick, instance_id: &str) -> bool {
+        if bytes.len() != self.item_size {
+            return false;
+        }
+        let Some(dst) = self.get_raw_mut(index) else {
+            return false;
+        };
+        dst.copy_from_slice(bytes);
+        self.changed_ticks[index] = tick;
+        self.changed_by[index] = instance_id.to_string();
+        true
+    }
+
+    /// Remove row `index` by swapping in the last row, keeping the buffer,
+    /// `added_ticks`, `changed_ticks`, and `changed_by` in sync. No-op if
+    /// `index` is out of bounds.
+    pub fn swap_remove_row(&mut self, index: usize) {
+        let len = self.len();
+        if index >= len {
+            return;
+        }
+        let last = len - 1;
+        if index != last {
+            let stride = self.stride;
+            // SAFETY: both `index * stride` and `last * stride` address
+            // disjoint (`index != last`) `stride`-byte ranges within the
+            // buffer's `len_bytes` prefix.
+            unsafe {
+                let base = self.buf.as_mut_ptr();
+                let src = base.add(last * stride);
+                let dst = base.add(index * stride);
+                std::ptr::copy_nonoverlapping(src, dst, stride);
+            }
+            self.added_ticks[index] = self.added_ticks[last];
+            self.changed_ticks[index] = self.changed_ticks[last];
+            self.changed_by[index] = self.changed_by[last].clone();
+        }
+        self.len_bytes -= self.stride;
+        self.added_ticks.truncate(last);
+        self.changed_ticks.truncate(last);
+        self.changed_by.truncate(last);
     }
 
     /// Get a mutable reference to the raw bytes of the component at `index`.
     #[must_use]
     pub fn get_raw_mut(&mut self, index: usize) -> Option<&mut [u8]> {
-        let start = index * self.item_size;
-        let end = start + self.item_size;
-        if end > self.data.len() {
+        if index >= self.len() {
             return None;
         }
-        Some(&mut self.data[start..end])
+        let start = index * self.stride;
+        // SAFETY: `index < len()` guarantees `[start, start + item_size)` is
+        // within the `len_bytes` prefix of the buffer that's been written.
+        Some(unsafe { std::slice::from_raw_parts_mut(self.buf.as_mut_ptr().add(start), self.item_size) })
     }
 
     /// Push a typed component value into the column.
@@ -117,7 +431,10 @@ impl Column {
         let bytes =
             // SAFETY: We read `size_of::<T>()` bytes from a valid `T` value.
             unsafe { std::slice::from_raw_parts(&value as *const T as *const u8, self.item_size) };
-        self.data.extend_from_slice(bytes);
+        self.write_row(bytes);
+        self.added_ticks.push(Tick::ZERO);
+        self.changed_ticks.push(Tick::ZERO);
+        self.changed_by.push(String::new());
         std::mem::forget(value);
     }
 
@@ -130,7 +447,8 @@ impl Column {
     #[must_use]
     pub unsafe fn get<T: Sized>(&self, index: usize) -> Option<&T> {
         let bytes = self.get_raw(index)?;
-        // SAFETY: Caller guarantees type match.
+        // SAFETY: Caller guarantees type match, and the column's buffer is
+        // aligned to (at least) `T`'s natural alignment.
         Some(unsafe { &*(bytes.as_ptr() as *const T) })
     }
 
@@ -143,7 +461,8 @@ impl Column {
     #[must_use]
     pub unsafe fn get_mut<T: Sized>(&mut self, index: usize) -> Option<&mut T> {
         let bytes = self.get_raw_mut(index)?;
-        // SAFETY: Caller guarantees type match.
+        // SAFETY: Caller guarantees type match, and the column's buffer is
+        // aligned to (at least) `T`'s natural alignment.
         Some(unsafe { &mut *(bytes.as_mut_ptr() as *mut T) })
     }
 }
@@ -163,6 +482,39 @@ pub struct ArchetypeTable {
     pub entities: Vec<Entity>,
     /// One column per component type, in the same order as `component_types`.
     pub columns: Vec<Column>,
+    /// Cached destination archetype reached by *adding* one component type
+    /// to this archetype, keyed by the type being added. Mirrors Bevy's
+    /// `Edges` structure: once a transition has been taken, it's memoized
+    /// here so the next entity that takes the same add/remove edge resolves
+    /// its destination archetype in O(1) instead of cloning
+    /// `component_types`, inserting the type, and rehashing to find (or
+    /// create) the destination archetype.
+    ///
+    /// An edge is valid forever once recorded: the destination is exactly
+    /// this archetype's component set plus one type, and archetype
+    /// membership never changes once an [`ArchetypeId`] exists, so there's
+    /// nothing to invalidate.
+    pub add_edges: HashMap<ComponentTypeId, ArchetypeId>,
+    /// Cached destination archetype reached by *removing* one component
+    /// type from this archetype, keyed by the type being removed. See
+    /// [`add_edges`](Self::add_edges) for the caching rationale.
+    pub remove_edges: HashMap<ComponentTypeId, ArchetypeId>,
+    /// Row index for each entity currently in this table, kept in sync by
+    /// [`push_entity_row`](Self::push_entity_row) and
+    /// [`swap_remove_row`](Self::swap_remove_row) so [`entity_row`](Self::entity_row)
+    /// is `O(1)` instead of scanning `entities`. Not `pub`: every insertion
+    /// or removal of a row must go through those two methods, or this index
+    /// goes stale.
+    entity_rows: HashMap<Entity, usize>,
+    /// The relation `kind` each relation-typed column in this table was
+    /// derived from via [`ComponentTypeId::relation`], keyed by the derived
+    /// (per-target) type ID. `ComponentTypeId::relation`'s hash is one-way,
+    /// so a column's `(kind, target)` pair can't be recovered from its type
+    /// ID alone; this side table is how [`has_relation`](Self::has_relation)
+    /// and [`relation_columns`](Self::relation_columns) answer "is there a
+    /// relation of kind K toward *any* target" without inverting the hash.
+    /// Populated by [`register_relation`](Self::register_relation).
+    relation_kinds: HashMap<ComponentTypeId, ComponentTypeId>,
 }
 
 impl ArchetypeTable {
@@ -181,6 +533,10 @@ impl ArchetypeTable {
             component_types,
             entities: Vec::new(),
             columns,
+            add_edges: HashMap::new(),
+            remove_edges: HashMap::new(),
+            entity_rows: HashMap::new(),
+            relation_kinds: HashMap::new(),
         }
     }
 
@@ -208,10 +564,100 @@ impl ArchetypeTable {
         self.component_types.iter().position(|&tid| tid == type_id)
     }
 
-    /// Find the row index for a given entity.
+    /// Records that `type_id` — already a column in this table, derived via
+    /// [`ComponentTypeId::relation`] — is a relation component of `kind`.
+    ///
+    /// The caller that built `type_id` is the only one who still has `kind`
+    /// and the target entity on hand; once `ComponentTypeId::relation` has
+    /// hashed them together, the table itself has no way to recover `kind`
+    /// from `type_id` alone, so it must be told.
+    pub fn register_relation(&mut self, type_id: ComponentTypeId, kind: ComponentTypeId) {
+        self.relation_kinds.insert(type_id, kind);
+    }
+
+    /// Returns `true` if this archetype has a relation component of `kind`
+    /// toward *any* target — the wildcard form of [`has_component`](Self::has_component).
+    #[must_use]
+    pub fn has_relation(&self, kind: ComponentTypeId) -> bool {
+        self.relation_kinds.values().any(|&k| k == kind)
+    }
+
+    /// Returns every column whose component type is a relation of `kind`,
+    /// regardless of which target each was registered for.
+    pub fn relation_columns(&self, kind: ComponentTypeId) -> impl Iterator<Item = &Column> {
+        self.relation_kinds
+            .iter()
+            .filter(move |(_, &k)| k == kind)
+            .filter_map(move |(&type_id, _)| {
+                let idx = self.column_index(type_id)?;
+                self.columns.get(idx)
+            })
+    }
+
+    /// Find the row index for a given entity. `O(1)` via the table's
+    /// internal entity→row index, kept up to date by
+    /// [`push_entity_row`](Self::push_entity_row) and
+    /// [`swap_remove_row`](Self::swap_remove_row).
     #[must_use]
     pub fn entity_row(&self, entity: Entity) -> Option<usize> {
-        self.entities.iter().position(|&e| e == entity)
+        self.entity_rows.get(&entity).copied()
+    }
+
+    /// Reserve capacity for `additional` more rows across `entities` and the
+    /// entity→row index, ahead of a batch of [`push_entity_row`] calls.
+    pub fn reserve_rows(&mut self, additional: usize) {
+        self.entities.reserve(additional);
+        self.entity_rows.reserve(additional);
+    }
+
+    /// Append `entity` as a new row, in `entities` and the entity→row index
+    /// alike. Returns the new row's index. Does not touch `columns` — the
+    /// caller is responsible for pushing a value into each column.
+    pub fn push_entity_row(&mut self, entity: Entity) -> usize {
+        let row = self.entities.len();
+        self.entities.push(entity);
+        self.entity_rows.insert(entity, row);
+        row
+    }
+
+    /// Remove row `row` by swapping in the last row: for each [`Column`],
+    /// copies the last `item_size` bytes over the bytes at `row` and
+    /// truncates by one element, and does the same swap on `entities` and
+    /// the entity→row index. The standard hecs/bevy table removal strategy.
+    ///
+    /// Returns the entity that was moved into `row` (the one that used to be
+    /// last), so the caller can fix up its own cached location. Returns
+    /// `None` if `row` was already the last row (nothing moved) or is out of
+    /// bounds.
+    pub fn swap_remove_row(&mut self, row: usize) -> Option<Entity> {
+        if row >= self.entities.len() {
+            return None;
+        }
+        let removed = self.entities.swap_remove(row);
+        self.entity_rows.remove(&removed);
+        for col in &mut self.columns {
+            col.swap_remove_row(row);
+        }
+        if let Some(&moved) = self.entities.get(row) {
+            self.entity_rows.insert(moved, row);
+            Some(moved)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cached destination archetype reached by adding
+    /// `type_id` to this archetype, if that edge has been taken before.
+    #[must_use]
+    pub fn add_edge(&self, type_id: ComponentTypeId) -> Option<ArchetypeId> {
+        self.add_edges.get(&type_id).copied()
+    }
+
+    /// Returns the cached destination archetype reached by removing
+    /// `type_id` from this archetype, if that edge has been taken before.
+    #[must_use]
+    pub fn remove_edge(&self, type_id: ComponentTypeId) -> Option<ArchetypeId> {
+        self.remove_edges.get(&type_id).copied()
     }
 }
 
@@ -234,6 +680,18 @@ mod tests {
         assert_eq!(id1, id2);
     }
 
+    #[test]
+    fn test_archetype_id_matches_known_fnv1a_vector() {
+        // Locks the wire format: any change to the hashing algorithm must
+        // not silently change the IDs two nodes compute for the same
+        // component set.
+        let types = make_types();
+        assert_eq!(
+            ArchetypeId::from_component_types(&types),
+            ArchetypeId(0x7717_9803_63c8_e066)
+        );
+    }
+
     #[test]
     fn test_archetype_id_order_independent() {
         let mut set1 = BTreeSet::new();
@@ -269,4 +727,230 @@ mod tests {
         assert!(table.is_empty());
         assert_eq!(table.columns.len(), 2);
     }
+
+    #[test]
+    fn test_push_raw_at_stamps_changed_tick() {
+        let mut col = Column::new(ComponentTypeId(1), 4);
+        col.push_raw_at(&[1, 2, 3, 4], Tick(7));
+        assert_eq!(col.changed_tick(0), Some(Tick(7)));
+    }
+
+    #[test]
+    fn test_set_raw_at_overwrites_data_and_tick() {
+        let mut col = Column::new(ComponentTypeId(1), 4);
+        col.push_raw_at(&[0, 0, 0, 0], Tick(1));
+        assert!(col.set_raw_at(0, &[9, 9, 9, 9], Tick(5)));
+        assert_eq!(col.get_raw(0), Some(&[9, 9, 9, 9][..]));
+        assert_eq!(col.changed_tick(0), Some(Tick(5)));
+    }
+
+    #[test]
+    fn test_push_raw_at_stamps_added_tick_once() {
+        let mut col = Column::new(ComponentTypeId(1), 4);
+        col.push_raw_at(&[1, 2, 3, 4], Tick(7));
+        assert_eq!(col.added_tick(0), Some(Tick(7)));
+        assert_eq!(col.changed_tick(0), Some(Tick(7)));
+    }
+
+    #[test]
+    fn test_set_raw_at_leaves_added_tick_untouched() {
+        let mut col = Column::new(ComponentTypeId(1), 4);
+        col.push_raw_at(&[0, 0, 0, 0], Tick(1));
+        assert!(col.set_raw_at(0, &[9, 9, 9, 9], Tick(5)));
+        assert_eq!(col.added_tick(0), Some(Tick(1)));
+        assert_eq!(col.changed_tick(0), Some(Tick(5)));
+    }
+
+    #[test]
+    fn test_merge_raw_at_stamps_changed_by() {
+        let mut col = Column::new(ComponentTypeId(1), 4);
+        col.push_raw_at(&[0, 0, 0, 0], Tick(1));
+        assert!(col.merge_raw_at(0, &[9, 9, 9, 9], Tick(5), "peer-a"));
+        assert_eq!(col.changed_by(0), Some("peer-a"));
+    }
+
+    #[test]
+    fn test_set_raw_at_clears_changed_by() {
+        let mut col = Column::new(ComponentTypeId(1), 4);
+        col.push_raw_at(&[0, 0, 0, 0], Tick(1));
+        col.merge_raw_at(0, &[9, 9, 9, 9], Tick(5), "peer-a");
+        assert!(col.set_raw_at(0, &[1, 1, 1, 1], Tick(6)));
+        assert_eq!(col.changed_by(0), Some(""));
+    }
+
+    #[test]
+    fn test_swap_remove_row_keeps_data_and_ticks_in_sync() {
+        let mut col = Column::new(ComponentTypeId(1), 4);
+        col.push_raw_at(&[1, 1, 1, 1], Tick(1));
+        col.push_raw_at(&[2, 2, 2, 2], Tick(2));
+        col.push_raw_at(&[3, 3, 3, 3], Tick(3));
+
+        col.swap_remove_row(0);
+
+        assert_eq!(col.len(), 2);
+        assert_eq!(col.get_raw(0), Some(&[3, 3, 3, 3][..]));
+        assert_eq!(col.added_tick(0), Some(Tick(3)));
+        assert_eq!(col.changed_tick(0), Some(Tick(3)));
+        assert_eq!(col.get_raw(1), Some(&[2, 2, 2, 2][..]));
+        assert_eq!(col.changed_tick(1), Some(Tick(2)));
+    }
+
+    #[test]
+    fn test_push_raw_full_preserves_explicit_ticks_and_changed_by() {
+        let mut col = Column::new(ComponentTypeId(1), 4);
+        col.push_raw_full(&[9, 9, 9, 9], Tick(3), Tick(7), "peer-a".to_string());
+        assert_eq!(col.get_raw(0), Some(&[9, 9, 9, 9][..]));
+        assert_eq!(col.added_tick(0), Some(Tick(3)));
+        assert_eq!(col.changed_tick(0), Some(Tick(7)));
+        assert_eq!(col.changed_by(0), Some("peer-a"));
+    }
+
+    #[test]
+    fn test_column_typed_access_is_correctly_aligned() {
+        // f64 needs 8-byte alignment; a `Vec<u8>`-backed column would only
+        // guarantee 1-byte alignment, making `get::<f64>` unsound.
+        let mut col = Column::new(ComponentTypeId(1), std::mem::size_of::<f64>());
+        for i in 0..8u8 {
+            unsafe { col.push(f64::from(i)) };
+        }
+        for i in 0..8usize {
+            let ptr = col.get_raw(i).unwrap().as_ptr();
+            assert_eq!(ptr as usize % std::mem::align_of::<f64>(), 0);
+            let val = unsafe { col.get::<f64>(i) }.unwrap();
+            assert!((*val - i as f64).abs() < f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_column_growth_preserves_existing_rows() {
+        // Push enough rows to force at least one buffer reallocation and
+        // confirm earlier rows survive the grow untouched.
+        let mut col = Column::new(ComponentTypeId(1), 4);
+        for i in 0..64u32 {
+            col.push_raw(&i.to_le_bytes());
+        }
+        assert_eq!(col.len(), 64);
+        for i in 0..64u32 {
+            assert_eq!(col.get_raw(i as usize), Some(&i.to_le_bytes()[..]));
+        }
+    }
+
+    #[test]
+    fn test_new_archetype_table_has_no_cached_edges() {
+        let table = ArchetypeTable::new(make_types(), &[4, 8]);
+        assert_eq!(table.add_edge(ComponentTypeId(3)), None);
+        assert_eq!(table.remove_edge(ComponentTypeId(1)), None);
+    }
+
+    #[test]
+    fn test_add_and_remove_edge_roundtrip() {
+        let mut table = ArchetypeTable::new(make_types(), &[4, 8]);
+        let dest = ArchetypeId(42);
+
+        table.add_edges.insert(ComponentTypeId(3), dest);
+        assert_eq!(table.add_edge(ComponentTypeId(3)), Some(dest));
+
+        table.remove_edges.insert(ComponentTypeId(1), dest);
+        assert_eq!(table.remove_edge(ComponentTypeId(1)), Some(dest));
+    }
+
+    #[test]
+    fn test_push_entity_row_updates_index() {
+        let mut table = ArchetypeTable::new(make_types(), &[4, 8]);
+        let e1 = Entity::new(1, 0);
+        let e2 = Entity::new(2, 0);
+
+        assert_eq!(table.push_entity_row(e1), 0);
+        assert_eq!(table.push_entity_row(e2), 1);
+
+        assert_eq!(table.entity_row(e1), Some(0));
+        assert_eq!(table.entity_row(e2), Some(1));
+    }
+
+    #[test]
+    fn test_swap_remove_row_returns_moved_entity_and_updates_index() {
+        let mut table = ArchetypeTable::new(make_types(), &[4, 8]);
+        let e1 = Entity::new(1, 0);
+        let e2 = Entity::new(2, 0);
+        let e3 = Entity::new(3, 0);
+        table.push_entity_row(e1);
+        table.push_entity_row(e2);
+        table.push_entity_row(e3);
+
+        // Removing row 0 swaps the last entity (e3) into its place.
+        let moved = table.swap_remove_row(0);
+        assert_eq!(moved, Some(e3));
+        assert_eq!(table.entity_row(e1), None);
+        assert_eq!(table.entity_row(e3), Some(0));
+        assert_eq!(table.entity_row(e2), Some(1));
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn test_swap_remove_last_row_returns_none() {
+        let mut table = ArchetypeTable::new(make_types(), &[4, 8]);
+        let e1 = Entity::new(1, 0);
+        table.push_entity_row(e1);
+
+        assert_eq!(table.swap_remove_row(0), None);
+        assert_eq!(table.entity_row(e1), None);
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_swap_remove_row_out_of_bounds_is_none() {
+        let mut table = ArchetypeTable::new(make_types(), &[4, 8]);
+        assert_eq!(table.swap_remove_row(0), None);
+    }
+
+    #[test]
+    fn test_register_relation_then_has_relation_is_true() {
+        let child_of = ComponentTypeId::from_name("ChildOf");
+        let parent = Entity::new(1, 0);
+        let rel_id = ComponentTypeId::relation(child_of, parent);
+
+        let mut types = BTreeSet::new();
+        types.insert(rel_id);
+        let mut table = ArchetypeTable::new(types, &[0]);
+
+        assert!(!table.has_relation(child_of));
+        table.register_relation(rel_id, child_of);
+        assert!(table.has_relation(child_of));
+        assert!(!table.has_relation(ComponentTypeId::from_name("Likes")));
+    }
+
+    #[test]
+    fn test_relation_columns_finds_every_target_for_a_kind() {
+        let child_of = ComponentTypeId::from_name("ChildOf");
+        let parent_a = Entity::new(1, 0);
+        let parent_b = Entity::new(2, 0);
+        let rel_a = ComponentTypeId::relation(child_of, parent_a);
+        let rel_b = ComponentTypeId::relation(child_of, parent_b);
+
+        let mut types = BTreeSet::new();
+        types.insert(rel_a);
+        types.insert(rel_b);
+        let mut table = ArchetypeTable::new(types, &[0, 0]);
+        table.register_relation(rel_a, child_of);
+        table.register_relation(rel_b, child_of);
+
+        let found: BTreeSet<ComponentTypeId> =
+            table.relation_columns(child_of).map(|col| col.type_id).collect();
+        assert_eq!(found, BTreeSet::from([rel_a, rel_b]));
+    }
+
+    #[test]
+    fn test_relation_columns_ignores_other_kinds() {
+        let child_of = ComponentTypeId::from_name("ChildOf");
+        let likes = ComponentTypeId::from_name("Likes");
+        let target = Entity::new(1, 0);
+        let rel_id = ComponentTypeId::relation(child_of, target);
+
+        let mut types = BTreeSet::new();
+        types.insert(rel_id);
+        let mut table = ArchetypeTable::new(types, &[0]);
+        table.register_relation(rel_id, child_of);
+
+        assert_eq!(table.relation_columns(likes).count(), 0);
+    }
 }
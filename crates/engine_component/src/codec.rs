@@ -0,0 +1,195 @@
+//! Pluggable wire codecs for component payloads.
+//!
+//! [`ComponentMeta::serialize_fn`]/[`deserialize_fn`](ComponentMeta::deserialize_fn)
+//! used to call `rmp_serde` directly, tying every component's wire format to
+//! MessagePack. A [`Codec`] lets the same [`ComponentRecord`](crate::ComponentRecord)
+//! bytes be produced in whichever serde data format a given connection wants —
+//! compact MessagePack between internal servers, JSON for a browser client —
+//! without recompiling component types. [`CodecId`] is the runtime-selectable
+//! handle a transport negotiates (e.g. a NATS subscribe-subject segment or
+//! header) and resolves to a concrete [`Codec`] impl.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Error produced by a [`Codec`] while encoding or decoding.
+#[derive(Debug, Clone)]
+pub struct CodecError(pub String);
+
+impl std::fmt::Display for CodecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+/// A serde data format that can encode/decode component values to bytes.
+///
+/// Implementations are zero-sized marker types rather than trait objects:
+/// `encode`/`decode` are generic over the value type `T`, which a `dyn Codec`
+/// could not dispatch. Runtime selection (e.g. "which format did this
+/// connection ask for?") goes through [`CodecId`] instead, which matches on
+/// a small fixed set of known codecs.
+pub trait Codec: Send + Sync + 'static {
+    /// A short, stable name for this codec (e.g. `"msgpack"`, `"json"`),
+    /// used to negotiate format over a transport such as a NATS header or a
+    /// `{prefix}.<codec>.set` subject segment.
+    fn name(&self) -> &'static str;
+
+    /// Encode `value` to this codec's wire format.
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, CodecError>;
+
+    /// Decode `bytes` from this codec's wire format.
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, CodecError>;
+}
+
+/// MessagePack, via `rmp_serde`. The default codec, matching this crate's
+/// historical (pre-[`Codec`]) wire format.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessagePackCodec;
+
+impl Codec for MessagePackCodec {
+    fn name(&self) -> &'static str {
+        "msgpack"
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        rmp_serde::to_vec_named(value).map_err(|e| CodecError(e.to_string()))
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, CodecError> {
+        rmp_serde::from_slice(bytes).map_err(|e| CodecError(e.to_string()))
+    }
+}
+
+/// JSON, via `serde_json`. Intended for polyglot or browser clients that
+/// prefer a human-readable, widely-supported format over density.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        serde_json::to_vec(value).map_err(|e| CodecError(e.to_string()))
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, CodecError> {
+        serde_json::from_slice(bytes).map_err(|e| CodecError(e.to_string()))
+    }
+}
+
+/// A runtime-selectable handle identifying one of the built-in [`Codec`]s.
+///
+/// This is what a transport actually negotiates — e.g. a NATS client might
+/// subscribe under `{prefix}.<codec>.set` or set a header naming the codec —
+/// since a bare `name: &str` would need validating on every message, while
+/// `CodecId` is parsed once at connection/subscription time via
+/// [`CodecId::from_name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodecId {
+    MsgPack,
+    Json,
+}
+
+impl CodecId {
+    /// The default codec used when a connection negotiates none —
+    /// MessagePack, matching this crate's pre-`Codec` behavior.
+    pub const DEFAULT: CodecId = CodecId::MsgPack;
+
+    /// Looks up a [`CodecId`] by its [`Codec::name`], e.g. from a NATS
+    /// subject segment or header value.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "msgpack" => Some(CodecId::MsgPack),
+            "json" => Some(CodecId::Json),
+            _ => None,
+        }
+    }
+
+    /// The [`Codec::name`] of the codec this id identifies.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            CodecId::MsgPack => MessagePackCodec.name(),
+            CodecId::Json => JsonCodec.name(),
+        }
+    }
+
+    /// Encodes `value` using the codec this id identifies.
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, CodecError> {
+        match self {
+            CodecId::MsgPack => MessagePackCodec.encode(value),
+            CodecId::Json => JsonCodec.encode(value),
+        }
+    }
+
+    /// Decodes `bytes` using the codec this id identifies.
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, CodecError> {
+        match self {
+            CodecId::MsgPack => MessagePackCodec.decode(bytes),
+            CodecId::Json => JsonCodec.decode(bytes),
+        }
+    }
+}
+
+impl Default for CodecId {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Health {
+        current: f32,
+        max: f32,
+    }
+
+    #[test]
+    fn test_msgpack_roundtrip() {
+        let health = Health { current: 80.0, max: 100.0 };
+        let bytes = MessagePackCodec.encode(&health).unwrap();
+        let restored: Health = MessagePackCodec.decode(&bytes).unwrap();
+        assert_eq!(health, restored);
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let health = Health { current: 80.0, max: 100.0 };
+        let bytes = JsonCodec.encode(&health).unwrap();
+        let restored: Health = JsonCodec.decode(&bytes).unwrap();
+        assert_eq!(health, restored);
+        assert_eq!(bytes, serde_json::to_vec(&health).unwrap());
+    }
+
+    #[test]
+    fn test_codec_id_from_name() {
+        assert_eq!(CodecId::from_name("json"), Some(CodecId::Json));
+        assert_eq!(CodecId::from_name("msgpack"), Some(CodecId::MsgPack));
+        assert_eq!(CodecId::from_name("bogus"), None);
+    }
+
+    #[test]
+    fn test_codec_id_default_is_msgpack() {
+        assert_eq!(CodecId::default(), CodecId::MsgPack);
+    }
+
+    #[test]
+    fn test_codec_id_dispatches_to_matching_format() {
+        let health = Health { current: 1.0, max: 2.0 };
+        let json_bytes = CodecId::Json.encode(&health).unwrap();
+        assert_eq!(json_bytes, serde_json::to_vec(&health).unwrap());
+
+        let restored: Health = CodecId::Json.decode(&json_bytes).unwrap();
+        assert_eq!(restored, health);
+    }
+}
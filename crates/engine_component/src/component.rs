@@ -13,6 +13,7 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::codec::{CodecError, CodecId};
 use crate::entity::Entity;
 
 /// A unique identifier for a component type, derived from its string name
@@ -70,6 +71,37 @@ impl ComponentTypeId {
     pub fn of<T: Component>() -> Self {
         Self::from_name(T::type_name())
     }
+
+    /// Derives the [`ComponentTypeId`] for a relationship component: `kind`
+    /// parameterised by a `target` entity, e.g. `ChildOf(parent)` or
+    /// `Likes(other)`.
+    ///
+    /// Hashes `kind`'s and `target`'s little-endian bytes with FNV-1a, so
+    /// each distinct `(kind, target)` pair gets its own `ComponentTypeId` —
+    /// an entity related to a different target lands in a different
+    /// archetype, matching the archetype-per-relation model. Like
+    /// [`Self::from_name`], this is one-way: recovering `kind` and `target`
+    /// from the resulting ID requires a side lookup (see
+    /// `ArchetypeTable::register_relation`).
+    #[must_use]
+    pub const fn relation(kind: Self, target: Entity) -> Self {
+        let kind_bytes = kind.0.to_le_bytes();
+        let target_bytes = target.0.to_le_bytes();
+        let mut hash = Self::FNV_OFFSET_BASIS;
+        let mut i = 0;
+        while i < kind_bytes.len() {
+            hash ^= kind_bytes[i] as u64;
+            hash = hash.wrapping_mul(Self::FNV_PRIME);
+            i += 1;
+        }
+        let mut j = 0;
+        while j < target_bytes.len() {
+            hash ^= target_bytes[j] as u64;
+            hash = hash.wrapping_mul(Self::FNV_PRIME);
+            j += 1;
+        }
+        Self(hash)
+    }
 }
 
 /// Metadata about a component type, used for type-erased storage.
@@ -83,17 +115,19 @@ pub struct ComponentMeta {
     pub layout: std::alloc::Layout,
     /// Function pointer to drop a component in-place.
     pub drop_fn: Option<unsafe fn(*mut u8)>,
-    /// Serialise a single component instance to MessagePack bytes.
-    pub serialize_fn: fn(&[u8]) -> Result<Vec<u8>, rmp_serde::encode::Error>,
-    /// Deserialise a single component instance from MessagePack bytes.
-    pub deserialize_fn: fn(&[u8]) -> Result<Vec<u8>, rmp_serde::decode::Error>,
+    /// Serialise a single component instance to `codec`'s wire format.
+    pub serialize_fn: fn(bytes: &[u8], codec: CodecId) -> Result<Vec<u8>, CodecError>,
+    /// Deserialise a single component instance from `codec`'s wire format.
+    pub deserialize_fn: fn(bytes: &[u8], codec: CodecId) -> Result<Vec<u8>, CodecError>,
 }
 
 /// The core component trait.
 ///
 /// All data stored in the ECS must implement this trait. Components must be
-/// serialisable for network transport and `Send + Sync` for safe concurrent
-/// access.
+/// serialisable for network transport, `Send + Sync` for safe concurrent
+/// access, and `Clone` so callers (e.g. `SystemContext`'s typed component
+/// cache) can hand out independent copies of a cached, already-deserialised
+/// value without re-decoding it.
 ///
 /// # Examples
 ///
@@ -111,7 +145,9 @@ pub struct ComponentMeta {
 ///     fn type_name() -> &'static str { "Health" }
 /// }
 /// ```
-pub trait Component: Send + Sync + 'static + Serialize + for<'de> Deserialize<'de> {
+pub trait Component:
+    Send + Sync + Clone + 'static + Serialize + for<'de> Deserialize<'de>
+{
     /// A human-readable name for this component type.
     fn type_name() -> &'static str;
 
@@ -136,15 +172,14 @@ pub trait Component: Send + Sync + 'static + Serialize + for<'de> Deserialize<'d
             } else {
                 None
             },
-            serialize_fn: |bytes: &[u8]| {
+            serialize_fn: |bytes: &[u8], codec: CodecId| {
                 assert!(bytes.len() >= std::mem::size_of::<Self>());
                 // SAFETY: Caller guarantees `bytes` points to a valid `Self`.
                 let value = unsafe { &*(bytes.as_ptr() as *const Self) };
-                rmp_serde::to_vec_named(value)
+                codec.encode(value)
             },
-            deserialize_fn: |bytes: &[u8]| {
-                let value: Self = rmp_serde::from_slice(bytes)
-                    .map_err(|e| rmp_serde::decode::Error::Syntax(e.to_string()))?;
+            deserialize_fn: |bytes: &[u8], codec: CodecId| {
+                let value: Self = codec.decode(bytes)?;
                 let mut result = vec![0u8; std::mem::size_of::<Self>()];
                 // SAFETY: We write a valid `Self` into the correctly-sized buffer.
                 unsafe {
@@ -255,4 +290,44 @@ mod tests {
         let restored: Health = rmp_serde::from_slice(&bytes).unwrap();
         assert_eq!(health, restored);
     }
+
+    #[test]
+    fn test_meta_serialize_fn_defaults_to_msgpack_wire_format() {
+        let meta = Health::meta();
+        let health = Health {
+            current: 80.0,
+            max: 100.0,
+        };
+        let raw = unsafe {
+            std::slice::from_raw_parts(
+                (&health as *const Health).cast::<u8>(),
+                std::mem::size_of::<Health>(),
+            )
+        };
+        let bytes = (meta.serialize_fn)(raw, CodecId::MsgPack).unwrap();
+        assert_eq!(bytes, rmp_serde::to_vec_named(&health).unwrap());
+    }
+
+    #[test]
+    fn test_meta_serialize_deserialize_roundtrip_via_json_codec() {
+        let meta = Health::meta();
+        let health = Health {
+            current: 1.0,
+            max: 2.0,
+        };
+        let raw = unsafe {
+            std::slice::from_raw_parts(
+                (&health as *const Health).cast::<u8>(),
+                std::mem::size_of::<Health>(),
+            )
+        };
+        let bytes = (meta.serialize_fn)(raw, CodecId::Json).unwrap();
+        assert_eq!(bytes, serde_json::to_vec(&health).unwrap());
+
+        let restored_bytes = (meta.deserialize_fn)(&bytes, CodecId::Json).unwrap();
+        // SAFETY: `restored_bytes` was just written by `deserialize_fn` as a
+        // valid `Health` of the correct size.
+        let restored: Health = unsafe { std::ptr::read(restored_bytes.as_ptr().cast()) };
+        assert_eq!(restored, health);
+    }
 }
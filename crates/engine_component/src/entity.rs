@@ -1,17 +1,23 @@
 //! Entity type and allocation utilities.
 //!
-//! An [`Entity`] is a lightweight `u64` identifier with no inherent data.
-//! All entity IDs are allocated by the coordinator to ensure global uniqueness.
+//! An [`Entity`] is a lightweight `u64` identifier with no inherent data,
+//! packing a 32-bit index and a 32-bit generation. Components are attached
+//! to entities to give them meaning. All entity IDs are allocated by the
+//! coordinator to ensure global uniqueness.
 
 use serde::{Deserialize, Serialize};
 
 /// A unique entity identifier.
 ///
-/// Entities are pure identifiers — they carry no data of their own. Components
-/// are attached to entities to give them meaning.
+/// Entities are pure identifiers — they carry no data of their own. The raw
+/// `u64` packs a 32-bit `index` in the low bits and a 32-bit `generation` in
+/// the high bits, following the same index/generation split Bevy and Legion
+/// use: when an index is recycled by [`EntityAllocator`] its generation is
+/// bumped, so a stale `Entity` referring to the old generation no longer
+/// resolves to the new occupant of that index.
 ///
-/// Entity IDs are allocated by the coordinator and are guaranteed to be unique
-/// across the entire distributed system.
+/// Entity IDs are allocated by the coordinator and are guaranteed to be
+/// unique (for their generation) across the entire distributed system.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Entity(pub u64);
 
@@ -25,12 +31,32 @@ impl Entity {
         Self(id)
     }
 
+    /// Create an entity from an `(index, generation)` pair.
+    #[must_use]
+    pub const fn new(index: u32, generation: u32) -> Self {
+        Self(((generation as u64) << 32) | index as u64)
+    }
+
     /// Returns the raw `u64` identifier.
     #[must_use]
     pub const fn id(self) -> u64 {
         self.0
     }
 
+    /// Returns the low 32 bits: the slot index into `EntityAllocator`'s
+    /// generation table.
+    #[must_use]
+    pub const fn index(self) -> u32 {
+        self.0 as u32
+    }
+
+    /// Returns the high 32 bits: the generation of the slot at `index()`
+    /// this `Entity` was allocated for.
+    #[must_use]
+    pub const fn generation(self) -> u32 {
+        (self.0 >> 32) as u32
+    }
+
     /// Returns `true` if this is a valid (non-zero) entity.
     #[must_use]
     pub const fn is_valid(self) -> bool {
@@ -44,34 +70,72 @@ impl std::fmt::Display for Entity {
     }
 }
 
-/// Allocates monotonically increasing entity IDs.
+/// Allocates entity IDs, recycling freed indices with a bumped generation.
 ///
 /// This allocator lives in the coordinator and is the single source of truth
-/// for entity identity. A free-list for recycling destroyed entity IDs can be
-/// added later.
+/// for entity identity. Each index has a generation counter in
+/// `generations`; `free` returns an index to `free_list` and bumps its
+/// generation so an old `Entity` referring to that index at its previous
+/// generation is recognized as stale by [`is_alive`](Self::is_alive), rather
+/// than silently resolving to whatever now occupies the recycled slot.
 #[derive(Debug)]
 pub struct EntityAllocator {
-    next_id: u64,
+    /// Current generation for each index ever allocated.
+    generations: Vec<u32>,
+    /// Indices freed by `free` and available for reuse.
+    free_list: Vec<u32>,
 }
 
 impl EntityAllocator {
-    /// Creates a new allocator. IDs start at 1 (0 is reserved for [`Entity::INVALID`]).
+    /// Creates a new, empty allocator.
     #[must_use]
     pub fn new() -> Self {
-        Self { next_id: 1 }
+        Self {
+            generations: Vec::new(),
+            free_list: Vec::new(),
+        }
     }
 
-    /// Allocates a fresh entity ID.
+    /// Allocates an entity ID, reusing a freed index if one is available.
     pub fn allocate(&mut self) -> Entity {
-        let id = self.next_id;
-        self.next_id += 1;
-        Entity(id)
+        if let Some(index) = self.free_list.pop() {
+            let generation = self.generations[index as usize];
+            Entity::new(index, generation)
+        } else {
+            let index = self.generations.len() as u32;
+            self.generations.push(1);
+            Entity::new(index, 1)
+        }
+    }
+
+    /// Frees `entity`'s index for reuse, bumping its generation so stale
+    /// references to the old `Entity` fail [`is_alive`](Self::is_alive)
+    /// once the index is reallocated.
+    ///
+    /// Does nothing if `entity`'s index was never allocated by this
+    /// allocator.
+    pub fn free(&mut self, entity: Entity) {
+        let index = entity.index();
+        if let Some(generation) = self.generations.get_mut(index as usize) {
+            *generation = generation.wrapping_add(1);
+            self.free_list.push(index);
+        }
+    }
+
+    /// Returns `true` if `entity`'s generation matches the current
+    /// generation stored for its index, i.e. it has not been freed (or has
+    /// been freed and reallocated under a different generation) since it
+    /// was allocated.
+    #[must_use]
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.generations.get(entity.index() as usize) == Some(&entity.generation())
     }
 
-    /// Returns the number of entities allocated so far.
+    /// Returns the number of distinct indices ever allocated, including
+    /// ones that have since been freed.
     #[must_use]
     pub fn count(&self) -> u64 {
-        self.next_id - 1
+        self.generations.len() as u64
     }
 }
 
@@ -104,12 +168,48 @@ mod tests {
         let e1 = alloc.allocate();
         let e2 = alloc.allocate();
         let e3 = alloc.allocate();
-        assert_eq!(e1.id(), 1);
-        assert_eq!(e2.id(), 2);
-        assert_eq!(e3.id(), 3);
+        assert_eq!(e1.index(), 0);
+        assert_eq!(e2.index(), 1);
+        assert_eq!(e3.index(), 2);
         assert_eq!(alloc.count(), 3);
     }
 
+    #[test]
+    fn test_entity_index_generation_roundtrip() {
+        let e = Entity::new(7, 3);
+        assert_eq!(e.index(), 7);
+        assert_eq!(e.generation(), 3);
+    }
+
+    #[test]
+    fn test_free_recycles_index_with_bumped_generation() {
+        let mut alloc = EntityAllocator::new();
+        let e1 = alloc.allocate();
+        alloc.free(e1);
+        let e2 = alloc.allocate();
+        assert_eq!(e1.index(), e2.index());
+        assert!(e2.generation() > e1.generation());
+        assert_eq!(alloc.count(), 1);
+    }
+
+    #[test]
+    fn test_is_alive_rejects_stale_generation() {
+        let mut alloc = EntityAllocator::new();
+        let e1 = alloc.allocate();
+        assert!(alloc.is_alive(e1));
+        alloc.free(e1);
+        assert!(!alloc.is_alive(e1));
+        let e2 = alloc.allocate();
+        assert!(alloc.is_alive(e2));
+        assert!(!alloc.is_alive(e1));
+    }
+
+    #[test]
+    fn test_is_alive_unknown_index_is_false() {
+        let alloc = EntityAllocator::new();
+        assert!(!alloc.is_alive(Entity::new(0, 1)));
+    }
+
     #[test]
     fn test_entity_serialization_roundtrip() {
         let entity = Entity::from_raw(999);
@@ -7,16 +7,29 @@
 //!
 //! - [`Component`] trait — the contract all ECS data must satisfy.
 //! - [`Entity`] — lightweight `u64` entity identifiers.
-//! - [`EntityAllocator`] — monotonically increasing ID allocator.
+//! - [`EntityAllocator`] — generational ID allocator with free-list recycling.
 //! - [`ArchetypeTable`] — SoA storage grouped by component combination.
 //! - [`QueryDescriptor`] — declarative data access requirements for systems.
+//! - [`Tick`] — monotonic change-detection counter.
+//! - [`Codec`]/[`CodecId`] — pluggable wire formats for component payloads.
+//! - [`ComponentRegistry`] — maps a [`ComponentTypeId`] back to its name.
+//! - [`pack_into_stages`] — topological-depth + conflict-packing algorithm
+//!   shared by the `engine_app` and `engine_system` schedulers.
 
 pub mod archetype;
+pub mod codec;
 pub mod component;
 pub mod entity;
 pub mod query;
+pub mod registry;
+pub mod stage_pack;
+pub mod tick;
 
 pub use archetype::{ArchetypeId, ArchetypeTable, Column};
+pub use codec::{Codec, CodecError, CodecId, JsonCodec, MessagePackCodec};
 pub use component::{Component, ComponentMeta, ComponentRecord, ComponentTypeId};
 pub use entity::{Entity, EntityAllocator};
 pub use query::{QueryDescriptor, QueryFilter};
+pub use registry::{ComponentRegistry, RegistryCollision};
+pub use stage_pack::pack_into_stages;
+pub use tick::Tick;
@@ -69,6 +69,46 @@ impl QueryDescriptor {
         self
     }
 
+    /// Restrict the query to entities where `type_id` has changed since the
+    /// system's last pass. Shorthand for `.filter(QueryFilter::Changed(type_id))`.
+    #[must_use]
+    pub fn changed(self, type_id: ComponentTypeId) -> Self {
+        self.filter(QueryFilter::Changed(type_id))
+    }
+
+    /// Restrict the query to entities where `type_id` was added since the
+    /// system's last pass. Shorthand for `.filter(QueryFilter::Added(type_id))`.
+    #[must_use]
+    pub fn added(self, type_id: ComponentTypeId) -> Self {
+        self.filter(QueryFilter::Added(type_id))
+    }
+
+    /// Returns the component types this query restricts to "changed since
+    /// last pass" via a [`QueryFilter::Changed`] filter.
+    #[must_use]
+    pub fn changed_types(&self) -> Vec<ComponentTypeId> {
+        self.filters
+            .iter()
+            .filter_map(|f| match f {
+                QueryFilter::Changed(type_id) => Some(*type_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the component types this query restricts to "added since last
+    /// pass" via a [`QueryFilter::Added`] filter.
+    #[must_use]
+    pub fn added_types(&self) -> Vec<ComponentTypeId> {
+        self.filters
+            .iter()
+            .filter_map(|f| match f {
+                QueryFilter::Added(type_id) => Some(*type_id),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Returns all component types that this query accesses (reads + writes + optionals).
     #[must_use]
     pub fn all_accessed_types(&self) -> Vec<ComponentTypeId> {
@@ -97,8 +137,18 @@ impl QueryDescriptor {
     /// A.writes ∩ (B.reads ∪ B.writes) ≠ ∅  OR
     /// B.writes ∩ (A.reads ∪ A.writes) ≠ ∅
     /// ```
+    ///
+    /// That overlap check is skipped entirely when [`filters_disjoint`]
+    /// proves no entity can ever match both queries — e.g. one requires
+    /// `With(Player)` while the other requires `Without(Player)` — even if
+    /// their read/write sets otherwise overlap.
+    ///
+    /// [`filters_disjoint`]: Self::filters_disjoint
     #[must_use]
     pub fn conflicts_with(&self, other: &QueryDescriptor) -> bool {
+        if self.filters_disjoint(other) {
+            return false;
+        }
         // Check if any of our writes overlap with their reads or writes.
         for w in &self.writes {
             if other.reads.contains(w) || other.writes.contains(w) {
@@ -113,6 +163,46 @@ impl QueryDescriptor {
         }
         false
     }
+
+    /// Returns `true` if `self` and `other` are provably disjoint — no
+    /// entity can ever match both — because one side requires a component
+    /// the other explicitly excludes.
+    ///
+    /// Looks for a `ComponentTypeId` `c` such that one query requires `c`
+    /// (a read, a write, or an explicit `With(c)` filter) while the other
+    /// excludes it via `Without(c)`. This is the only disjointness the
+    /// filter set can prove here; anything else falls through to the usual
+    /// read/write overlap check in [`conflicts_with`](Self::conflicts_with).
+    #[must_use]
+    pub fn filters_disjoint(&self, other: &QueryDescriptor) -> bool {
+        fn requires(q: &QueryDescriptor, c: ComponentTypeId) -> bool {
+            q.required_types().contains(&c)
+                || q.filters
+                    .iter()
+                    .any(|f| matches!(f, QueryFilter::With(t) if *t == c))
+        }
+        fn excludes(q: &QueryDescriptor, c: ComponentTypeId) -> bool {
+            q.filters
+                .iter()
+                .any(|f| matches!(f, QueryFilter::Without(t) if *t == c))
+        }
+        fn filter_types(q: &QueryDescriptor) -> impl Iterator<Item = ComponentTypeId> + '_ {
+            q.filters.iter().filter_map(|f| match f {
+                QueryFilter::With(t) | QueryFilter::Without(t) => Some(*t),
+                QueryFilter::Changed(_) | QueryFilter::Added(_) => None,
+            })
+        }
+
+        self.required_types()
+            .into_iter()
+            .chain(filter_types(self))
+            .chain(other.required_types())
+            .chain(filter_types(other))
+            .any(|c| {
+                (requires(self, c) && excludes(other, c))
+                    || (requires(other, c) && excludes(self, c))
+            })
+    }
 }
 
 impl Default for QueryDescriptor {
@@ -130,6 +220,9 @@ pub enum QueryFilter {
     Without(ComponentTypeId),
     /// Only match entities where this component has changed since the last tick.
     Changed(ComponentTypeId),
+    /// Only match entities where this component was added since the system's
+    /// last pass (distinct from `Changed`, which also matches overwrites).
+    Added(ComponentTypeId),
 }
 
 #[cfg(test)]
@@ -206,4 +299,83 @@ mod tests {
         assert!(required.contains(&b));
         assert!(!required.contains(&c));
     }
+
+    #[test]
+    fn test_changed_builder_adds_changed_filter() {
+        let velocity = ComponentTypeId(2);
+
+        let q = QueryDescriptor::new().read(velocity).changed(velocity);
+
+        assert_eq!(q.changed_types(), vec![velocity]);
+    }
+
+    #[test]
+    fn test_changed_types_ignores_other_filters() {
+        let transform = ComponentTypeId(1);
+        let velocity = ComponentTypeId(2);
+
+        let q = QueryDescriptor::new()
+            .filter(QueryFilter::With(transform))
+            .changed(velocity);
+
+        assert_eq!(q.changed_types(), vec![velocity]);
+    }
+
+    #[test]
+    fn test_added_builder_adds_added_filter() {
+        let velocity = ComponentTypeId(2);
+
+        let q = QueryDescriptor::new().read(velocity).added(velocity);
+
+        assert_eq!(q.added_types(), vec![velocity]);
+        assert!(q.changed_types().is_empty());
+    }
+
+    #[test]
+    fn test_with_vs_without_same_component_is_disjoint() {
+        let player = ComponentTypeId(1);
+        let velocity = ComponentTypeId(2);
+
+        // Both write Velocity, which would normally conflict, but one only
+        // ever matches entities with Player and the other only entities
+        // without it, so no entity can trigger both at once.
+        let with_player = QueryDescriptor::new()
+            .write(velocity)
+            .filter(QueryFilter::With(player));
+        let without_player = QueryDescriptor::new()
+            .write(velocity)
+            .filter(QueryFilter::Without(player));
+
+        assert!(with_player.filters_disjoint(&without_player));
+        assert!(!with_player.conflicts_with(&without_player));
+    }
+
+    #[test]
+    fn test_required_read_vs_without_is_disjoint() {
+        let player = ComponentTypeId(1);
+        let velocity = ComponentTypeId(2);
+
+        // Requiring Player as a read (not just a With filter) also counts.
+        let reads_player = QueryDescriptor::new().read(player).write(velocity);
+        let without_player = QueryDescriptor::new()
+            .write(velocity)
+            .filter(QueryFilter::Without(player));
+
+        assert!(reads_player.filters_disjoint(&without_player));
+        assert!(!reads_player.conflicts_with(&without_player));
+    }
+
+    #[test]
+    fn test_unrelated_filters_are_not_disjoint() {
+        let transform = ComponentTypeId(1);
+        let velocity = ComponentTypeId(2);
+
+        let q1 = QueryDescriptor::new()
+            .write(velocity)
+            .filter(QueryFilter::Changed(transform));
+        let q2 = QueryDescriptor::new().write(velocity);
+
+        assert!(!q1.filters_disjoint(&q2));
+        assert!(q1.conflicts_with(&q2));
+    }
 }
@@ -0,0 +1,174 @@
+//! Process-wide registry mapping [`ComponentTypeId`] back to the component
+//! name and [`ComponentMeta`] it was derived from.
+//!
+//! [`ComponentTypeId::from_name`] is a one-way FNV-1a hash: given an ID,
+//! there's no way to recover the name it came from, which is what wire
+//! consumers (a `ComponentRecord`'s `entity`/`data` carry only the
+//! component's raw bytes, keyed by the ID in the surrounding message) need
+//! when rendering something like an `entity_snapshot` or an
+//! `events.changed.{component}` subject back to a human-readable name. FNV-1a
+//! is also not collision-free, so two distinct names could in principle hash
+//! to the same 64-bit ID; [`ComponentRegistry::register`] catches that at
+//! registration time rather than letting the two types silently alias.
+
+use std::collections::HashMap;
+
+use crate::component::{Component, ComponentMeta, ComponentTypeId};
+
+/// Two distinct component names hashed to the same [`ComponentTypeId`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryCollision {
+    pub id: ComponentTypeId,
+    pub existing_name: &'static str,
+    pub incoming_name: &'static str,
+}
+
+impl std::fmt::Display for RegistryCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "component '{}' and '{}' both hash to {:?}",
+            self.existing_name, self.incoming_name, self.id
+        )
+    }
+}
+
+impl std::error::Error for RegistryCollision {}
+
+/// Maps [`ComponentTypeId`] back to the name and [`ComponentMeta`] of the
+/// component type it was derived from, populated as types are registered.
+#[derive(Debug, Default)]
+pub struct ComponentRegistry {
+    by_id: HashMap<ComponentTypeId, ComponentMeta>,
+}
+
+impl ComponentRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `T`, recording its [`ComponentMeta`] under its
+    /// [`ComponentTypeId`]. Re-registering the same type is a no-op; an
+    /// incoming name that hashes to an ID already held by a *different*
+    /// name is rejected as a [`RegistryCollision`] rather than silently
+    /// overwriting it.
+    pub fn register<T: Component>(&mut self) -> Result<(), RegistryCollision> {
+        self.register_meta(T::meta())
+    }
+
+    /// As [`Self::register`], but from an already-built [`ComponentMeta`] —
+    /// useful when the caller only has type-erased metadata on hand (e.g. a
+    /// schema-driven registration loop).
+    pub fn register_meta(&mut self, meta: ComponentMeta) -> Result<(), RegistryCollision> {
+        match self.by_id.get(&meta.type_id) {
+            Some(existing) if existing.name != meta.name => Err(RegistryCollision {
+                id: meta.type_id,
+                existing_name: existing.name,
+                incoming_name: meta.name,
+            }),
+            _ => {
+                self.by_id.insert(meta.type_id, meta);
+                Ok(())
+            }
+        }
+    }
+
+    /// The name a previously-registered [`ComponentTypeId`] was derived
+    /// from, or `None` if nothing has registered it yet.
+    #[must_use]
+    pub fn name_of(&self, id: ComponentTypeId) -> Option<&'static str> {
+        self.by_id.get(&id).map(|meta| meta.name)
+    }
+
+    /// The full [`ComponentMeta`] a previously-registered [`ComponentTypeId`]
+    /// was derived from.
+    #[must_use]
+    pub fn meta_of(&self, id: ComponentTypeId) -> Option<&ComponentMeta> {
+        self.by_id.get(&id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    struct Health {
+        current: f32,
+    }
+    impl Component for Health {
+        fn type_name() -> &'static str {
+            "Health"
+        }
+    }
+
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    struct Velocity {
+        x: f32,
+    }
+    impl Component for Velocity {
+        fn type_name() -> &'static str {
+            "Velocity"
+        }
+    }
+
+    #[test]
+    fn test_register_then_name_of_roundtrips() {
+        let mut registry = ComponentRegistry::new();
+        registry.register::<Health>().unwrap();
+        assert_eq!(
+            registry.name_of(Health::component_type_id()),
+            Some("Health")
+        );
+    }
+
+    #[test]
+    fn test_name_of_unregistered_id_is_none() {
+        let registry = ComponentRegistry::new();
+        assert_eq!(registry.name_of(Velocity::component_type_id()), None);
+    }
+
+    #[test]
+    fn test_meta_of_returns_full_metadata() {
+        let mut registry = ComponentRegistry::new();
+        registry.register::<Health>().unwrap();
+        let meta = registry.meta_of(Health::component_type_id()).unwrap();
+        assert_eq!(meta.name, "Health");
+    }
+
+    #[test]
+    fn test_re_registering_same_type_is_ok() {
+        let mut registry = ComponentRegistry::new();
+        registry.register::<Health>().unwrap();
+        registry.register::<Health>().unwrap();
+    }
+
+    #[test]
+    fn test_registering_distinct_types_does_not_collide() {
+        let mut registry = ComponentRegistry::new();
+        registry.register::<Health>().unwrap();
+        registry.register::<Velocity>().unwrap();
+        assert_eq!(
+            registry.name_of(Velocity::component_type_id()),
+            Some("Velocity")
+        );
+    }
+
+    #[test]
+    fn test_register_meta_detects_hash_collision() {
+        let mut registry = ComponentRegistry::new();
+        registry.register::<Health>().unwrap();
+
+        // Simulate a different name that happens to hash to the same ID —
+        // a real FNV-1a collision is astronomically unlikely to occur
+        // between "Health" and "Velocity", so we fabricate one directly.
+        let mut colliding_meta = Velocity::meta();
+        colliding_meta.type_id = Health::component_type_id();
+
+        let err = registry.register_meta(colliding_meta).unwrap_err();
+        assert_eq!(err.id, Health::component_type_id());
+        assert_eq!(err.existing_name, "Health");
+        assert_eq!(err.incoming_name, "Velocity");
+    }
+}
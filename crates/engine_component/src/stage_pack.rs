@@ -0,0 +1,188 @@
+//! Generic topological-depth + conflict-packing algorithm shared by
+//! `engine_app::scheduler::compute_stages` and
+//! `engine_system::schedule::Schedule::build`. Both group a list of systems
+//! into parallel-safe stages from the same two ingredients — an
+//! `order_after`/`order_before` dependency graph, and a conflict predicate
+//! over system indices — so the algorithm is lifted here once, and each
+//! caller supplies only its own notion of "conflict" (a bitmask in one
+//! crate, [`QueryDescriptor::conflicts_with`](crate::QueryDescriptor::conflicts_with)
+//! in the other) via its own per-stage accumulator type `S`.
+
+use std::collections::VecDeque;
+
+/// Packs `0..len` into stages such that no two systems sharing a stage
+/// conflict, honouring the `order_after`/`order_before` edges in
+/// `order_edges` (each `(pred, succ)` meaning `succ` must run in a stage
+/// strictly after `pred`'s *actual* assigned stage).
+///
+/// Runs Kahn's algorithm over `order_edges`, but — unlike computing a
+/// purely topological minimum depth up front and packing in a separate,
+/// later pass — interleaves placement with traversal: a system is only
+/// dequeued once every `order_edges` predecessor has already been placed,
+/// so its floor can be re-derived from where those predecessors *actually*
+/// landed (`1 + max(actual_stage(pred))`) rather than their topological
+/// depth alone. This matters whenever an unrelated conflict has pushed a
+/// predecessor later than its topological minimum — packing against the
+/// stale minimum would let a successor land in the same stage as, or
+/// earlier than, a predecessor it's supposed to strictly follow.
+///
+/// `conflicts` tests whether placing system `idx` into a stage's current
+/// accumulator `&S` would conflict; `merge` folds `idx`'s access into that
+/// accumulator once it's placed there. This lets each caller keep its own
+/// conflict representation instead of this module dictating one.
+///
+/// # Errors
+///
+/// Returns the indices that never reached in-degree zero — a cycle in
+/// `order_edges` — instead of a packed result.
+pub fn pack_into_stages<S: Default>(
+    len: usize,
+    order_edges: &[(usize, usize)],
+    mut conflicts: impl FnMut(&S, usize) -> bool,
+    mut merge: impl FnMut(&mut S, usize),
+) -> Result<Vec<Vec<usize>>, Vec<usize>> {
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); len];
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); len];
+    let mut in_degree = vec![0usize; len];
+    for &(pred, succ) in order_edges {
+        successors[pred].push(succ);
+        predecessors[succ].push(pred);
+        in_degree[succ] += 1;
+    }
+
+    let mut queue: VecDeque<usize> = (0..len).filter(|&i| in_degree[i] == 0).collect();
+    let mut visited = 0usize;
+
+    let mut actual_stage = vec![usize::MAX; len];
+    let mut stages: Vec<Vec<usize>> = Vec::new();
+    let mut stage_states: Vec<S> = Vec::new();
+
+    while let Some(i) = queue.pop_front() {
+        visited += 1;
+
+        // Every direct predecessor was placed before `i` could reach
+        // in-degree zero, so `actual_stage[p]` is always known here.
+        let floor = predecessors[i]
+            .iter()
+            .map(|&p| actual_stage[p] + 1)
+            .max()
+            .unwrap_or(0);
+
+        let mut placed_stage = None;
+        for (stage_idx, state) in stage_states.iter_mut().enumerate().skip(floor) {
+            if !conflicts(state, i) {
+                merge(state, i);
+                stages[stage_idx].push(i);
+                placed_stage = Some(stage_idx);
+                break;
+            }
+        }
+        let stage_idx = placed_stage.unwrap_or_else(|| {
+            while stages.len() < floor {
+                stages.push(Vec::new());
+                stage_states.push(S::default());
+            }
+            let idx = stages.len();
+            let mut state = S::default();
+            merge(&mut state, i);
+            stages.push(vec![i]);
+            stage_states.push(state);
+            idx
+        });
+        actual_stage[i] = stage_idx;
+
+        for &succ in &successors[i] {
+            in_degree[succ] -= 1;
+            if in_degree[succ] == 0 {
+                queue.push_back(succ);
+            }
+        }
+    }
+
+    if visited != len {
+        let cyclic = (0..len).filter(|&i| in_degree[i] > 0).collect();
+        return Err(cyclic);
+    }
+
+    stages.retain(|s| !s.is_empty());
+    Ok(stages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct SetAccumulator(Vec<u32>);
+
+    #[test]
+    fn test_no_systems_no_stages() {
+        let stages = pack_into_stages::<SetAccumulator>(0, &[], |_, _| false, |_, _| {}).unwrap();
+        assert!(stages.is_empty());
+    }
+
+    #[test]
+    fn test_disjoint_systems_share_one_stage() {
+        let stages =
+            pack_into_stages::<SetAccumulator>(2, &[], |_, _| false, |_, _| {}).unwrap();
+        assert_eq!(stages.len(), 1);
+        assert_eq!(stages[0], vec![0, 1]);
+    }
+
+    #[test]
+    fn test_conflicting_systems_split_into_stages() {
+        let conflict_tags: Vec<u32> = vec![1, 1];
+        let stages = pack_into_stages::<SetAccumulator>(
+            2,
+            &[],
+            |state: &SetAccumulator, idx| state.0.contains(&conflict_tags[idx]),
+            |state: &mut SetAccumulator, idx| state.0.push(conflict_tags[idx]),
+        )
+        .unwrap();
+        assert_eq!(stages.len(), 2);
+    }
+
+    #[test]
+    fn test_ordering_cycle_returns_cyclic_indices() {
+        let result = pack_into_stages::<SetAccumulator>(
+            2,
+            &[(0, 1), (1, 0)],
+            |_, _| false,
+            |_, _| {},
+        );
+        let mut cyclic = result.unwrap_err();
+        cyclic.sort_unstable();
+        assert_eq!(cyclic, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_unrelated_conflict_inflating_predecessors_actual_stage_pushes_successor_later() {
+        // `z` and `a` both touch tag 1 and must land in different stages.
+        // `c` has no conflict with anyone but declares order_after(a), so
+        // it must land strictly after `a`'s *actual* stage (1, not a's
+        // topological depth of 0).
+        let conflict_tags: [Option<u32>; 3] = [Some(1), Some(1), None];
+        let stages = pack_into_stages::<SetAccumulator>(
+            3,
+            &[(1, 2)], // a(1) -> c(2)
+            |state: &SetAccumulator, idx| {
+                conflict_tags[idx].is_some_and(|tag| state.0.contains(&tag))
+            },
+            |state: &mut SetAccumulator, idx| {
+                if let Some(tag) = conflict_tags[idx] {
+                    state.0.push(tag);
+                }
+            },
+        )
+        .unwrap();
+
+        let stage_of = |sys: usize| stages.iter().position(|s| s.contains(&sys)).unwrap();
+        assert_eq!(stage_of(0), 0, "z has no predecessors");
+        assert_eq!(stage_of(1), 1, "a conflicts with z, so must move to stage 1");
+        assert_eq!(
+            stage_of(2),
+            2,
+            "c must land strictly after a's actual stage (1), not a's topological depth (0)"
+        );
+    }
+}
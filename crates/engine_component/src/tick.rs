@@ -0,0 +1,102 @@
+//! Monotonic change-detection ticks, modeled on Bevy ECS's change-tick scheme.
+//!
+//! Every write to a component cell is stamped with the coordinator's current
+//! [`Tick`]. Comparing a cell's stamp against the tick a system last observed
+//! tells us whether that cell changed since then, which is the basis for
+//! shipping only modified components over NATS instead of a full snapshot
+//! every tick.
+
+use serde::{Deserialize, Serialize};
+
+/// A monotonically increasing tick counter that wraps at `u32::MAX`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize,
+)]
+pub struct Tick(pub u32);
+
+impl Tick {
+    /// The tick before any ticks have run. A system that has never observed
+    /// the world (`last_seen == Tick::ZERO`) should get a full snapshot
+    /// rather than a diff, since there's nothing to compare against.
+    pub const ZERO: Tick = Tick(0);
+
+    /// Returns the next tick, wrapping on overflow.
+    #[must_use]
+    pub fn next(self) -> Tick {
+        Tick(self.0.wrapping_add(1))
+    }
+
+    /// Returns `true` if `self` is strictly newer than `last_seen`.
+    ///
+    /// Compares via wrapping difference rather than `>` so a tick counter
+    /// that has wrapped around `u32::MAX` still orders correctly: a
+    /// difference in `1..u32::MAX/2` counts as newer, covering the wrap.
+    #[must_use]
+    pub fn is_newer_than(self, last_seen: Tick) -> bool {
+        let diff = self.0.wrapping_sub(last_seen.0);
+        diff != 0 && diff < u32::MAX / 2
+    }
+
+    /// Returns the oldest tick that can still be compared unambiguously
+    /// against `self` — `self` minus half the value space.
+    ///
+    /// Stored changed-ticks older than this floor should be clamped up to
+    /// it once per tick, otherwise a cell that hasn't changed in a very
+    /// long time could eventually wrap around and appear "newer" than the
+    /// current tick.
+    #[must_use]
+    pub fn wrap_floor(self) -> Tick {
+        Tick(self.0.wrapping_sub(u32::MAX / 2))
+    }
+
+    /// Clamp `self` up to `floor` if it has drifted older than `floor`.
+    #[must_use]
+    pub fn clamped_to(self, floor: Tick) -> Tick {
+        if floor.is_newer_than(self) {
+            floor
+        } else {
+            self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_wraps_at_u32_max() {
+        let t = Tick(u32::MAX);
+        assert_eq!(t.next(), Tick(0));
+    }
+
+    #[test]
+    fn test_is_newer_than_simple_case() {
+        assert!(Tick(5).is_newer_than(Tick(3)));
+        assert!(!Tick(3).is_newer_than(Tick(5)));
+        assert!(!Tick(5).is_newer_than(Tick(5)));
+    }
+
+    #[test]
+    fn test_is_newer_than_across_wraparound() {
+        // Tick(1) is "newer" than Tick(u32::MAX) because the counter wrapped.
+        assert!(Tick(1).is_newer_than(Tick(u32::MAX)));
+        assert!(!Tick(u32::MAX).is_newer_than(Tick(1)));
+    }
+
+    #[test]
+    fn test_clamped_to_raises_stale_ticks() {
+        let current = Tick(1_000_000);
+        let floor = current.wrap_floor();
+        let stale = Tick(0);
+        assert_eq!(stale.clamped_to(floor), floor);
+    }
+
+    #[test]
+    fn test_clamped_to_leaves_recent_ticks_alone() {
+        let current = Tick(1_000_000);
+        let floor = current.wrap_floor();
+        let recent = Tick(999_999);
+        assert_eq!(recent.clamped_to(floor), recent);
+    }
+}
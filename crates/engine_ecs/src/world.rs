@@ -3,13 +3,57 @@
 /// Components are schema-defined (not Rust types), so we store them as
 /// `serde_json::Value` keyed by component name. The schema is used at
 /// runtime to validate incoming data.
-use engine_schema::{Schema, TypeExpr};
+use engine_schema::{RecordDef, Schema, TypeExpr};
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 
 pub type EntityId = u64;
 
+/// Caps observer reentrancy so a cascading mutation (e.g. an `OnInsert`
+/// observer calling `set_component` again) can't recurse forever.
+const MAX_OBSERVER_DEPTH: u32 = 16;
+
+/// The moment in a component's lifecycle an observer fires at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriggerKind {
+    /// The component was not present on the entity before this write.
+    OnAdd,
+    /// The component was written, whether newly added or overwritten.
+    OnInsert,
+    /// The component is about to be removed (via `remove_component` or `despawn`).
+    OnRemove,
+}
+
+/// Which transition a [`World::query`] `changed_components` filter matches against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeMode {
+    /// Component appeared on an entity that lacked it.
+    Added,
+    /// Value overwritten on an entity that already had it.
+    Changed,
+    /// Component (or its entity) was removed.
+    Removed,
+    /// Added or changed this tick — the common case for systems that don't
+    /// care which transition happened, only that something did.
+    Any,
+}
+
+/// The event passed to an observer callback.
+#[derive(Debug, Clone)]
+pub struct Trigger {
+    pub entity: EntityId,
+    pub component: String,
+    pub kind: TriggerKind,
+    /// The new value for `OnAdd`/`OnInsert`, or the value being removed for `OnRemove`.
+    pub value: Value,
+}
+
+/// A callback registered via [`World::add_observer`]. Takes `&mut World` so
+/// observers can cascade further mutations (e.g. a `frozen` tag observer
+/// clearing `velocity`).
+type Observer = Box<dyn FnMut(&mut World, &Trigger)>;
+
 #[derive(Debug, Error)]
 pub enum WorldError {
     #[error("entity {0} not found")]
@@ -23,6 +67,8 @@ pub enum WorldError {
     },
     #[error("component '{0}' not found on entity {1}")]
     ComponentNotFound(String, EntityId),
+    #[error("resource '{0}' not found")]
+    ResourceNotFound(String),
 }
 
 /// A single entity's component set.
@@ -31,22 +77,133 @@ struct EntityData {
     components: HashMap<String, Value>,
 }
 
+/// Number of component bits packed into one word of a [`Bitmask`].
+const MASK_BITS: usize = u64::BITS as usize;
+
+/// A component-set signature: bit `i` set means the entity has the record
+/// assigned bit `i` by [`World::new`]. Entities sharing a signature live in
+/// the same archetype bucket.
+///
+/// Backed by a growable `Vec<u64>` rather than a single `u64`, so a schema
+/// isn't capped at 64 distinct records — a plain `u64` would panic
+/// (`1u64 << i` overflowing, debug builds) or silently wrap onto the wrong
+/// bit (release builds) past that, corrupting archetype signatures. Mirrors
+/// `engine_app::scheduler`'s `ConflictMask`, which solves the same problem
+/// for system read/write sets.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct Bitmask {
+    words: Vec<u64>,
+}
+
+impl Bitmask {
+    /// A mask with only bit `bit` set.
+    fn with_bit(bit: usize) -> Self {
+        let mut mask = Self::default();
+        mask.set(bit);
+        mask
+    }
+
+    fn set(&mut self, bit: usize) {
+        let word = bit / MASK_BITS;
+        if self.words.len() <= word {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1u64 << (bit % MASK_BITS);
+    }
+
+    /// `self | other`, widening to whichever mask has more words.
+    fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        if other.words.len() > result.words.len() {
+            result.words.resize(other.words.len(), 0);
+        }
+        for (a, b) in result.words.iter_mut().zip(other.words.iter()) {
+            *a |= b;
+        }
+        result
+    }
+
+    /// `true` if every bit set in `other` is also set in `self` — tests
+    /// "this signature has all of the `required` bits".
+    fn contains_all(&self, other: &Self) -> bool {
+        other.words.iter().enumerate().all(|(i, &word)| {
+            let mine = self.words.get(i).copied().unwrap_or(0);
+            mine & word == word
+        })
+    }
+
+    /// `true` if `self` and `other` share any set bit — tests "this
+    /// signature has any of the `forbidden` bits".
+    fn intersects(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .any(|(a, b)| a & b != 0)
+    }
+}
+
+/// A bucket of entities that all have exactly the same set of components.
+#[derive(Debug, Default)]
+struct Archetype {
+    /// Entities in this archetype, in insertion order.
+    entities: Vec<EntityId>,
+    /// Row data parallel to `entities`.
+    rows: Vec<EntityData>,
+}
+
 /// The ECS world: entity storage, schema-validated component operations.
 pub struct World {
     schema: Schema,
     next_entity: EntityId,
-    entities: HashMap<EntityId, EntityData>,
-    /// Tracks which entities had a component changed this tick (for `changed` queries).
+    /// Stable bit index assigned to each schema record, computed once at
+    /// construction time so signatures remain comparable across the world's
+    /// lifetime.
+    bit_for: HashMap<String, Bitmask>,
+    archetypes: HashMap<Bitmask, Archetype>,
+    /// Which archetype (by signature) and row index each entity currently occupies.
+    entity_location: HashMap<EntityId, (Bitmask, usize)>,
+    /// Entities that gained a component this tick that they didn't have before.
+    added: HashMap<String, HashSet<EntityId>>,
+    /// Entities whose existing component value was overwritten this tick.
     changed: HashMap<String, HashSet<EntityId>>,
+    /// Entities that lost a component (or were despawned) this tick.
+    removed: HashMap<String, HashSet<EntityId>>,
+    /// World-level singletons, schema-validated the same way as components
+    /// but not attached to any entity (e.g. simulation time, gravity config).
+    resources: HashMap<String, Value>,
+    /// Tracks which resources were written this tick.
+    resources_changed: HashSet<String>,
+    /// Registered reactive callbacks, keyed by component name and trigger kind.
+    observers: HashMap<(String, TriggerKind), Vec<Observer>>,
+    /// Current observer call-stack depth, to guard against unbounded reentrancy.
+    observer_depth: u32,
 }
 
 impl World {
     pub fn new(schema: Schema) -> Self {
+        // Assign bits in a stable (sorted) order so the same schema always
+        // produces the same signatures, regardless of HashMap iteration order.
+        let mut record_names = schema.record_names();
+        record_names.sort_unstable();
+        let bit_for = record_names
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| (name.to_string(), Bitmask::with_bit(i)))
+            .collect();
+
         Self {
             schema,
             next_entity: 1,
-            entities: HashMap::new(),
+            bit_for,
+            archetypes: HashMap::new(),
+            entity_location: HashMap::new(),
+            added: HashMap::new(),
             changed: HashMap::new(),
+            removed: HashMap::new(),
+            resources: HashMap::new(),
+            resources_changed: HashSet::new(),
+            observers: HashMap::new(),
+            observer_depth: 0,
         }
     }
 
@@ -55,6 +212,101 @@ impl World {
         &self.schema
     }
 
+    // -- Archetype storage --
+
+    /// The bit assigned to `component`, or 0 if the schema has no record by
+    /// that name (in which case no entity can ever carry it).
+    fn bit(&self, component: &str) -> Bitmask {
+        self.bit_for.get(component).cloned().unwrap_or_default()
+    }
+
+    fn signature_of(&self, data: &EntityData) -> Bitmask {
+        data.components
+            .keys()
+            .fold(Bitmask::default(), |acc, name| acc.union(&self.bit(name)))
+    }
+
+    /// Look up an entity's current row without removing it.
+    fn entity_data(&self, id: EntityId) -> Result<&EntityData, WorldError> {
+        let (signature, row) = self
+            .entity_location
+            .get(&id)
+            .ok_or(WorldError::EntityNotFound(id))?;
+        Ok(&self.archetypes[signature].rows[*row])
+    }
+
+    /// Remove an entity's row from whichever archetype holds it, fixing up
+    /// the displaced entity's location, and return the freed row.
+    fn remove_from_archetype(&mut self, id: EntityId) -> Option<EntityData> {
+        let (signature, row) = self.entity_location.remove(&id)?;
+        let archetype = self.archetypes.get_mut(&signature)?;
+        let data = archetype.rows.swap_remove(row);
+        archetype.entities.swap_remove(row);
+
+        if row < archetype.entities.len() {
+            let moved_id = archetype.entities[row];
+            self.entity_location.insert(moved_id, (signature.clone(), row));
+        }
+        if archetype.entities.is_empty() {
+            self.archetypes.remove(&signature);
+        }
+
+        Some(data)
+    }
+
+    /// Insert a row into the archetype for `signature`, creating it if needed.
+    fn insert_into_archetype(&mut self, id: EntityId, signature: Bitmask, data: EntityData) {
+        let archetype = self.archetypes.entry(signature.clone()).or_default();
+        let row = archetype.entities.len();
+        archetype.entities.push(id);
+        archetype.rows.push(data);
+        self.entity_location.insert(id, (signature, row));
+    }
+
+    // -- Observers --
+
+    /// Register a callback that fires whenever `component` transitions
+    /// through `kind` on any entity.
+    pub fn add_observer<F>(&mut self, component: &str, kind: TriggerKind, observer: F)
+    where
+        F: FnMut(&mut World, &Trigger) + 'static,
+    {
+        self.observers
+            .entry((component.to_string(), kind))
+            .or_default()
+            .push(Box::new(observer));
+    }
+
+    /// Fire all observers registered for `(component, kind)`.
+    ///
+    /// Callbacks are removed from `self.observers` for the duration of the
+    /// call so they can take `&mut World` (including re-registering more
+    /// observers) without conflicting with the borrow on the map itself.
+    fn fire(&mut self, component: &str, kind: TriggerKind, entity: EntityId, value: Value) {
+        if self.observer_depth >= MAX_OBSERVER_DEPTH {
+            return;
+        }
+        let key = (component.to_string(), kind);
+        let Some(mut callbacks) = self.observers.remove(&key) else {
+            return;
+        };
+
+        let trigger = Trigger {
+            entity,
+            component: component.to_string(),
+            kind,
+            value,
+        };
+
+        self.observer_depth += 1;
+        for callback in &mut callbacks {
+            callback(self, &trigger);
+        }
+        self.observer_depth -= 1;
+
+        self.observers.entry(key).or_default().extend(callbacks);
+    }
+
     // -- Entity lifecycle --
 
     /// Spawn a new entity, optionally with initial components.
@@ -66,25 +318,48 @@ impl World {
         self.next_entity += 1;
 
         let mut data = EntityData::default();
+        let mut to_fire = Vec::new();
 
         if let Some(comps) = components {
             for (name, value) in comps {
                 self.validate_component(&name, &value)?;
-                self.mark_changed(&name, id);
-                data.components.insert(name, value);
+                self.mark_added(&name, id);
+                data.components.insert(name.clone(), value.clone());
+                to_fire.push((name, value));
             }
         }
 
-        self.entities.insert(id, data);
+        let signature = self.signature_of(&data);
+        self.insert_into_archetype(id, signature, data);
+
+        for (name, value) in to_fire {
+            self.fire(&name, TriggerKind::OnAdd, id, value.clone());
+            self.fire(&name, TriggerKind::OnInsert, id, value);
+        }
+
         Ok(id)
     }
 
     /// Despawn an entity, removing all its components.
     pub fn despawn(&mut self, id: EntityId) -> Result<(), WorldError> {
-        if self.entities.remove(&id).is_none() {
-            return Err(WorldError::EntityNotFound(id));
+        let components: Vec<(String, Value)> = self
+            .entity_data(id)?
+            .components
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        for (name, value) in components {
+            self.fire(&name, TriggerKind::OnRemove, id, value);
+            self.mark_removed(&name, id);
+        }
+
+        self.remove_from_archetype(id);
+        // An entity can't be "added" or "changed" once it's gone; the removal
+        // itself was just recorded above and should survive this cleanup.
+        for set in self.added.values_mut() {
+            set.remove(&id);
         }
-        // Clean up change tracking
         for set in self.changed.values_mut() {
             set.remove(&id);
         }
@@ -93,17 +368,17 @@ impl World {
 
     /// Check if an entity exists.
     pub fn exists(&self, id: EntityId) -> bool {
-        self.entities.contains_key(&id)
+        self.entity_location.contains_key(&id)
     }
 
     /// Return all entity IDs.
     pub fn all_entities(&self) -> Vec<EntityId> {
-        self.entities.keys().copied().collect()
+        self.entity_location.keys().copied().collect()
     }
 
     /// Return the count of live entities.
     pub fn entity_count(&self) -> usize {
-        self.entities.len()
+        self.entity_location.len()
     }
 
     // -- Component operations --
@@ -116,62 +391,125 @@ impl World {
         value: Value,
     ) -> Result<(), WorldError> {
         self.validate_component(component, &value)?;
-        let data = self
-            .entities
-            .get_mut(&id)
+        let (signature, row) = self
+            .entity_location
+            .get(&id)
+            .cloned()
             .ok_or(WorldError::EntityNotFound(id))?;
-        data.components.insert(component.to_string(), value);
-        self.mark_changed(component, id);
+        let is_new = !self.archetypes[&signature].rows[row]
+            .components
+            .contains_key(component);
+
+        if is_new {
+            // Adding a component changes the entity's signature, so it has
+            // to move into a (possibly new) archetype.
+            let mut data = self
+                .remove_from_archetype(id)
+                .expect("entity location was just checked above");
+            data.components.insert(component.to_string(), value.clone());
+            let new_signature = self.signature_of(&data);
+            self.insert_into_archetype(id, new_signature, data);
+        } else {
+            self.archetypes.get_mut(&signature).unwrap().rows[row]
+                .components
+                .insert(component.to_string(), value.clone());
+        }
+        if is_new {
+            self.mark_added(component, id);
+        } else {
+            self.mark_changed(component, id);
+        }
+
+        if is_new {
+            self.fire(component, TriggerKind::OnAdd, id, value.clone());
+        }
+        self.fire(component, TriggerKind::OnInsert, id, value);
         Ok(())
     }
 
     /// Get a component value from an entity.
     pub fn get_component(&self, id: EntityId, component: &str) -> Result<&Value, WorldError> {
-        let data = self
-            .entities
-            .get(&id)
-            .ok_or(WorldError::EntityNotFound(id))?;
-        data.components
+        self.entity_data(id)?
+            .components
             .get(component)
             .ok_or_else(|| WorldError::ComponentNotFound(component.to_string(), id))
     }
 
     /// Remove a component from an entity.
     pub fn remove_component(&mut self, id: EntityId, component: &str) -> Result<(), WorldError> {
-        let data = self
-            .entities
-            .get_mut(&id)
-            .ok_or(WorldError::EntityNotFound(id))?;
-        if data.components.remove(component).is_none() {
+        if !self.entity_data(id)?.components.contains_key(component) {
             return Err(WorldError::ComponentNotFound(component.to_string(), id));
         }
+
+        let mut data = self
+            .remove_from_archetype(id)
+            .expect("entity location was just checked above");
+        let old_value = data
+            .components
+            .remove(component)
+            .expect("presence was just checked above");
+        let new_signature = self.signature_of(&data);
+        self.insert_into_archetype(id, new_signature, data);
+
+        self.fire(component, TriggerKind::OnRemove, id, old_value);
+        self.mark_removed(component, id);
         Ok(())
     }
 
     /// Check if an entity has a specific component.
     pub fn has_component(&self, id: EntityId, component: &str) -> bool {
-        self.entities
-            .get(&id)
+        self.entity_data(id)
             .map(|d| d.components.contains_key(component))
             .unwrap_or(false)
     }
 
     /// Get all component names on an entity.
     pub fn entity_components(&self, id: EntityId) -> Result<Vec<String>, WorldError> {
-        let data = self
-            .entities
-            .get(&id)
-            .ok_or(WorldError::EntityNotFound(id))?;
-        Ok(data.components.keys().cloned().collect())
+        Ok(self.entity_data(id)?.components.keys().cloned().collect())
     }
 
     /// Get all components on an entity as a map.
     pub fn entity_snapshot(&self, id: EntityId) -> Result<&HashMap<String, Value>, WorldError> {
-        let data = self
-            .entities
-            .get(&id)
-            .ok_or(WorldError::EntityNotFound(id))?;
-        Ok(&data.components)
+        Ok(&self.entity_data(id)?.components)
+    }
+
+    // -- Resources --
+
+    /// Set a world-level resource, schema-validated like a component.
+    ///
+    /// Unlike components, resources aren't attached to any entity, so
+    /// there's no archetype move to perform — just a direct insert.
+    pub fn set_resource(&mut self, name: &str, value: Value) -> Result<(), WorldError> {
+        self.validate_component(name, &value)?;
+        self.resources.insert(name.to_string(), value);
+        self.resources_changed.insert(name.to_string());
+        Ok(())
+    }
+
+    /// Get a resource value.
+    pub fn get_resource(&self, name: &str) -> Result<&Value, WorldError> {
+        self.resources
+            .get(name)
+            .ok_or_else(|| WorldError::ResourceNotFound(name.to_string()))
+    }
+
+    /// Check if a resource is present.
+    pub fn has_resource(&self, name: &str) -> bool {
+        self.resources.contains_key(name)
+    }
+
+    /// Remove a resource.
+    pub fn remove_resource(&mut self, name: &str) -> Result<(), WorldError> {
+        self.resources
+            .remove(name)
+            .ok_or_else(|| WorldError::ResourceNotFound(name.to_string()))?;
+        self.resources_changed.remove(name);
+        Ok(())
+    }
+
+    /// Check whether a resource was written this tick.
+    pub fn resource_changed(&self, name: &str) -> bool {
+        self.resources_changed.contains(name)
     }
 
     // -- Query --
@@ -180,49 +518,61 @@ impl World {
     ///
     ///  - `with`: entity must have ALL of these components
     ///  - `without`: entity must have NONE of these components
-    ///  - `changed_components`: if non-empty, at least one must be in the changed set
+    ///  - `changed_components`: if non-empty, at least one must match `mode` this tick
+    ///
+    /// Rather than scanning every entity, this computes a required/forbidden
+    /// bitmask from `with`/`without` and only visits archetypes whose
+    /// signature satisfies both, so the cost scales with the number of
+    /// matching archetypes rather than the total entity count.
     pub fn query(
         &self,
         with: &[String],
         without: &[String],
         changed_components: &[String],
+        mode: ChangeMode,
     ) -> Vec<EntityId> {
-        self.entities
+        // A `with` name the schema doesn't know can never be on any entity.
+        if with.iter().any(|c| !self.bit_for.contains_key(c)) {
+            return Vec::new();
+        }
+
+        let required = with
             .iter()
-            .filter(|(id, data)| {
-                // Must have all `with` components
-                let has_all = with.iter().all(|c| data.components.contains_key(c));
-                if !has_all {
-                    return false;
-                }
+            .fold(Bitmask::default(), |acc, c| acc.union(&self.bit(c)));
+        let forbidden = without
+            .iter()
+            .fold(Bitmask::default(), |acc, c| acc.union(&self.bit(c)));
 
-                // Must not have any `without` components
-                let has_none = without.iter().all(|c| !data.components.contains_key(c));
-                if !has_none {
-                    return false;
-                }
+        let mut results = Vec::new();
+        for (signature, archetype) in &self.archetypes {
+            if !signature.contains_all(&required) || signature.intersects(&forbidden) {
+                continue;
+            }
 
-                // If changed filter is specified, at least one must have changed
+            for &id in &archetype.entities {
                 if !changed_components.is_empty() {
-                    let any_changed = changed_components.iter().any(|c| {
-                        self.changed
-                            .get(c)
-                            .map(|set| set.contains(id))
-                            .unwrap_or(false)
-                    });
-                    if !any_changed {
-                        return false;
+                    let matches = changed_components
+                        .iter()
+                        .any(|c| self.matches_change_mode(c, id, mode));
+                    if !matches {
+                        continue;
                     }
                 }
-
-                true
-            })
-            .map(|(id, _)| *id)
-            .collect()
+                results.push(id);
+            }
+        }
+        results
     }
 
     // -- Change tracking --
 
+    fn mark_added(&mut self, component: &str, entity: EntityId) {
+        self.added
+            .entry(component.to_string())
+            .or_default()
+            .insert(entity);
+    }
+
     fn mark_changed(&mut self, component: &str, entity: EntityId) {
         self.changed
             .entry(component.to_string())
@@ -230,17 +580,48 @@ impl World {
             .insert(entity);
     }
 
+    fn mark_removed(&mut self, component: &str, entity: EntityId) {
+        self.removed
+            .entry(component.to_string())
+            .or_default()
+            .insert(entity);
+    }
+
+    fn matches_change_mode(&self, component: &str, entity: EntityId, mode: ChangeMode) -> bool {
+        let contains = |set: &HashMap<String, HashSet<EntityId>>| {
+            set.get(component)
+                .map(|s| s.contains(&entity))
+                .unwrap_or(false)
+        };
+        match mode {
+            ChangeMode::Added => contains(&self.added),
+            ChangeMode::Changed => contains(&self.changed),
+            ChangeMode::Removed => contains(&self.removed),
+            ChangeMode::Any => contains(&self.added) || contains(&self.changed),
+        }
+    }
+
     /// Clear all change tracking. Call this at the end of each tick.
     pub fn clear_changes(&mut self) {
+        self.added.clear();
         self.changed.clear();
+        self.removed.clear();
+        self.resources_changed.clear();
     }
 
-    /// Get entities that had a specific component changed.
+    /// Get entities that gained `component` this tick.
+    pub fn get_added(&self, component: &str) -> HashSet<EntityId> {
+        self.added.get(component).cloned().unwrap_or_default()
+    }
+
+    /// Get entities that had `component` overwritten (not newly added) this tick.
     pub fn get_changed(&self, component: &str) -> HashSet<EntityId> {
-        self.changed
-            .get(component)
-            .cloned()
-            .unwrap_or_default()
+        self.changed.get(component).cloned().unwrap_or_default()
+    }
+
+    /// Get entities that lost `component` (via `remove_component` or `despawn`) this tick.
+    pub fn get_removed(&self, component: &str) -> HashSet<EntityId> {
+        self.removed.get(component).cloned().unwrap_or_default()
     }
 
     // -- Validation --
@@ -252,24 +633,42 @@ impl World {
             .get_record(name)
             .ok_or_else(|| WorldError::UnknownRecord(name.to_string()))?;
 
+        let mut visited = HashSet::new();
+        visited.insert(name.to_string());
+        self.validate_record(record, value, name, name, &mut visited)
+    }
+
+    /// Validate a record's fields, recursing into nested `Named` types.
+    ///
+    /// `path` is the dotted/indexed location of `value` within the
+    /// top-level component (e.g. `inventory[2]`), used to build
+    /// path-qualified error messages as we descend into fields.
+    fn validate_record(
+        &self,
+        record: &RecordDef,
+        value: &Value,
+        component: &str,
+        path: &str,
+        visited: &mut HashSet<String>,
+    ) -> Result<(), WorldError> {
         // Tag (empty record) — must be null or empty object
         if record.is_tag() {
-            match value {
-                Value::Null => return Ok(()),
-                Value::Object(_) => return Ok(()),
-                _ => {
-                    return Err(WorldError::ValidationError {
-                        component: name.to_string(),
-                        message: "tag component must be null or empty object".to_string(),
-                    })
-                }
-            }
+            return match value {
+                Value::Null | Value::Object(_) => Ok(()),
+                _ => Err(WorldError::ValidationError {
+                    component: component.to_string(),
+                    message: format!(
+                        "field '{}': tag component must be null or empty object",
+                        path
+                    ),
+                }),
+            };
         }
 
-        // Component with fields — expect a JSON object
+        // Record with fields — expect a JSON object
         let obj = value.as_object().ok_or_else(|| WorldError::ValidationError {
-            component: name.to_string(),
-            message: "expected JSON object".to_string(),
+            component: component.to_string(),
+            message: format!("field '{}': expected JSON object", path),
         })?;
 
         // Check required fields are present
@@ -280,8 +679,8 @@ impl World {
                     continue;
                 }
                 return Err(WorldError::ValidationError {
-                    component: name.to_string(),
-                    message: format!("missing required field '{}'", field.name),
+                    component: component.to_string(),
+                    message: format!("field '{}': missing required field '{}'", path, field.name),
                 });
             }
         }
@@ -289,7 +688,8 @@ impl World {
         // Type validation for each provided field
         for field in &record.fields {
             if let Some(val) = obj.get(&field.name) {
-                self.validate_value(val, &field.ty, name, &field.name)?;
+                let field_path = format!("{}.{}", path, field.name);
+                self.validate_value(val, &field.ty, component, &field_path, visited)?;
             }
         }
 
@@ -301,11 +701,12 @@ impl World {
         value: &Value,
         ty: &TypeExpr,
         component: &str,
-        field: &str,
+        path: &str,
+        visited: &mut HashSet<String>,
     ) -> Result<(), WorldError> {
         let err = |msg: String| WorldError::ValidationError {
             component: component.to_string(),
-            message: format!("field '{}': {}", field, msg),
+            message: format!("field '{}': {}", path, msg),
         };
 
         match ty {
@@ -336,29 +737,29 @@ impl World {
                 }
                 _ => {}
             },
-            TypeExpr::Named(_) => {
-                // Named types (other records, enums, etc.) — accept any valid JSON for now.
-                // Full recursive validation could resolve aliases, but that's a future enhancement.
-            }
-            TypeExpr::List(_inner) => {
-                value
+            TypeExpr::Named(name) => return self.validate_named(value, name, component, path, visited),
+            TypeExpr::List(inner) | TypeExpr::Set(inner) => {
+                let arr = value
                     .as_array()
                     .ok_or_else(|| err("expected array".into()))?;
+                for (i, item) in arr.iter().enumerate() {
+                    self.validate_value(item, inner, component, &format!("{}[{}]", path, i), visited)?;
+                }
             }
             TypeExpr::Option(inner) => {
                 if !value.is_null() {
-                    self.validate_value(value, inner, component, field)?;
+                    self.validate_value(value, inner, component, path, visited)?;
                 }
             }
-            TypeExpr::Set(_) => {
-                value
-                    .as_array()
-                    .ok_or_else(|| err("expected array for set".into()))?;
-            }
-            TypeExpr::Map(_, _) => {
-                value
+            TypeExpr::Map(_key_ty, value_ty) => {
+                let obj = value
                     .as_object()
                     .ok_or_else(|| err("expected object for map".into()))?;
+                // Map keys are always JSON strings, so there's nothing useful to
+                // check them against beyond that; only the values are validated.
+                for (key, val) in obj {
+                    self.validate_value(val, value_ty, component, &format!("{}.{}", path, key), visited)?;
+                }
             }
             TypeExpr::Tuple(types) => {
                 let arr = value
@@ -371,11 +772,133 @@ impl World {
                         types.len()
                     )));
                 }
+                for (i, (item, item_ty)) in arr.iter().zip(types.iter()).enumerate() {
+                    self.validate_value(item, item_ty, component, &format!("{}[{}]", path, i), visited)?;
+                }
             }
         }
 
         Ok(())
     }
+
+    /// Resolve a `Named` type reference against the schema and validate
+    /// `value` against whatever it resolves to: a record (recurse into its
+    /// fields), an enum (bare string or single-key tagged object matching a
+    /// known variant), a tagged-union variant (same shape, plus payload
+    /// types), flags (array of known flag names), or a type alias (resolve
+    /// and validate against its target).
+    ///
+    /// `visited` guards against self-referential schemas (e.g. a `tree`
+    /// record with a `children: list<tree>` field): once a type name is on
+    /// the current recursion path, further occurrences are accepted without
+    /// descending again.
+    fn validate_named(
+        &self,
+        value: &Value,
+        name: &str,
+        component: &str,
+        path: &str,
+        visited: &mut HashSet<String>,
+    ) -> Result<(), WorldError> {
+        let err = |msg: String| WorldError::ValidationError {
+            component: component.to_string(),
+            message: format!("field '{}': {}", path, msg),
+        };
+
+        if !visited.insert(name.to_string()) {
+            return Ok(());
+        }
+
+        let result = if let Some(record) = self.schema.get_record(name) {
+            self.validate_record(record, value, component, path, visited)
+        } else if let Some(en) = self.schema.enums.get(name) {
+            let variant = match value {
+                Value::String(s) => Some(s.as_str()),
+                Value::Object(obj) if obj.len() == 1 => obj.keys().next().map(|s| s.as_str()),
+                _ => None,
+            };
+            match variant {
+                Some(v) if en.variants.iter().any(|candidate| candidate == v) => Ok(()),
+                Some(v) => Err(err(format!("unknown variant '{}' for enum '{}'", v, name))),
+                None => Err(err(format!(
+                    "expected string or single-key object for enum '{}'",
+                    name
+                ))),
+            }
+        } else if let Some(vr) = self.schema.variants.get(name) {
+            match value {
+                Value::String(s) => match vr.cases.iter().find(|c| &c.name == s) {
+                    Some(case) if case.payload.as_ref().is_none_or(|p| p.is_empty()) => Ok(()),
+                    Some(_) => Err(err(format!("case '{}' of '{}' requires a payload", s, name))),
+                    None => Err(err(format!("unknown case '{}' for variant '{}'", s, name))),
+                },
+                Value::Object(obj) if obj.len() == 1 => {
+                    let case_name = obj.keys().next().expect("len checked above");
+                    let payload = obj.values().next().expect("len checked above");
+                    match vr.cases.iter().find(|c| &c.name == case_name) {
+                        Some(case) => {
+                            let types = case.payload.clone().unwrap_or_default();
+                            let values = payload.as_array().cloned().unwrap_or_else(|| vec![payload.clone()]);
+                            if values.len() != types.len() {
+                                Err(err(format!(
+                                    "case '{}' has {} payload value(s), expected {}",
+                                    case_name,
+                                    values.len(),
+                                    types.len()
+                                )))
+                            } else {
+                                let mut outcome = Ok(());
+                                for (i, (item, item_ty)) in values.iter().zip(types.iter()).enumerate() {
+                                    let item_path = format!("{}.{}[{}]", path, case_name, i);
+                                    outcome = self.validate_value(item, item_ty, component, &item_path, visited);
+                                    if outcome.is_err() {
+                                        break;
+                                    }
+                                }
+                                outcome
+                            }
+                        }
+                        None => Err(err(format!(
+                            "unknown case '{}' for variant '{}'",
+                            case_name, name
+                        ))),
+                    }
+                }
+                _ => Err(err(format!(
+                    "expected string or single-key object for variant '{}'",
+                    name
+                ))),
+            }
+        } else if let Some(flags) = self.schema.flags.get(name) {
+            match value.as_array() {
+                Some(arr) => {
+                    let mut outcome = Ok(());
+                    for item in arr {
+                        match item.as_str() {
+                            Some(s) if flags.flags.iter().any(|candidate| candidate == s) => {}
+                            Some(s) => {
+                                outcome = Err(err(format!("unknown flag '{}' for '{}'", s, name)));
+                                break;
+                            }
+                            None => {
+                                outcome = Err(err("expected array of flag-name strings".into()));
+                                break;
+                            }
+                        }
+                    }
+                    outcome
+                }
+                None => Err(err(format!("expected array of flag names for '{}'", name))),
+            }
+        } else if let Some(alias) = self.schema.type_aliases.get(name) {
+            self.validate_value(value, &alias.target, component, path, visited)
+        } else {
+            Err(WorldError::UnknownRecord(name.to_string()))
+        };
+
+        visited.remove(name);
+        result
+    }
 }
 
 #[cfg(test)]
@@ -471,23 +994,140 @@ mod tests {
             &["transform".into(), "velocity".into()],
             &["frozen".into()],
             &[],
+            ChangeMode::Any,
         );
         assert_eq!(results, vec![e1]);
     }
 
+    #[test]
+    fn test_query_unknown_component_matches_nothing() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        world
+            .set_component(id, "transform", serde_json::json!({"x": 0, "y": 0, "z": 0}))
+            .unwrap();
+
+        assert!(world
+            .query(&["nonexistent".into()], &[], &[], ChangeMode::Any)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_component_moves_entity_between_archetypes() {
+        let mut world = make_test_world();
+        let e1 = world.spawn(None).unwrap();
+        world
+            .set_component(e1, "transform", serde_json::json!({"x": 0, "y": 0, "z": 0}))
+            .unwrap();
+
+        // Before adding velocity, e1 shouldn't match a `with: [velocity]` query.
+        assert!(world
+            .query(&["velocity".into()], &[], &[], ChangeMode::Any)
+            .is_empty());
+
+        world
+            .set_component(e1, "velocity", serde_json::json!({"x": 1, "y": 0}))
+            .unwrap();
+        assert_eq!(
+            world.query(&["velocity".into()], &[], &[], ChangeMode::Any),
+            vec![e1]
+        );
+
+        // Removing transform moves it to a different archetype again, and all
+        // data (including the untouched `velocity`) must survive the move.
+        world.remove_component(e1, "transform").unwrap();
+        assert!(!world.has_component(e1, "transform"));
+        assert!(world.has_component(e1, "velocity"));
+    }
+
     #[test]
     fn test_change_tracking() {
         let mut world = make_test_world();
         let id = world.spawn(None).unwrap();
+
+        // First write: the entity lacked `transform`, so this is an add, not a change.
         world
             .set_component(id, "transform", serde_json::json!({"x": 0, "y": 0, "z": 0}))
             .unwrap();
+        assert!(world.get_added("transform").contains(&id));
+        assert!(!world.get_changed("transform").contains(&id));
+
+        world.clear_changes();
+        assert!(!world.get_added("transform").contains(&id));
 
+        // Overwriting an existing component is a change, not an add.
+        world
+            .set_component(id, "transform", serde_json::json!({"x": 1, "y": 0, "z": 0}))
+            .unwrap();
         assert!(world.get_changed("transform").contains(&id));
+        assert!(!world.get_added("transform").contains(&id));
+
         world.clear_changes();
         assert!(!world.get_changed("transform").contains(&id));
     }
 
+    #[test]
+    fn test_removed_tracking() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        world
+            .set_component(id, "transform", serde_json::json!({"x": 0, "y": 0, "z": 0}))
+            .unwrap();
+        world.clear_changes();
+
+        world.remove_component(id, "transform").unwrap();
+        assert!(world.get_removed("transform").contains(&id));
+        assert!(!world.get_changed("transform").contains(&id));
+
+        world.clear_changes();
+        assert!(!world.get_removed("transform").contains(&id));
+    }
+
+    #[test]
+    fn test_despawn_marks_removed_and_clears_added_changed() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        world
+            .set_component(id, "transform", serde_json::json!({"x": 0, "y": 0, "z": 0}))
+            .unwrap();
+
+        world.despawn(id).unwrap();
+        assert!(world.get_removed("transform").contains(&id));
+        assert!(!world.get_added("transform").contains(&id));
+    }
+
+    #[test]
+    fn test_query_change_mode_filters() {
+        let mut world = make_test_world();
+        let e1 = world.spawn(None).unwrap();
+        world
+            .set_component(e1, "transform", serde_json::json!({"x": 0, "y": 0, "z": 0}))
+            .unwrap();
+
+        // Freshly added: matches Added/Any, not Changed/Removed.
+        assert_eq!(
+            world.query(&[], &[], &["transform".into()], ChangeMode::Added),
+            vec![e1]
+        );
+        assert!(world
+            .query(&[], &[], &["transform".into()], ChangeMode::Changed)
+            .is_empty());
+
+        world.clear_changes();
+        world
+            .set_component(e1, "transform", serde_json::json!({"x": 1, "y": 0, "z": 0}))
+            .unwrap();
+
+        // Now it's an overwrite: matches Changed/Any, not Added.
+        assert_eq!(
+            world.query(&[], &[], &["transform".into()], ChangeMode::Changed),
+            vec![e1]
+        );
+        assert!(world
+            .query(&[], &[], &["transform".into()], ChangeMode::Added)
+            .is_empty());
+    }
+
     #[test]
     fn test_validation_rejects_bad_data() {
         let mut world = make_test_world();
@@ -502,6 +1142,132 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn make_recursive_test_world() -> World {
+        let mut schema = Schema::new();
+        schema
+            .load_source(
+                r#"
+            package test:recursive@0.1.0
+
+            record item {
+                count: u32,
+            }
+
+            record inventory {
+                items: list<item>,
+            }
+
+            record tree {
+                label: string,
+                children: list<tree>,
+            }
+
+            enum color { red, green, blue }
+
+            variant shape {
+                circle(f32),
+                rect(f32, f32),
+                point,
+            }
+
+            flags layers { ground, water, air }
+
+            record tagged {
+                tint: color,
+                outline: shape,
+                mask: layers,
+            }
+        "#,
+            )
+            .unwrap();
+        World::new(schema)
+    }
+
+    #[test]
+    fn test_nested_record_validation_reports_qualified_path() {
+        let mut world = make_recursive_test_world();
+        let id = world.spawn(None).unwrap();
+
+        let err = world
+            .set_component(
+                id,
+                "inventory",
+                serde_json::json!({"items": [{"count": 1}, {"count": "not a number"}]}),
+            )
+            .unwrap_err();
+        assert!(matches!(err, WorldError::ValidationError { .. }));
+        assert!(err.to_string().contains("items[1].count"));
+
+        world
+            .set_component(id, "inventory", serde_json::json!({"items": [{"count": 1}]}))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_self_referential_record_does_not_infinite_loop() {
+        let mut world = make_recursive_test_world();
+        let id = world.spawn(None).unwrap();
+
+        world
+            .set_component(
+                id,
+                "tree",
+                serde_json::json!({
+                    "label": "root",
+                    "children": [
+                        {"label": "child", "children": []}
+                    ],
+                }),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_enum_variant_flags_validation() {
+        let mut world = make_recursive_test_world();
+        let id = world.spawn(None).unwrap();
+
+        // Valid: bare string enum variant, tagged-union case with payload, flag list.
+        world
+            .set_component(
+                id,
+                "tagged",
+                serde_json::json!({
+                    "tint": "red",
+                    "outline": {"circle": 1.5},
+                    "mask": ["ground", "air"],
+                }),
+            )
+            .unwrap();
+
+        // Unknown enum variant.
+        assert!(world
+            .set_component(
+                id,
+                "tagged",
+                serde_json::json!({"tint": "purple", "outline": "point", "mask": []}),
+            )
+            .is_err());
+
+        // Unknown flag name.
+        assert!(world
+            .set_component(
+                id,
+                "tagged",
+                serde_json::json!({"tint": "red", "outline": "point", "mask": ["fire"]}),
+            )
+            .is_err());
+
+        // Variant case with wrong payload arity.
+        assert!(world
+            .set_component(
+                id,
+                "tagged",
+                serde_json::json!({"tint": "red", "outline": {"rect": [1.0]}, "mask": []}),
+            )
+            .is_err());
+    }
+
     #[test]
     fn test_despawn() {
         let mut world = make_test_world();
@@ -510,4 +1276,149 @@ mod tests {
         world.despawn(id).unwrap();
         assert!(!world.exists(id));
     }
+
+    #[test]
+    fn test_set_component_fires_on_add_then_on_insert() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let add_log = log.clone();
+        let insert_log = log.clone();
+        world.add_observer("transform", TriggerKind::OnAdd, move |_, _| {
+            add_log.borrow_mut().push("add");
+        });
+        world.add_observer("transform", TriggerKind::OnInsert, move |_, _| {
+            insert_log.borrow_mut().push("insert");
+        });
+
+        world
+            .set_component(id, "transform", serde_json::json!({"x": 0, "y": 0, "z": 0}))
+            .unwrap();
+        assert_eq!(*log.borrow(), vec!["add", "insert"]);
+
+        // Overwriting an existing component only fires OnInsert.
+        world
+            .set_component(id, "transform", serde_json::json!({"x": 1, "y": 0, "z": 0}))
+            .unwrap();
+        assert_eq!(*log.borrow(), vec!["add", "insert", "insert"]);
+    }
+
+    #[test]
+    fn test_despawn_fires_on_remove_for_each_component() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        world
+            .set_component(id, "transform", serde_json::json!({"x": 0, "y": 0, "z": 0}))
+            .unwrap();
+        world.set_component(id, "frozen", Value::Null).unwrap();
+
+        let removed = Rc::new(RefCell::new(Vec::new()));
+        let transform_removed = removed.clone();
+        let frozen_removed = removed.clone();
+        world.add_observer("transform", TriggerKind::OnRemove, move |_, t| {
+            transform_removed.borrow_mut().push(t.component.clone());
+        });
+        world.add_observer("frozen", TriggerKind::OnRemove, move |_, t| {
+            frozen_removed.borrow_mut().push(t.component.clone());
+        });
+
+        world.despawn(id).unwrap();
+        removed.borrow_mut().sort();
+        assert_eq!(*removed.borrow(), vec!["frozen", "transform"]);
+    }
+
+    #[test]
+    fn test_observer_can_cascade_into_world_mutation() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        world.set_component(id, "frozen", Value::Null).unwrap();
+
+        // A `frozen` observer clears `velocity` on the same entity.
+        world.add_observer("frozen", TriggerKind::OnAdd, move |world, trigger| {
+            let _ = world.remove_component(trigger.entity, "velocity");
+        });
+
+        world
+            .set_component(id, "velocity", serde_json::json!({"x": 1, "y": 0}))
+            .unwrap();
+        assert!(world.has_component(id, "velocity"));
+
+        // Re-inserting `frozen` on a fresh entity fires OnAdd and cascades.
+        let id2 = world.spawn(None).unwrap();
+        world
+            .set_component(id2, "velocity", serde_json::json!({"x": 1, "y": 0}))
+            .unwrap();
+        world.set_component(id2, "frozen", Value::Null).unwrap();
+        assert!(!world.has_component(id2, "velocity"));
+    }
+
+    #[test]
+    fn test_set_get_remove_resource() {
+        let mut world = make_test_world();
+        world
+            .set_resource("transform", serde_json::json!({"x": 1.0, "y": 2.0, "z": 3.0}))
+            .unwrap();
+        assert!(world.has_resource("transform"));
+        assert_eq!(world.get_resource("transform").unwrap()["x"], 1.0);
+
+        world.remove_resource("transform").unwrap();
+        assert!(!world.has_resource("transform"));
+        assert!(world.get_resource("transform").is_err());
+    }
+
+    #[test]
+    fn test_resource_validation_rejects_bad_data() {
+        let mut world = make_test_world();
+        let result = world.set_resource("transform", serde_json::json!({"x": 1.0}));
+        assert!(result.is_err());
+
+        let result = world.set_resource("nonexistent", serde_json::json!({}));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_resource_change_tracking() {
+        let mut world = make_test_world();
+        world.set_resource("frozen", Value::Null).unwrap();
+        assert!(world.resource_changed("frozen"));
+
+        world.clear_changes();
+        assert!(!world.resource_changed("frozen"));
+
+        // Resources persist across a `clear_changes` — only the change flag resets.
+        assert!(world.has_resource("frozen"));
+    }
+
+    #[test]
+    fn test_observer_reentrancy_is_depth_limited() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+
+        // Each OnInsert observer bumps the value and writes it back, which would
+        // recurse forever without the depth guard.
+        world.add_observer("transform", TriggerKind::OnInsert, move |world, trigger| {
+            let mut next = trigger.value.clone();
+            let x = next["x"].as_f64().unwrap_or(0.0);
+            next["x"] = serde_json::json!(x + 1.0);
+            if x < 1000.0 {
+                let _ = world.set_component(trigger.entity, "transform", next);
+            }
+        });
+
+        world
+            .set_component(id, "transform", serde_json::json!({"x": 0, "y": 0, "z": 0}))
+            .unwrap();
+
+        let t = world.get_component(id, "transform").unwrap();
+        let x = t["x"].as_f64().unwrap();
+        assert!(x >= MAX_OBSERVER_DEPTH as f64);
+        assert!(x < 1000.0);
+    }
 }
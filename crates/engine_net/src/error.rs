@@ -30,4 +30,35 @@ pub enum NetError {
     /// A required NATS header was missing.
     #[error("missing NATS header: {0}")]
     MissingHeader(String),
+
+    /// Peers disagree on protocol major version and cannot safely communicate.
+    #[error("protocol version mismatch: local {local}, remote {remote}")]
+    VersionMismatch {
+        /// This process's protocol version.
+        local: String,
+        /// The remote peer's protocol version.
+        remote: String,
+    },
+
+    /// A value on the wire could not be safely coerced to the locally
+    /// declared schema type (e.g. a narrowing integer conversion that
+    /// would lose data).
+    #[error("schema coercion error in field '{field}': {message}")]
+    SchemaCoercion {
+        /// The field that failed to coerce.
+        field: String,
+        /// A human-readable description of the failure.
+        message: String,
+    },
+
+    /// Both sides touch a component whose schema digest disagrees.
+    #[error("schema digest mismatch for component '{component}': local {local:016x}, remote {remote:016x}")]
+    SchemaDigestMismatch {
+        /// The component whose digest disagreed.
+        component: String,
+        /// This process's digest for the component.
+        local: u64,
+        /// The remote peer's digest for the component.
+        remote: u64,
+    },
 }
@@ -9,13 +9,22 @@
 //! - [`codec`] — MessagePack serialisation/deserialisation helpers.
 //! - [`connection`] — NATS connection management.
 //! - [`error`] — Network-layer error types.
+//! - [`negotiate`] — Protocol version and capability handshake.
+//! - [`schema_codec`] — Schema-evolution tolerant decoding.
+//! - [`trace`] — W3C Trace Context propagation over NATS headers.
 
 pub mod codec;
 pub mod connection;
 pub mod error;
 pub mod messages;
+pub mod negotiate;
+pub mod schema_codec;
 pub mod subjects;
+pub mod trace;
 
 pub use codec::{decode, encode};
 pub use connection::NatsConnection;
 pub use error::NetError;
+pub use negotiate::{negotiate, Negotiated};
+pub use schema_codec::{decode_record, migrate_record};
+pub use trace::TraceContext;
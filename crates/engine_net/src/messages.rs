@@ -2,9 +2,11 @@
 //!
 //! All message types derive `Serialize` and `Deserialize` for MessagePack
 //! transport. Routing metadata (tick-id, instance-id, msg-type) is carried
-//! in NATS headers — not in the payload.
+//! in NATS headers — not in the payload. Fields carrying raw component
+//! bytes use `serde_bytes::ByteBuf` rather than `Vec<u8>` so they encode as
+//! MessagePack `bin` instead of `array<u8>`.
 
-use engine_component::{ComponentTypeId, Entity, QueryDescriptor};
+use engine_component::{ComponentTypeId, Entity, QueryDescriptor, Tick};
 use serde::{Deserialize, Serialize};
 
 // ── Tick messages ───────────────────────────────────────────────────────────
@@ -47,23 +49,69 @@ pub struct EntityDestroyed {
     pub entity: Entity,
 }
 
-/// A system requests that the coordinator spawn a new entity with the given
-/// component data. Published on
-/// [`subjects::ENTITY_SPAWN_REQUEST`](crate::subjects::ENTITY_SPAWN_REQUEST).
+/// A single deferred structural change recorded by a system's
+/// `engine_system::commands::CommandBuffer` during its tick.
 ///
-/// The coordinator processes these between ticks, allocates entity IDs, writes
-/// the component data into the appropriate archetype, and broadcasts
-/// [`EntityCreated`] events.
+/// Mid-tick structural edits would race with every other system reading or
+/// writing the same world state, so a system records the change it wants
+/// here instead of applying it directly. The coordinator replays a system's
+/// commands, in recorded order, once every system for the tick has acked —
+/// see [`EntityCommandBatch`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EntitySpawnRequest {
-    /// The component types the new entity should have.
-    pub component_types: Vec<ComponentTypeId>,
-    /// Serialised component data, one entry per type (parallel with
-    /// `component_types`).
-    pub component_data: Vec<Vec<u8>>,
-    /// Byte sizes of each component type (parallel with `component_types`).
-    /// Needed so the coordinator can allocate archetype columns.
-    pub component_sizes: Vec<usize>,
+pub enum EntityCommand {
+    /// Spawn a new entity with the given component data.
+    Spawn {
+        /// The component types the new entity should have.
+        component_types: Vec<ComponentTypeId>,
+        /// Serialised component data, one entry per type (parallel with
+        /// `component_types`). Encoded as MessagePack `bin` via `ByteBuf`
+        /// rather than `array<u8>` — roughly halves the wire size of every
+        /// payload.
+        component_data: Vec<serde_bytes::ByteBuf>,
+        /// Byte sizes of each component type (parallel with
+        /// `component_types`). Needed so the coordinator can allocate
+        /// archetype columns.
+        component_sizes: Vec<usize>,
+    },
+    /// Destroy an existing entity.
+    Despawn(Entity),
+    /// Add a component to an existing entity.
+    AddComponent {
+        /// The entity to add the component to.
+        entity: Entity,
+        /// The component type being added.
+        component_type: ComponentTypeId,
+        /// Serialised component data.
+        data: serde_bytes::ByteBuf,
+        /// Byte size of the component, needed so the coordinator can
+        /// allocate the archetype column it migrates the entity into.
+        item_size: usize,
+    },
+    /// Remove a component from an existing entity.
+    RemoveComponent {
+        /// The entity to remove the component from.
+        entity: Entity,
+        /// The component type being removed.
+        component_type: ComponentTypeId,
+    },
+}
+
+/// A system's ordered batch of deferred structural changes for one tick.
+/// Published on
+/// [`subjects::entity_commands`](crate::subjects::entity_commands).
+///
+/// The coordinator replays `commands` in order against the world once every
+/// system for the tick has acked, so `tick_id` lets it apply the batch
+/// deterministically alongside that tick's component changes rather than
+/// whichever tick happens to be current when the message is drained.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityCommandBatch {
+    /// The tick this batch was recorded during.
+    pub tick_id: u64,
+    /// The system that recorded these commands.
+    pub system: String,
+    /// The commands, in the order the system recorded them.
+    pub commands: Vec<EntityCommand>,
 }
 
 // ── Component data ──────────────────────────────────────────────────────────
@@ -78,8 +126,135 @@ pub struct ComponentShard {
     pub component_type: ComponentTypeId,
     /// Entity IDs in this shard (parallel with `data`).
     pub entities: Vec<Entity>,
-    /// MessagePack-encoded component data, one entry per entity.
-    pub data: Vec<Vec<u8>>,
+    /// MessagePack-encoded component data, one entry per entity. Encoded as
+    /// `bin` via `ByteBuf` rather than `array<u8>` — this is the hottest
+    /// path in the per-tick data exchange, so halving its wire size matters.
+    pub data: Vec<serde_bytes::ByteBuf>,
+    /// The tick this shard's data was written at by its sender. The
+    /// coordinator's merge step compares this against the local row's
+    /// `changed_tick` to resolve concurrent edits instead of blindly
+    /// applying arrival order.
+    #[serde(default)]
+    pub origin_tick: Tick,
+    /// The instance that produced this shard, if any. Empty for shards sent
+    /// by the coordinator itself. Used to break `origin_tick` ties
+    /// deterministically when two instances edit the same row in the same
+    /// tick.
+    #[serde(default)]
+    pub instance_id: String,
+    /// The `changed_tick` each row was stamped with, parallel with
+    /// `entities`/`data`. Lets a recipient apply its own
+    /// `Changed<T>`/`since_tick` filtering instead of trusting that every
+    /// row in the shard is actually new to it. Empty for senders that
+    /// predate this field — callers should treat an empty (or
+    /// length-mismatched) `changed_ticks` as "freshness unknown, include
+    /// every row".
+    #[serde(default)]
+    pub changed_ticks: Vec<Tick>,
+    /// The `added_tick` each row was stamped with at insertion, parallel with
+    /// `entities`/`data`. Unlike `changed_ticks`, this never updates on a
+    /// later overwrite, so a recipient can tell "this row is new to the
+    /// world" apart from "this row was merely modified" — the distinction an
+    /// `Added<T>` filter needs that a `Changed<T>` filter can't make. Same
+    /// empty/length-mismatch fallback as `changed_ticks`: treat as "freshness
+    /// unknown, include every row".
+    #[serde(default)]
+    pub added_ticks: Vec<Tick>,
+    /// The `RecordDef::layout_version` the sender encoded `data` with.
+    /// `0` for senders that predate this field, which a recipient should
+    /// treat the same as "matches whatever layout this record currently
+    /// has" — the tolerant [`crate::schema_codec::decode_record`] path
+    /// already handled that drift before `layout_version` existed.
+    #[serde(default)]
+    pub layout_version: u64,
+    /// The system that produced this shard, for causal-provenance tracking.
+    /// Empty for shards sent by the coordinator itself, same convention as
+    /// an empty `instance_id`. Unlike `instance_id`/`origin_tick` (used for
+    /// last-writer-wins conflict resolution), this identifies the *node* in
+    /// a per-tick causal DAG — see [`ShardOrigin`] and
+    /// [`Self::origin`].
+    #[serde(default)]
+    pub producing_system: String,
+}
+
+/// A causal-provenance tag naming the `(system, instance, tick)` that
+/// produced a shard's data — the node identity a read-before-write
+/// dependency edge points at. Built from a [`ComponentShard`]'s
+/// `producing_system`/`instance_id`/`origin_tick` via [`ComponentShard::origin`].
+///
+/// `SystemContext` collects these for every input shard a system reads, and
+/// `SystemRunner` carries the collected set as a dependency list in NATS
+/// headers when it publishes that system's output, so a coordinator can
+/// assemble a per-tick causal DAG (nodes = `(system, tick)`, edges =
+/// read-before-write) for debugging non-determinism and ordering bugs.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ShardOrigin {
+    /// The system that produced the shard this origin was derived from.
+    pub system: String,
+    /// The producing instance's unique identifier.
+    pub instance_id: String,
+    /// The tick the shard was produced at.
+    pub tick_id: u64,
+}
+
+impl ShardOrigin {
+    /// Format as a single NATS header value: `system/instance_id/tick_id`.
+    #[must_use]
+    pub fn to_header_value(&self) -> String {
+        format!("{}/{}/{}", self.system, self.instance_id, self.tick_id)
+    }
+
+    /// Parse a header value produced by [`Self::to_header_value`]. Returns
+    /// `None` if it doesn't match the `system/instance_id/tick_id` shape.
+    #[must_use]
+    pub fn from_header_value(value: &str) -> Option<Self> {
+        let mut parts = value.splitn(3, '/');
+        let system = parts.next()?.to_string();
+        let instance_id = parts.next()?.to_string();
+        let tick_id = parts.next()?.parse().ok()?;
+        Some(Self {
+            system,
+            instance_id,
+            tick_id,
+        })
+    }
+}
+
+impl ComponentShard {
+    /// The origin this shard contributes as a dependency to a downstream
+    /// reader, or `None` for a shard with no `producing_system` recorded
+    /// (e.g. one the coordinator produced itself).
+    #[must_use]
+    pub fn origin(&self) -> Option<ShardOrigin> {
+        if self.producing_system.is_empty() {
+            return None;
+        }
+        Some(ShardOrigin {
+            system: self.producing_system.clone(),
+            instance_id: self.instance_id.clone(),
+            tick_id: self.origin_tick.0.into(),
+        })
+    }
+}
+
+/// Attach `origins` to `headers` as a dependency list, one
+/// [`headers::DEPENDENCY`] header value per origin.
+pub fn inject_dependencies(headers: &mut async_nats::HeaderMap, origins: &[ShardOrigin]) {
+    for origin in origins {
+        headers.append(headers::DEPENDENCY, origin.to_header_value());
+    }
+}
+
+/// Read back the dependency list [`inject_dependencies`] attached to
+/// `headers`. Values that don't parse as a [`ShardOrigin`] are skipped.
+#[must_use]
+pub fn extract_dependencies(headers: &async_nats::HeaderMap) -> Vec<ShardOrigin> {
+    headers
+        .get(headers::DEPENDENCY)
+        .into_iter()
+        .flat_map(|value| value.iter())
+        .filter_map(|v| ShardOrigin::from_header_value(v))
+        .collect()
 }
 
 /// Sentinel published by a system instance on `component.changed.<system>`
@@ -106,6 +281,49 @@ pub struct DataDone {
     pub tick_id: u64,
 }
 
+// ── Protocol handshake ──────────────────────────────────────────────────────
+
+/// Describes one component a participant registered via
+/// [`SystemConfig::with_component_schemas`](https://docs.rs/engine_system),
+/// so peers can detect schema drift before it causes an opaque decode
+/// failure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentSchema {
+    /// The component's name (matches [`ComponentTypeId::from_name`]).
+    pub name: String,
+    /// A digest of the component's wire layout (e.g. a hash of its field
+    /// names and types). Two participants that agree on this digest agree
+    /// on how to decode the component.
+    pub digest: u64,
+}
+
+/// Protocol version tuple, following semantic-versioning compatibility
+/// rules: a major mismatch is a hard rejection, a minor mismatch is a
+/// warning (the newer side may support fields the older side ignores).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProtocolVersion {
+    /// Incremented on breaking wire-format changes.
+    pub major: u32,
+    /// Incremented on backwards-compatible additions.
+    pub minor: u32,
+    /// Incremented on fixes that don't change the wire format.
+    pub patch: u32,
+}
+
+/// Published by a participant when it joins the mesh, so the other side can
+/// run [`negotiate`](crate::negotiate) before trusting any payload it sends.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Handshake {
+    /// The protocol version this participant speaks.
+    pub protocol_version: ProtocolVersion,
+    /// A human-readable server/system version string (e.g. `"physics 0.3.1"`).
+    pub version_string: String,
+    /// Capability flags this participant supports (e.g. `"schema_evolution"`).
+    pub capabilities: Vec<String>,
+    /// Digests of every component schema this participant registered.
+    pub component_schemas: Vec<ComponentSchema>,
+}
+
 // ── System management ───────────────────────────────────────────────────────
 
 /// A system registers itself with the coordinator on startup.
@@ -171,6 +389,62 @@ pub struct QueryResponse {
     pub shards: Vec<ComponentShard>,
 }
 
+// ── Reactive queries ────────────────────────────────────────────────────────
+
+/// Opens a standing subscription on a [`QueryDescriptor`], turning it from a
+/// one-shot [`QueryRequest`] into a continuous stream of [`QueryUpdate`]s.
+///
+/// Published on
+/// [`subjects::QUERY_SUBSCRIBE`](crate::subjects::QUERY_SUBSCRIBE). The
+/// coordinator replies with an initial [`QueryUpdate`] carrying every
+/// currently matching entity in `asserted`, then publishes further updates on
+/// [`subjects::query_update`](crate::subjects::query_update) as the matching
+/// set changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuerySubscribe {
+    /// The query to track.
+    pub query: QueryDescriptor,
+    /// Caller-chosen identifier for this subscription, used to address
+    /// [`QueryUpdate`]s and [`QueryUnsubscribe`].
+    pub subscription_id: String,
+}
+
+/// Ends a standing subscription opened by [`QuerySubscribe`].
+///
+/// Published on
+/// [`subjects::QUERY_UNSUBSCRIBE`](crate::subjects::QUERY_UNSUBSCRIBE).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryUnsubscribe {
+    /// The subscription to end.
+    pub subscription_id: String,
+}
+
+/// An incremental delta for a [`QuerySubscribe`] subscription.
+///
+/// Published on
+/// [`subjects::query_update`](crate::subjects::query_update) whenever the
+/// subscription's matching set changes. An entity appears in `asserted` the
+/// tick it first matches, and again on any later tick where its data changed
+/// while it still matches — it never needs to be re-sent just because it
+/// already matched before. An entity appears in `retracted` the tick it stops
+/// matching, whether because its components changed or because it was
+/// despawned. A subscription opened mid-tick gets its first `QueryUpdate`
+/// with every currently matching entity in `asserted` and an empty
+/// `retracted`, rather than waiting for the next tick boundary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryUpdate {
+    /// The subscription this update belongs to.
+    pub subscription_id: String,
+    /// Entities that newly match (or still match but changed), paired with
+    /// their current data for every component type the query requires.
+    pub asserted: Vec<(Entity, Vec<ComponentShard>)>,
+    /// Entities that no longer match, because they stopped satisfying the
+    /// query or were destroyed.
+    pub retracted: Vec<Entity>,
+    /// The tick this update was computed at.
+    pub tick_id: u64,
+}
+
 // ── NATS header keys ────────────────────────────────────────────────────────
 
 /// Standard NATS header keys used for routing metadata.
@@ -181,6 +455,25 @@ pub mod headers {
     pub const TICK_ID: &str = "tick-id";
     /// The instance ID of the sender.
     pub const INSTANCE_ID: &str = "instance-id";
+    /// W3C Trace Context `traceparent`: `version-trace_id-parent_id-flags`.
+    /// See [`crate::trace`] for the helper that formats/parses this value.
+    pub const TRACEPARENT: &str = "traceparent";
+    /// W3C Trace Context `tracestate`, forwarded verbatim as an opaque,
+    /// comma-separated list — the engine never inspects it.
+    pub const TRACESTATE: &str = "tracestate";
+    /// The `RecordDef::layout_version` a payload was encoded with, for
+    /// message kinds that don't carry a `layout_version` field of their own
+    /// (e.g. ad-hoc invokes). Absent means the same "matches current /
+    /// untracked" meaning as `ComponentShard::layout_version: 0`.
+    pub const SCHEMA_VERSION: &str = "schema-version";
+    /// A [`crate::messages::ShardOrigin`] this published shard causally
+    /// depends on (it was read via `SystemContext::read_components` or
+    /// similar before this shard was written), formatted with
+    /// `ShardOrigin::to_header_value`. Repeated once per dependency via
+    /// `HeaderMap::append` rather than carried in the payload — debugging
+    /// metadata for assembling a causal DAG, not data every recipient needs
+    /// to decode.
+    pub const DEPENDENCY: &str = "dependency";
 }
 
 /// Header value for a [`ChangesDone`] sentinel on `component.changed.<system>`.
@@ -242,16 +535,255 @@ mod tests {
     }
 
     #[test]
-    fn test_entity_spawn_request_roundtrip() {
-        let msg = EntitySpawnRequest {
+    fn test_handshake_roundtrip() {
+        let msg = Handshake {
+            protocol_version: ProtocolVersion {
+                major: 1,
+                minor: 2,
+                patch: 3,
+            },
+            version_string: "coordinator 1.2.3".to_string(),
+            capabilities: vec!["schema_evolution".to_string()],
+            component_schemas: vec![ComponentSchema {
+                name: "Velocity".to_string(),
+                digest: 42,
+            }],
+        };
+        let bytes = rmp_serde::to_vec(&msg).unwrap();
+        let restored: Handshake = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(restored.protocol_version, msg.protocol_version);
+        assert_eq!(restored.component_schemas.len(), 1);
+    }
+
+    #[test]
+    fn test_entity_command_spawn_roundtrip() {
+        let msg = EntityCommand::Spawn {
             component_types: vec![ComponentTypeId(1), ComponentTypeId(2)],
-            component_data: vec![vec![1, 2, 3], vec![4, 5, 6]],
+            component_data: vec![
+                serde_bytes::ByteBuf::from(vec![1, 2, 3]),
+                serde_bytes::ByteBuf::from(vec![4, 5, 6]),
+            ],
             component_sizes: vec![12, 24],
         };
         let bytes = rmp_serde::to_vec(&msg).unwrap();
-        let restored: EntitySpawnRequest = rmp_serde::from_slice(&bytes).unwrap();
-        assert_eq!(restored.component_types.len(), 2);
-        assert_eq!(restored.component_data.len(), 2);
-        assert_eq!(restored.component_sizes, vec![12, 24]);
+        let restored: EntityCommand = rmp_serde::from_slice(&bytes).unwrap();
+        match restored {
+            EntityCommand::Spawn {
+                component_types,
+                component_data,
+                component_sizes,
+            } => {
+                assert_eq!(component_types.len(), 2);
+                assert_eq!(component_data.len(), 2);
+                assert_eq!(component_sizes, vec![12, 24]);
+            }
+            other => panic!("expected Spawn, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_entity_command_batch_roundtrip_preserves_order() {
+        let msg = EntityCommandBatch {
+            tick_id: 7,
+            system: "spawner".to_string(),
+            commands: vec![
+                EntityCommand::Despawn(Entity(1)),
+                EntityCommand::RemoveComponent {
+                    entity: Entity(2),
+                    component_type: ComponentTypeId(9),
+                },
+                EntityCommand::AddComponent {
+                    entity: Entity(2),
+                    component_type: ComponentTypeId(3),
+                    data: serde_bytes::ByteBuf::from(vec![1, 0, 0, 0]),
+                    item_size: 4,
+                },
+            ],
+        };
+        let bytes = rmp_serde::to_vec(&msg).unwrap();
+        let restored: EntityCommandBatch = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(restored.tick_id, 7);
+        assert_eq!(restored.system, "spawner");
+        assert_eq!(restored.commands.len(), 3);
+        assert!(matches!(restored.commands[0], EntityCommand::Despawn(Entity(1))));
+        assert!(matches!(
+            restored.commands[1],
+            EntityCommand::RemoveComponent { .. }
+        ));
+        assert!(matches!(
+            restored.commands[2],
+            EntityCommand::AddComponent { .. }
+        ));
+    }
+
+    #[test]
+    fn test_component_shard_data_encodes_as_compact_bin() {
+        // 4 entities, each carrying a 64-byte component blob.
+        let raw_payload_len = 4 * 64;
+        let msg = ComponentShard {
+            component_type: ComponentTypeId(1),
+            entities: vec![Entity::from_raw(1), Entity::from_raw(2), Entity::from_raw(3), Entity::from_raw(4)],
+            data: (0..4)
+                .map(|_| serde_bytes::ByteBuf::from(vec![0xABu8; 64]))
+                .collect(),
+            origin_tick: Tick::ZERO,
+            instance_id: String::new(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        };
+        let bytes = rmp_serde::to_vec(&msg).unwrap();
+        let restored: ComponentShard = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(restored.data.len(), 4);
+        assert_eq!(restored.data[0].as_slice(), &[0xABu8; 64][..]);
+
+        // A `bin` payload needs only a handful of bytes of header overhead
+        // per entry; the old `array<u8>` encoding would have cost roughly
+        // 2x `raw_payload_len` by itself (one MessagePack integer byte per
+        // u8, plus the array header).
+        assert!(
+            bytes.len() < raw_payload_len * 2,
+            "expected compact bin encoding, got {} bytes for {} bytes of raw data",
+            bytes.len(),
+            raw_payload_len
+        );
+    }
+
+    #[test]
+    fn test_query_subscribe_roundtrip() {
+        let msg = QuerySubscribe {
+            query: QueryDescriptor::new().read(ComponentTypeId(1)),
+            subscription_id: "sub-1".to_string(),
+        };
+        let bytes = rmp_serde::to_vec(&msg).unwrap();
+        let restored: QuerySubscribe = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(restored.subscription_id, "sub-1");
+        assert_eq!(restored.query.reads.len(), 1);
+    }
+
+    #[test]
+    fn test_query_unsubscribe_roundtrip() {
+        let msg = QueryUnsubscribe {
+            subscription_id: "sub-1".to_string(),
+        };
+        let bytes = rmp_serde::to_vec(&msg).unwrap();
+        let restored: QueryUnsubscribe = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(restored.subscription_id, "sub-1");
+    }
+
+    #[test]
+    fn test_query_update_roundtrip() {
+        let msg = QueryUpdate {
+            subscription_id: "sub-1".to_string(),
+            asserted: vec![(
+                Entity::from_raw(1),
+                vec![ComponentShard {
+                    component_type: ComponentTypeId(1),
+                    entities: vec![Entity::from_raw(1)],
+                    data: vec![serde_bytes::ByteBuf::from(vec![1, 2, 3, 4])],
+                    origin_tick: Tick(3),
+                    instance_id: String::new(),
+                    changed_ticks: vec![Tick(3)],
+                    added_ticks: Vec::new(),
+                    layout_version: 0,
+                    producing_system: String::new(),
+                }],
+            )],
+            retracted: vec![Entity::from_raw(2)],
+            tick_id: 3,
+        };
+        let bytes = rmp_serde::to_vec(&msg).unwrap();
+        let restored: QueryUpdate = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(restored.subscription_id, "sub-1");
+        assert_eq!(restored.asserted.len(), 1);
+        assert_eq!(restored.retracted, vec![Entity::from_raw(2)]);
+        assert_eq!(restored.tick_id, 3);
+    }
+
+    #[test]
+    fn test_component_shard_origin_is_none_without_producing_system() {
+        let shard = ComponentShard {
+            component_type: ComponentTypeId(1),
+            entities: vec![Entity::from_raw(1)],
+            data: vec![serde_bytes::ByteBuf::from(vec![0u8; 4])],
+            origin_tick: Tick(5),
+            instance_id: "inst-1".to_string(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        };
+        assert!(shard.origin().is_none());
+    }
+
+    #[test]
+    fn test_component_shard_origin_combines_system_instance_and_tick() {
+        let shard = ComponentShard {
+            component_type: ComponentTypeId(1),
+            entities: vec![Entity::from_raw(1)],
+            data: vec![serde_bytes::ByteBuf::from(vec![0u8; 4])],
+            origin_tick: Tick(5),
+            instance_id: "inst-1".to_string(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: "physics".to_string(),
+        };
+        assert_eq!(
+            shard.origin(),
+            Some(ShardOrigin {
+                system: "physics".to_string(),
+                instance_id: "inst-1".to_string(),
+                tick_id: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn test_shard_origin_header_value_roundtrip() {
+        let origin = ShardOrigin {
+            system: "physics".to_string(),
+            instance_id: "inst-1".to_string(),
+            tick_id: 42,
+        };
+        let value = origin.to_header_value();
+        assert_eq!(ShardOrigin::from_header_value(&value), Some(origin));
+    }
+
+    #[test]
+    fn test_shard_origin_from_header_value_rejects_malformed() {
+        assert!(ShardOrigin::from_header_value("not-an-origin").is_none());
+        assert!(ShardOrigin::from_header_value("system/instance/not-a-number").is_none());
+    }
+
+    #[test]
+    fn test_inject_and_extract_dependencies_roundtrip() {
+        let origins = vec![
+            ShardOrigin {
+                system: "physics".to_string(),
+                instance_id: "inst-1".to_string(),
+                tick_id: 1,
+            },
+            ShardOrigin {
+                system: "ai".to_string(),
+                instance_id: "inst-2".to_string(),
+                tick_id: 1,
+            },
+        ];
+        let mut headers = async_nats::HeaderMap::new();
+        inject_dependencies(&mut headers, &origins);
+
+        let mut extracted = extract_dependencies(&headers);
+        extracted.sort();
+        let mut expected = origins;
+        expected.sort();
+        assert_eq!(extracted, expected);
+    }
+
+    #[test]
+    fn test_extract_dependencies_empty_without_header() {
+        let headers = async_nats::HeaderMap::new();
+        assert!(extract_dependencies(&headers).is_empty());
     }
 }
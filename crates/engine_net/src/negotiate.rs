@@ -0,0 +1,172 @@
+//! Protocol version and capability negotiation.
+//!
+//! Before trusting a peer's MessagePack payloads, both sides should exchange
+//! a [`Handshake`] and run [`negotiate`] so mismatches surface as a clear
+//! [`NetError`] instead of a later opaque decode failure.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::error::NetError;
+use crate::messages::Handshake;
+
+/// The result of successfully negotiating two [`Handshake`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Negotiated {
+    /// Capability flags both sides support.
+    pub capabilities: Vec<String>,
+}
+
+/// Compare a local and remote [`Handshake`], rejecting incompatible peers.
+///
+/// - A protocol major-version mismatch is rejected with
+///   [`NetError::VersionMismatch`].
+/// - A minor-version mismatch is allowed but logged via `tracing::warn!`.
+/// - Capability flags are intersected.
+/// - Any component named in both handshakes' `component_schemas` must carry
+///   the same digest, or negotiation fails with
+///   [`NetError::SchemaDigestMismatch`].
+///
+/// # Errors
+///
+/// Returns [`NetError::VersionMismatch`] on a major version mismatch, or
+/// [`NetError::SchemaDigestMismatch`] if a shared component's schema digest
+/// disagrees.
+pub fn negotiate(local: &Handshake, remote: &Handshake) -> Result<Negotiated, NetError> {
+    if local.protocol_version.major != remote.protocol_version.major {
+        return Err(NetError::VersionMismatch {
+            local: format_version(&local.protocol_version),
+            remote: format_version(&remote.protocol_version),
+        });
+    }
+
+    if local.protocol_version.minor != remote.protocol_version.minor {
+        tracing::warn!(
+            local = %format_version(&local.protocol_version),
+            remote = %format_version(&remote.protocol_version),
+            "protocol minor version mismatch"
+        );
+    }
+
+    let remote_digests: HashMap<&str, u64> = remote
+        .component_schemas
+        .iter()
+        .map(|s| (s.name.as_str(), s.digest))
+        .collect();
+
+    for schema in &local.component_schemas {
+        if let Some(&remote_digest) = remote_digests.get(schema.name.as_str()) {
+            if remote_digest != schema.digest {
+                return Err(NetError::SchemaDigestMismatch {
+                    component: schema.name.clone(),
+                    local: schema.digest,
+                    remote: remote_digest,
+                });
+            }
+        }
+    }
+
+    let local_caps: HashSet<&str> = local.capabilities.iter().map(String::as_str).collect();
+    let capabilities = remote
+        .capabilities
+        .iter()
+        .filter(|c| local_caps.contains(c.as_str()))
+        .cloned()
+        .collect();
+
+    Ok(Negotiated { capabilities })
+}
+
+fn format_version(v: &crate::messages::ProtocolVersion) -> String {
+    format!("{}.{}.{}", v.major, v.minor, v.patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{ComponentSchema, ProtocolVersion};
+
+    fn handshake(major: u32, minor: u32, schemas: Vec<ComponentSchema>) -> Handshake {
+        Handshake {
+            protocol_version: ProtocolVersion {
+                major,
+                minor,
+                patch: 0,
+            },
+            version_string: "test 0.0.0".to_string(),
+            capabilities: vec!["a".to_string(), "b".to_string()],
+            component_schemas: schemas,
+        }
+    }
+
+    #[test]
+    fn test_negotiate_rejects_major_mismatch() {
+        let local = handshake(1, 0, vec![]);
+        let remote = handshake(2, 0, vec![]);
+        assert!(matches!(
+            negotiate(&local, &remote),
+            Err(NetError::VersionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_negotiate_allows_minor_mismatch() {
+        let local = handshake(1, 0, vec![]);
+        let remote = handshake(1, 1, vec![]);
+        assert!(negotiate(&local, &remote).is_ok());
+    }
+
+    #[test]
+    fn test_negotiate_intersects_capabilities() {
+        let mut local = handshake(1, 0, vec![]);
+        local.capabilities = vec!["a".to_string(), "c".to_string()];
+        let mut remote = handshake(1, 0, vec![]);
+        remote.capabilities = vec!["a".to_string(), "b".to_string()];
+        let result = negotiate(&local, &remote).unwrap();
+        assert_eq!(result.capabilities, vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn test_negotiate_rejects_schema_digest_mismatch() {
+        let local = handshake(
+            1,
+            0,
+            vec![ComponentSchema {
+                name: "Velocity".to_string(),
+                digest: 42,
+            }],
+        );
+        let remote = handshake(
+            1,
+            0,
+            vec![ComponentSchema {
+                name: "Velocity".to_string(),
+                digest: 99,
+            }],
+        );
+        assert!(matches!(
+            negotiate(&local, &remote),
+            Err(NetError::SchemaDigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_negotiate_ignores_components_not_shared() {
+        let local = handshake(
+            1,
+            0,
+            vec![ComponentSchema {
+                name: "Velocity".to_string(),
+                digest: 42,
+            }],
+        );
+        let remote = handshake(
+            1,
+            0,
+            vec![ComponentSchema {
+                name: "Health".to_string(),
+                digest: 99,
+            }],
+        );
+        assert!(negotiate(&local, &remote).is_ok());
+    }
+}
@@ -0,0 +1,384 @@
+//! Schema-evolution decode path.
+//!
+//! Components are defined in the `.ecs` IDL (see `engine_schema`) and sent
+//! as named MessagePack, so two processes built at different times will
+//! disagree on fields. [`decode_record`] tolerates that drift instead of
+//! failing a hard decode: unknown wire fields are ignored, missing fields
+//! get type-appropriate defaults, and primitive values are widened where
+//! safe.
+//!
+//! That's enough for additive/subtractive drift, but a field rename looks
+//! identical to "one field removed, an unrelated one added" from the wire
+//! alone. [`migrate_record`] handles that case: given the `layout_version` a
+//! shard was tagged with, it applies the renames declared in a
+//! [`engine_schema::MigrationRegistry`] before falling through to
+//! [`decode_record`] for the rest.
+
+use engine_schema::{MigrationRegistry, Schema, TypeExpr};
+use serde_json::Value;
+
+use crate::error::NetError;
+
+/// Decode MessagePack `bytes` for `record_name` against `schema`, tolerating
+/// schema drift between the writer and this process.
+///
+/// - Unknown fields present on the wire are ignored.
+/// - Fields declared in the schema but absent from the wire are filled with
+///   a type-appropriate default (`0`/`0.0`, an empty list/map, or `None`
+///   for `option<T>`).
+/// - Primitive widenings (`u8`→`u16`→`u32`→`u64`, the signed equivalents,
+///   and integer→float) are performed automatically when the declared
+///   field type differs from the encoded value.
+///
+/// # Errors
+///
+/// Returns [`NetError::Decode`] if `bytes` isn't valid MessagePack. Returns
+/// [`NetError::SchemaCoercion`] if `record_name` is unknown to `schema`, or
+/// if a field's wire value can't be safely coerced to its declared type
+/// (e.g. a narrowing or type-incompatible conversion).
+pub fn decode_record(schema: &Schema, record_name: &str, bytes: &[u8]) -> Result<Value, NetError> {
+    let record = schema
+        .get_record(record_name)
+        .ok_or_else(|| NetError::SchemaCoercion {
+            field: record_name.to_string(),
+            message: "unknown record".to_string(),
+        })?;
+
+    let wire: Value = rmp_serde::from_slice(bytes)?;
+
+    if record.is_tag() {
+        return Ok(wire);
+    }
+
+    let mut obj = match wire {
+        Value::Object(map) => map,
+        other => {
+            return Err(NetError::SchemaCoercion {
+                field: record_name.to_string(),
+                message: format!("expected object, found {other}"),
+            })
+        }
+    };
+
+    for field in &record.fields {
+        let coerced = match obj.remove(&field.name) {
+            Some(value) => coerce_value(value, &field.ty, &field.name)?,
+            None => default_for_type(&field.ty),
+        };
+        obj.insert(field.name.clone(), coerced);
+    }
+
+    Ok(Value::Object(obj))
+}
+
+/// Decode MessagePack `bytes` for `record_name`, written at `from_version`,
+/// bringing it forward to `schema`'s current layout before applying the same
+/// tolerant decode as [`decode_record`].
+///
+/// Added and removed fields are already handled generically by
+/// [`decode_record`] (a missing field gets a default, an unknown one is
+/// passed through unchanged) — this only needs to apply the renames declared in `migrations`
+/// first, since a rename is otherwise indistinguishable from an unrelated
+/// field being removed and another being added.
+///
+/// # Errors
+///
+/// Returns [`NetError::Decode`]/[`NetError::Encode`] if `bytes` can't be
+/// read as or re-written to MessagePack. Returns [`NetError::SchemaCoercion`]
+/// if `record_name` is unknown, the wire payload isn't an object, or
+/// `migrations` has no path from `from_version` to the current layout.
+pub fn migrate_record(
+    schema: &Schema,
+    migrations: &MigrationRegistry,
+    record_name: &str,
+    from_version: u64,
+    bytes: &[u8],
+) -> Result<Value, NetError> {
+    let record = schema
+        .get_record(record_name)
+        .ok_or_else(|| NetError::SchemaCoercion {
+            field: record_name.to_string(),
+            message: "unknown record".to_string(),
+        })?;
+    let to_version = record.layout_version();
+
+    if from_version == to_version || record.is_tag() {
+        return decode_record(schema, record_name, bytes);
+    }
+
+    let path = migrations
+        .path(record_name, from_version, to_version)
+        .ok_or_else(|| NetError::SchemaCoercion {
+            field: record_name.to_string(),
+            message: format!(
+                "no migration path from layout version {from_version} to {to_version}"
+            ),
+        })?;
+
+    let wire: Value = rmp_serde::from_slice(bytes)?;
+    let mut obj = match wire {
+        Value::Object(map) => map,
+        other => {
+            return Err(NetError::SchemaCoercion {
+                field: record_name.to_string(),
+                message: format!("expected object, found {other}"),
+            })
+        }
+    };
+
+    for step in &path {
+        for (old_name, new_name) in &step.renamed_fields {
+            if let Some(value) = obj.remove(old_name) {
+                obj.insert(new_name.clone(), value);
+            }
+        }
+    }
+
+    let rewritten = rmp_serde::to_vec_named(&Value::Object(obj))?;
+    decode_record(schema, record_name, &rewritten)
+}
+
+/// A type-appropriate default for a field absent from the wire.
+fn default_for_type(ty: &TypeExpr) -> Value {
+    match ty {
+        TypeExpr::Primitive(p) => match p.as_str() {
+            "bool" => Value::Bool(false),
+            "f32" | "f64" => Value::from(0.0_f64),
+            "string" => Value::from(String::new()),
+            "bytes" => Value::Array(Vec::new()),
+            _ => Value::from(0_u64),
+        },
+        TypeExpr::Named(_) => Value::Null,
+        TypeExpr::List(_) | TypeExpr::Set(_) => Value::Array(Vec::new()),
+        TypeExpr::Option(_) => Value::Null,
+        TypeExpr::Map(_, _) => Value::Object(serde_json::Map::new()),
+        TypeExpr::Tuple(types) => Value::Array(types.iter().map(default_for_type).collect()),
+    }
+}
+
+/// Coerce a wire value to its declared type, widening primitives where
+/// safe and rejecting anything that would narrow or change kind.
+fn coerce_value(value: Value, ty: &TypeExpr, field: &str) -> Result<Value, NetError> {
+    match ty {
+        TypeExpr::Primitive(p) => coerce_primitive(value, p, field),
+        TypeExpr::Option(inner) => {
+            if value.is_null() {
+                Ok(Value::Null)
+            } else {
+                coerce_value(value, inner, field)
+            }
+        }
+        // Named types, collections, and tuples are passed through as-is;
+        // their contents are not checked against the schema here.
+        TypeExpr::Named(_) | TypeExpr::List(_) | TypeExpr::Set(_) | TypeExpr::Map(_, _) => {
+            Ok(value)
+        }
+        TypeExpr::Tuple(_) => Ok(value),
+    }
+}
+
+fn coerce_primitive(value: Value, primitive: &str, field: &str) -> Result<Value, NetError> {
+    let err = |message: String| NetError::SchemaCoercion {
+        field: field.to_string(),
+        message,
+    };
+
+    match primitive {
+        "bool" => {
+            if value.is_boolean() {
+                Ok(value)
+            } else {
+                Err(err("expected bool".to_string()))
+            }
+        }
+        "string" => {
+            if value.is_string() {
+                Ok(value)
+            } else {
+                Err(err("expected string".to_string()))
+            }
+        }
+        "bytes" => {
+            if value.is_string() || value.is_array() {
+                Ok(value)
+            } else {
+                Err(err("expected string or array for bytes".to_string()))
+            }
+        }
+        "f32" | "f64" => {
+            if let Some(f) = value.as_f64() {
+                Ok(Value::from(f))
+            } else {
+                Err(err("expected a number".to_string()))
+            }
+        }
+        "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" => {
+            coerce_integer(value, primitive, field)
+        }
+        _ => Ok(value),
+    }
+}
+
+fn coerce_integer(value: Value, primitive: &str, field: &str) -> Result<Value, NetError> {
+    let err = |message: String| NetError::SchemaCoercion {
+        field: field.to_string(),
+        message,
+    };
+
+    let n = value
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| value.as_u64().map(i128::from))
+        .ok_or_else(|| err("expected an integer".to_string()))?;
+
+    let (min, max): (i128, i128) = match primitive {
+        "u8" => (u8::MIN as i128, u8::MAX as i128),
+        "u16" => (u16::MIN as i128, u16::MAX as i128),
+        "u32" => (u32::MIN as i128, u32::MAX as i128),
+        "u64" => (u64::MIN as i128, u64::MAX as i128),
+        "i8" => (i8::MIN as i128, i8::MAX as i128),
+        "i16" => (i16::MIN as i128, i16::MAX as i128),
+        "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "i64" => (i64::MIN as i128, i64::MAX as i128),
+        _ => unreachable!("coerce_integer called with non-integer primitive"),
+    };
+
+    if n < min || n > max {
+        return Err(err(format!(
+            "value {n} does not fit in declared type '{primitive}' (disallowed narrowing)"
+        )));
+    }
+
+    if n > i128::from(i64::MAX) {
+        return Ok(Value::from(n as u64));
+    }
+    Ok(Value::from(n as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_schema() -> Schema {
+        let mut schema = Schema::new();
+        schema
+            .load_source(
+                r#"
+            package test:game@0.1.0
+
+            record velocity {
+                x: f32,
+                y: f32,
+                label: string,
+            }
+        "#,
+            )
+            .unwrap();
+        schema
+    }
+
+    #[test]
+    fn test_decode_record_fills_missing_field_with_default() {
+        let schema = test_schema();
+        let partial = serde_json::json!({ "x": 1.0, "y": 2.0 });
+        let bytes = rmp_serde::to_vec_named(&partial).unwrap();
+
+        let decoded = decode_record(&schema, "velocity", &bytes).unwrap();
+        assert_eq!(decoded["label"], Value::from(String::new()));
+    }
+
+    #[test]
+    fn test_decode_record_ignores_unknown_field() {
+        let schema = test_schema();
+        let extra = serde_json::json!({ "x": 1.0, "y": 2.0, "label": "a", "bogus": 42 });
+        let bytes = rmp_serde::to_vec_named(&extra).unwrap();
+
+        let decoded = decode_record(&schema, "velocity", &bytes).unwrap();
+        assert_eq!(decoded["bogus"], Value::from(42));
+    }
+
+    #[test]
+    fn test_decode_record_widens_integer_to_float() {
+        let schema = test_schema();
+        let wire = serde_json::json!({ "x": 1, "y": 2, "label": "a" });
+        let bytes = rmp_serde::to_vec_named(&wire).unwrap();
+
+        let decoded = decode_record(&schema, "velocity", &bytes).unwrap();
+        assert!((decoded["x"].as_f64().unwrap() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_decode_record_rejects_narrowing_overflow() {
+        let mut schema = Schema::new();
+        schema
+            .load_source(
+                r#"
+            package test:game@0.1.0
+
+            record health {
+                current: u8,
+            }
+        "#,
+            )
+            .unwrap();
+
+        let wire = serde_json::json!({ "current": 1000 });
+        let bytes = rmp_serde::to_vec_named(&wire).unwrap();
+
+        let err = decode_record(&schema, "health", &bytes).unwrap_err();
+        assert!(matches!(err, NetError::SchemaCoercion { .. }));
+    }
+
+    #[test]
+    fn test_migrate_record_applies_declared_rename() {
+        // `speed` was renamed to `label` between layout versions — a plain
+        // `decode_record` against the new schema would pass `speed` through
+        // as an unknown field and fill `label` with its empty-string default
+        // instead of carrying the value across.
+        let schema = test_schema();
+        let old_version = 1;
+        let mut migrations = engine_schema::MigrationRegistry::new();
+        migrations.register(
+            "velocity",
+            engine_schema::RecordMigration {
+                from_version: old_version,
+                to_version: schema.get_record("velocity").unwrap().layout_version(),
+                renamed_fields: vec![("speed".to_string(), "label".to_string())],
+            },
+        );
+
+        let wire = serde_json::json!({ "x": 1.0, "y": 2.0, "speed": "fast" });
+        let bytes = rmp_serde::to_vec_named(&wire).unwrap();
+
+        let migrated =
+            migrate_record(&schema, &migrations, "velocity", old_version, &bytes).unwrap();
+        assert_eq!(migrated["label"], Value::from("fast"));
+        assert!(migrated.get("speed").is_none());
+    }
+
+    #[test]
+    fn test_migrate_record_fails_without_a_path() {
+        let schema = test_schema();
+        let migrations = engine_schema::MigrationRegistry::new();
+
+        let wire = serde_json::json!({ "x": 1.0, "y": 2.0, "label": "a" });
+        let bytes = rmp_serde::to_vec_named(&wire).unwrap();
+
+        let err = migrate_record(&schema, &migrations, "velocity", 1, &bytes).unwrap_err();
+        assert!(matches!(err, NetError::SchemaCoercion { .. }));
+    }
+
+    #[test]
+    fn test_migrate_record_is_a_no_op_at_the_current_version() {
+        let schema = test_schema();
+        let migrations = engine_schema::MigrationRegistry::new();
+        let current = schema.get_record("velocity").unwrap().layout_version();
+
+        let wire = serde_json::json!({ "x": 1.0, "y": 2.0, "label": "a" });
+        let bytes = rmp_serde::to_vec_named(&wire).unwrap();
+
+        let migrated =
+            migrate_record(&schema, &migrations, "velocity", current, &bytes).unwrap();
+        assert_eq!(migrated["label"], Value::from("a"));
+    }
+}
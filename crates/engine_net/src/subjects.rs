@@ -3,6 +3,8 @@
 //! All engine subjects are prefixed with `engine.` to namespace within a
 //! shared NATS cluster. See `ARCHITECTURE.md` for the full hierarchy.
 
+use engine_component::ArchetypeId;
+
 /// Root prefix for all engine NATS subjects.
 pub const PREFIX: &str = "engine";
 
@@ -38,6 +40,18 @@ pub const QUERY_REQUEST: &str = "engine.query.request";
 /// Ad-hoc query response. Coordinator → Requester.
 pub const QUERY_RESPONSE: &str = "engine.query.response";
 
+/// Opens a standing reactive subscription on a query. Any → Coordinator.
+pub const QUERY_SUBSCRIBE: &str = "engine.query.subscribe";
+
+/// Ends a standing reactive subscription. Any → Coordinator.
+pub const QUERY_UNSUBSCRIBE: &str = "engine.query.unsubscribe";
+
+// ── Debug inspector ──────────────────────────────────────────────────────────
+
+/// A debug-adapter-style inspector tool attaches here to pause, single-step,
+/// and inspect a running coordinator. Inspector → Coordinator (request/reply).
+pub const DEBUG_INSPECT: &str = "engine.debug.inspect";
+
 // ── Dynamic subject builders ────────────────────────────────────────────────
 
 /// Build the subject for sending component data to a specific system.
@@ -56,6 +70,39 @@ pub fn component_changed(system_name: &str) -> String {
     format!("engine.component.changed.{system_name}")
 }
 
+/// Build the subject for sending component data to a specific system,
+/// scoped to entities in one archetype — lets a system that only cares
+/// about one archetype subscribe narrowly instead of filtering a firehose
+/// addressed to its name alone.
+///
+/// `engine.component.set.<system_name>.<archetype_hex>`
+#[must_use]
+pub fn component_set_for_archetype(system_name: &str, archetype: ArchetypeId) -> String {
+    format!("engine.component.set.{system_name}.{:016x}", archetype.0)
+}
+
+/// Build the subject for receiving changed component data from a system,
+/// scoped to entities in one archetype. See
+/// [`component_set_for_archetype`] for the rationale.
+///
+/// `engine.component.changed.<system_name>.<archetype_hex>`
+#[must_use]
+pub fn component_changed_for_archetype(system_name: &str, archetype: ArchetypeId) -> String {
+    format!("engine.component.changed.{system_name}.{:016x}", archetype.0)
+}
+
+/// Build the wildcard subject a system subscribes to in order to receive
+/// changed component data for *every* archetype its query matches, without
+/// enumerating each archetype it currently knows about. Combined with
+/// [`ArchetypeId`]'s deterministic hashing, this turns NATS subject
+/// filtering into a coarse query index rather than a firehose.
+///
+/// `engine.component.changed.<system_name>.*`
+#[must_use]
+pub fn component_changed_for_archetype_wildcard(system_name: &str) -> String {
+    format!("engine.component.changed.{system_name}.*")
+}
+
 /// Build the subject for scheduling a specific system.
 ///
 /// `engine.system.schedule.<system_name>`
@@ -72,6 +119,53 @@ pub fn queue_group(system_name: &str) -> String {
     format!("q.{system_name}")
 }
 
+// ── Ad-hoc invocation (single instance, outside the stage loop) ────────────
+
+/// Build the subject for invoking a specific system instance directly,
+/// bypassing the normal per-tick schedule.
+///
+/// `engine.system.invoke.<instance_id>`
+#[must_use]
+pub fn system_invoke(instance_id: &str) -> String {
+    format!("engine.system.invoke.{instance_id}")
+}
+
+/// Build the subject for sending component data to a specific system
+/// instance for an ad-hoc invocation.
+///
+/// `engine.component.invoke.<instance_id>`
+#[must_use]
+pub fn component_invoke(instance_id: &str) -> String {
+    format!("engine.component.invoke.{instance_id}")
+}
+
+/// Build the subject for receiving changed component data from a specific
+/// system instance's ad-hoc invocation.
+///
+/// `engine.component.invoke.changed.<instance_id>`
+#[must_use]
+pub fn component_invoke_changed(instance_id: &str) -> String {
+    format!("engine.component.invoke.changed.{instance_id}")
+}
+
+/// Build the subject for delivering `QueryUpdate` deltas to a specific
+/// reactive query subscription.
+///
+/// `engine.query.update.<subscription_id>`
+#[must_use]
+pub fn query_update(subscription_id: &str) -> String {
+    format!("engine.query.update.{subscription_id}")
+}
+
+/// Build the subject for a system's deferred entity-command batch
+/// (`EntityCommandBatch` — spawn/despawn/add-component/remove-component).
+///
+/// `engine.entity.commands.<system_name>`
+#[must_use]
+pub fn entity_commands(system_name: &str) -> String {
+    format!("engine.entity.commands.{system_name}")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,6 +183,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_component_set_for_archetype_subject() {
+        assert_eq!(
+            component_set_for_archetype("physics", ArchetypeId(0x12)),
+            "engine.component.set.physics.0000000000000012"
+        );
+    }
+
+    #[test]
+    fn test_component_changed_for_archetype_subject() {
+        assert_eq!(
+            component_changed_for_archetype("physics", ArchetypeId(0x12)),
+            "engine.component.changed.physics.0000000000000012"
+        );
+    }
+
+    #[test]
+    fn test_component_changed_for_archetype_wildcard_subject() {
+        assert_eq!(
+            component_changed_for_archetype_wildcard("physics"),
+            "engine.component.changed.physics.*"
+        );
+    }
+
     #[test]
     fn test_system_schedule_subject() {
         assert_eq!(system_schedule("physics"), "engine.system.schedule.physics");
@@ -98,4 +216,43 @@ mod tests {
     fn test_queue_group_name() {
         assert_eq!(queue_group("physics"), "q.physics");
     }
+
+    #[test]
+    fn test_debug_inspect_subject() {
+        assert_eq!(DEBUG_INSPECT, "engine.debug.inspect");
+    }
+
+    #[test]
+    fn test_system_invoke_subject() {
+        assert_eq!(system_invoke("inst-1"), "engine.system.invoke.inst-1");
+    }
+
+    #[test]
+    fn test_component_invoke_subject() {
+        assert_eq!(
+            component_invoke("inst-1"),
+            "engine.component.invoke.inst-1"
+        );
+    }
+
+    #[test]
+    fn test_component_invoke_changed_subject() {
+        assert_eq!(
+            component_invoke_changed("inst-1"),
+            "engine.component.invoke.changed.inst-1"
+        );
+    }
+
+    #[test]
+    fn test_query_update_subject() {
+        assert_eq!(query_update("sub-1"), "engine.query.update.sub-1");
+    }
+
+    #[test]
+    fn test_entity_commands_subject() {
+        assert_eq!(
+            entity_commands("physics"),
+            "engine.entity.commands.physics"
+        );
+    }
 }
@@ -0,0 +1,214 @@
+//! W3C Trace Context propagation over NATS headers.
+//!
+//! Routing metadata (`msg-type`, `tick-id`, `instance-id`) tells a peer what
+//! a message is, but not how it relates to the rest of one tick's fan-out —
+//! coordinator publishes a schedule, systems execute and reply, coordinator
+//! merges. [`TraceContext`] carries a W3C `traceparent`
+//! (<https://www.w3.org/TR/trace-context/>) through that chain so a tracing
+//! backend can reconstruct it, without putting tracing data in the
+//! MessagePack payload.
+
+use uuid::Uuid;
+
+/// A W3C trace context: a `trace-id` shared by every span in one tick's
+/// causal chain, and a `span-id` unique to this hop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    /// 16-byte trace identifier, shared across every hop of one tick.
+    pub trace_id: [u8; 16],
+    /// 8-byte identifier for this hop's span.
+    pub span_id: [u8; 8],
+    /// Whether this trace is sampled (flags bit 0).
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// Start a new root trace with a freshly generated trace-id and span-id
+    /// — e.g. the coordinator beginning a tick.
+    #[must_use]
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: *Uuid::new_v4().as_bytes(),
+            span_id: random_span_id(),
+            sampled: true,
+        }
+    }
+
+    /// Derive a child span for the same trace: same `trace_id`, fresh
+    /// `span_id`. A receiver calls this after extracting the parent context
+    /// to get the context for the span it creates before its own reply.
+    #[must_use]
+    pub fn child(self) -> Self {
+        Self {
+            trace_id: self.trace_id,
+            span_id: random_span_id(),
+            sampled: self.sampled,
+        }
+    }
+
+    /// Format as a W3C `traceparent` header value:
+    /// `00-<32 hex trace-id>-<16 hex span-id>-<2 hex flags>`.
+    #[must_use]
+    pub fn to_traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-{:02x}",
+            hex(&self.trace_id),
+            hex(&self.span_id),
+            u8::from(self.sampled)
+        )
+    }
+
+    /// Parse a W3C `traceparent` header value. Returns `None` if it doesn't
+    /// match the `version-trace_id-span_id-flags` shape.
+    #[must_use]
+    pub fn from_traceparent(value: &str) -> Option<Self> {
+        let mut parts = value.split('-');
+        let version = parts.next()?;
+        let trace_id_hex = parts.next()?;
+        let span_id_hex = parts.next()?;
+        let flags_hex = parts.next()?;
+        if version.len() != 2 || parts.next().is_some() {
+            return None;
+        }
+        let trace_id = parse_hex_bytes::<16>(trace_id_hex)?;
+        let span_id = parse_hex_bytes::<8>(span_id_hex)?;
+        let flags = u8::from_str_radix(flags_hex, 16).ok()?;
+        Some(Self {
+            trace_id,
+            span_id,
+            sampled: flags & 0x01 != 0,
+        })
+    }
+}
+
+impl TraceContext {
+    /// Build the [`opentelemetry::trace::SpanContext`] this trace context
+    /// represents, for handing to
+    /// `tracing_opentelemetry::OpenTelemetrySpanExt::set_parent` so a local
+    /// span links up to the remote hop that sent it — e.g. a system's
+    /// per-tick span becoming a child of the coordinator's tick span instead
+    /// of starting a disconnected trace.
+    #[must_use]
+    pub fn to_otel_span_context(self) -> opentelemetry::trace::SpanContext {
+        opentelemetry::trace::SpanContext::new(
+            opentelemetry::trace::TraceId::from_bytes(self.trace_id),
+            opentelemetry::trace::SpanId::from_bytes(self.span_id),
+            if self.sampled {
+                opentelemetry::trace::TraceFlags::SAMPLED
+            } else {
+                opentelemetry::trace::TraceFlags::default()
+            },
+            true,
+            opentelemetry::trace::TraceState::default(),
+        )
+    }
+}
+
+fn random_span_id() -> [u8; 8] {
+    let bytes = Uuid::new_v4().into_bytes();
+    let mut span_id = [0u8; 8];
+    span_id.copy_from_slice(&bytes[..8]);
+    span_id
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn parse_hex_bytes<const N: usize>(s: &str) -> Option<[u8; N]> {
+    if s.len() != N * 2 {
+        return None;
+    }
+    let mut out = [0u8; N];
+    for (i, slot) in out.iter_mut().enumerate() {
+        *slot = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(out)
+}
+
+/// Write `ctx`'s `traceparent` into `headers`, forwarding `tracestate`
+/// verbatim alongside it if present.
+pub fn inject(headers: &mut async_nats::HeaderMap, ctx: &TraceContext, tracestate: Option<&str>) {
+    headers.insert(crate::messages::headers::TRACEPARENT, ctx.to_traceparent());
+    if let Some(state) = tracestate {
+        headers.insert(crate::messages::headers::TRACESTATE, state);
+    }
+}
+
+/// Extract a [`TraceContext`] and verbatim `tracestate` from `headers`, if a
+/// valid `traceparent` is present.
+#[must_use]
+pub fn extract(headers: &async_nats::HeaderMap) -> Option<(TraceContext, Option<String>)> {
+    let traceparent = headers.get(crate::messages::headers::TRACEPARENT)?;
+    let ctx = TraceContext::from_traceparent(traceparent.as_str())?;
+    let tracestate = headers
+        .get(crate::messages::headers::TRACESTATE)
+        .map(|v| v.as_str().to_string());
+    Some((ctx, tracestate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traceparent_roundtrip() {
+        let ctx = TraceContext::new_root();
+        let formatted = ctx.to_traceparent();
+        let parsed = TraceContext::from_traceparent(&formatted).unwrap();
+        assert_eq!(parsed, ctx);
+    }
+
+    #[test]
+    fn test_traceparent_format_matches_w3c_shape() {
+        let ctx = TraceContext::new_root();
+        let formatted = ctx.to_traceparent();
+        let parts: Vec<&str> = formatted.split('-').collect();
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[0], "00");
+        assert_eq!(parts[1].len(), 32);
+        assert_eq!(parts[2].len(), 16);
+        assert_eq!(parts[3].len(), 2);
+    }
+
+    #[test]
+    fn test_child_keeps_trace_id_but_changes_span_id() {
+        let root = TraceContext::new_root();
+        let child = root.child();
+        assert_eq!(child.trace_id, root.trace_id);
+        assert_ne!(child.span_id, root.span_id);
+    }
+
+    #[test]
+    fn test_from_traceparent_rejects_malformed_value() {
+        assert!(TraceContext::from_traceparent("not-a-traceparent").is_none());
+        assert!(TraceContext::from_traceparent("00-short-short-01").is_none());
+    }
+
+    #[test]
+    fn test_inject_and_extract_roundtrip() {
+        let ctx = TraceContext::new_root();
+        let mut headers = async_nats::HeaderMap::new();
+        inject(&mut headers, &ctx, Some("vendor=value"));
+
+        let (extracted, tracestate) = extract(&headers).unwrap();
+        assert_eq!(extracted, ctx);
+        assert_eq!(tracestate.as_deref(), Some("vendor=value"));
+    }
+
+    #[test]
+    fn test_extract_returns_none_without_traceparent() {
+        let headers = async_nats::HeaderMap::new();
+        assert!(extract(&headers).is_none());
+    }
+
+    #[test]
+    fn test_to_otel_span_context_preserves_trace_and_span_id() {
+        let ctx = TraceContext::new_root();
+        let otel_ctx = ctx.to_otel_span_context();
+        assert_eq!(otel_ctx.trace_id().to_bytes(), ctx.trace_id);
+        assert_eq!(otel_ctx.span_id().to_bytes(), ctx.span_id);
+        assert!(otel_ctx.is_sampled());
+        assert!(otel_ctx.is_remote());
+    }
+}
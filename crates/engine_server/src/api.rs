@@ -6,6 +6,7 @@
 ///     {prefix}.spawn           — create entity, optional initial components
 ///     {prefix}.despawn         — destroy entity
 ///     {prefix}.set             — set component on entity
+///     {prefix}.cas             — set component only if its current value matches an expected one
 ///     {prefix}.get             — get component from entity
 ///     {prefix}.remove          — remove component from entity
 ///     {prefix}.query           — query entities by component filters
@@ -13,22 +14,62 @@
 ///     {prefix}.entities        — list all entity IDs
 ///     {prefix}.schema          — get schema info
 ///     {prefix}.schema.record   — get record schema by name
+///     {prefix}.batch           — run several ops as one request, see below
 ///
-///   Publish (broadcast):
+///   Any request subject may carry an extra `<codec>` segment right after
+///   the prefix (e.g. `{prefix}.json.set`) to pick the wire format its
+///   payload is decoded with and its reply encoded in — see
+///   [`Api::negotiate_codec`]. Without one, requests and replies are JSON,
+///   matching this API's historical (pre-`Codec`) behavior.
+///
+///   Publish (broadcast) — every event is an [`EventEnvelope`], carrying an
+///   `event_id`, the triggering request's `correlation_id` if it supplied
+///   one, and an `EventKind` so e.g. a real removal (`kind: "removed"`,
+///   no `value`) can't be confused with a component set to `null`
+///   (`kind: "changed"`, `value: null`):
 ///     {prefix}.events.spawned      — entity spawned
 ///     {prefix}.events.despawned    — entity despawned
-///     {prefix}.events.changed.{component} — component changed
+///     {prefix}.events.changed.{component} — component changed or removed
+///
+///   Gossip (anti-entropy replication across `Api` instances sharing this
+///   subject space — see the [`gossip`] module):
+///     {prefix}.gossip.delta        — a single versioned component write
+///     {prefix}.gossip.digest       — a node's periodic version-map summary
+///     {prefix}.gossip.pull         — request re-publication of stale keys
+mod gossip;
+
 use async_nats::Client;
-use engine_ecs::{EntityId, World};
+use engine_component::CodecId;
+use engine_ecs::{ChangeMode, EntityId, World, WorldError};
+use gossip::{GossipDelta, GossipDigest, GossipPull, VersionMap, VersionStamp};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
+/// How often a node publishes a [`GossipDigest`] on `{prefix}.gossip.digest`.
+const GOSSIP_DIGEST_INTERVAL: Duration = Duration::from_secs(5);
+
 pub struct Api {
     world: World,
     client: Client,
     prefix: String,
+    /// This node's identity for gossip last-writer-wins tie-breaking —
+    /// stable for the process's lifetime, distinct per `Api` instance.
+    node_id: String,
+    /// Highest known version per `(entity, component)`, local or gossiped.
+    versions: VersionMap,
+    /// Counter handed out as each published event's `event_id`.
+    next_event_id: u64,
+    /// While `Some`, an atomic batch is in progress: [`Self::gossip_component_write`]
+    /// stages writes here instead of bumping `versions`/publishing them
+    /// immediately. Flushed once the whole batch commits, or dropped
+    /// untouched if [`Self::rollback`] runs — so a sub-op undone by a later
+    /// failure in the same batch never reaches peers (and `versions` never
+    /// advances past the reverted value) in the first place, rather than
+    /// needing a corrective delta after the fact.
+    pending_gossip: Option<Vec<(EntityId, String, Value)>>,
 }
 
 // ---------------------------------------------------------------------------
@@ -39,6 +80,10 @@ pub struct Api {
 struct SpawnRequest {
     #[serde(default)]
     components: Option<HashMap<String, Value>>,
+    /// Echoed back as the resulting event's `correlation_id`, so the caller
+    /// can match its request to the broadcast it produced.
+    #[serde(default)]
+    correlation_id: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -49,6 +94,8 @@ struct SpawnResponse {
 #[derive(Deserialize)]
 struct EntityRequest {
     entity_id: EntityId,
+    #[serde(default)]
+    correlation_id: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -56,6 +103,8 @@ struct SetComponentRequest {
     entity_id: EntityId,
     component: String,
     value: Value,
+    #[serde(default)]
+    correlation_id: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -68,6 +117,8 @@ struct GetComponentRequest {
 struct RemoveComponentRequest {
     entity_id: EntityId,
     component: String,
+    #[serde(default)]
+    correlation_id: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -78,6 +129,19 @@ struct QueryRequest {
     without: Vec<String>,
     #[serde(default)]
     changed: Vec<String>,
+    /// Which transition `changed` should match: "added", "changed", "removed",
+    /// or "any" (the default — either added or changed this tick).
+    #[serde(default)]
+    changed_mode: Option<String>,
+}
+
+fn parse_change_mode(mode: Option<&str>) -> ChangeMode {
+    match mode {
+        Some("added") => ChangeMode::Added,
+        Some("changed") => ChangeMode::Changed,
+        Some("removed") => ChangeMode::Removed,
+        _ => ChangeMode::Any,
+    }
 }
 
 #[derive(Deserialize)]
@@ -85,6 +149,79 @@ struct SchemaRecordRequest {
     name: String,
 }
 
+/// A compare-and-swap write: `value` is only applied if the component's
+/// current value equals `expected`. An absent component reads as `null`, so
+/// `expected: None` (i.e. JSON `null`) matches "the component doesn't exist
+/// yet".
+#[derive(Deserialize)]
+struct CasRequest {
+    entity_id: EntityId,
+    component: String,
+    expected: Option<Value>,
+    value: Value,
+    #[serde(default)]
+    correlation_id: Option<u64>,
+}
+
+/// One sub-operation inside a `{prefix}.batch` request. `op` and `payload`
+/// mirror the top-level `{"op": "...", ...}` shape a single request would
+/// use, just nested.
+#[derive(Deserialize)]
+struct BatchOp {
+    op: String,
+    #[serde(default)]
+    payload: Value,
+}
+
+#[derive(Deserialize)]
+struct BatchRequest {
+    ops: Vec<BatchOp>,
+    /// If true, any op failing discards every mutation the batch already
+    /// made instead of leaving earlier ops committed.
+    #[serde(default)]
+    atomic: bool,
+}
+
+/// Just enough of a `BatchOp`'s payload to plan how to undo it, without
+/// needing a dedicated request type per operation.
+#[derive(Deserialize, Default)]
+struct BatchOpFields {
+    entity_id: Option<EntityId>,
+    component: Option<String>,
+}
+
+/// How to reverse a single committed batch op, so an atomic batch can roll
+/// everything back if a later op fails. Captured *before* the op runs, from
+/// `World`'s state at that point.
+#[derive(Debug)]
+enum Undo {
+    /// Undo a `spawn`: despawn the entity it created.
+    Despawn(EntityId),
+    /// Undo a `set` that added a component which did not exist before.
+    RemoveComponent(EntityId, String),
+    /// Undo a `set`/`remove` that overwrote a component's prior value.
+    RestoreComponent(EntityId, String, Value),
+}
+
+impl Undo {
+    /// Applies the inverse mutation to `world`, best-effort — if the entity
+    /// or component it targets is already gone for some other reason, there
+    /// is nothing left to undo.
+    fn apply(self, world: &mut World) {
+        match self {
+            Undo::Despawn(id) => {
+                let _ = world.despawn(id);
+            }
+            Undo::RemoveComponent(id, component) => {
+                let _ = world.remove_component(id, &component);
+            }
+            Undo::RestoreComponent(id, component, value) => {
+                let _ = world.set_component(id, &component, value);
+            }
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct ApiResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -108,8 +245,22 @@ impl ApiResponse {
         }
     }
 
-    fn to_bytes(&self) -> Vec<u8> {
-        serde_json::to_vec(self).unwrap_or_else(|_| b"{}".to_vec())
+    /// An error response that also carries a value — e.g. a CAS mismatch's
+    /// actual current value, so the caller can retry without a second
+    /// round-trip to fetch it.
+    fn error_with(msg: impl Into<String>, value: Value) -> Self {
+        Self {
+            ok: Some(value),
+            error: Some(msg.into()),
+        }
+    }
+
+    /// Encodes this response in `codec`'s wire format — whichever format
+    /// the requester negotiated via [`Api::negotiate_codec`] for its
+    /// request, so the reply round-trips in the same format it was asked
+    /// for.
+    fn to_bytes(&self, codec: CodecId) -> Vec<u8> {
+        codec.encode(self).unwrap_or_else(|_| b"{}".to_vec())
     }
 }
 
@@ -117,16 +268,35 @@ impl ApiResponse {
 // Event payloads (broadcast)
 // ---------------------------------------------------------------------------
 
-#[derive(Serialize)]
-struct EntityEvent {
-    entity_id: EntityId,
+/// Which lifecycle transition an [`EventEnvelope`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum EventKind {
+    Spawned,
+    Despawned,
+    Changed,
+    Removed,
 }
 
+/// Every `{prefix}.events.*` broadcast is wrapped in this envelope, so a
+/// client can tell events apart by `kind` alone (in particular, a real
+/// removal from a component overwritten with `null`, which used to publish
+/// identically) and match a `changed`/`removed`/`despawned` event back to
+/// the request that caused it via `correlation_id`.
 #[derive(Serialize)]
-struct ComponentChangedEvent {
+struct EventEnvelope {
+    /// Monotonically increasing per [`Api`] instance — not a global
+    /// sequence number across a multi-node deployment.
+    event_id: u64,
+    /// Echoes the `correlation_id` the triggering request supplied, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<u64>,
+    kind: EventKind,
     entity_id: EntityId,
-    component: String,
-    value: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    component: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<Value>,
 }
 
 // ---------------------------------------------------------------------------
@@ -134,14 +304,56 @@ struct ComponentChangedEvent {
 // ---------------------------------------------------------------------------
 
 impl Api {
-    pub fn new(world: World, client: Client, prefix: String) -> Self {
+    /// `node_id` should be stable for this process and distinct from every
+    /// other node sharing `prefix`'s subject space — it's the gossip
+    /// last-writer-wins tiebreaker, see [`gossip::VersionMap`].
+    pub fn new(world: World, client: Client, prefix: String, node_id: String) -> Self {
         Self {
             world,
             client,
             prefix,
+            node_id,
+            versions: VersionMap::new(),
+            next_event_id: 0,
+            pending_gossip: None,
         }
     }
 
+    /// Builds and publishes an [`EventEnvelope`] for a lifecycle transition.
+    /// `changed`/`removed` share the `events.changed.{component}` subject —
+    /// the envelope's `kind` is what tells them apart.
+    async fn publish_event(
+        &mut self,
+        kind: EventKind,
+        entity_id: EntityId,
+        component: Option<String>,
+        value: Option<Value>,
+        correlation_id: Option<u64>,
+    ) {
+        self.next_event_id += 1;
+        let envelope = EventEnvelope {
+            event_id: self.next_event_id,
+            correlation_id,
+            kind,
+            entity_id,
+            component: component.clone(),
+            value,
+        };
+        let subject = match kind {
+            EventKind::Spawned => format!("{}.events.spawned", self.prefix),
+            EventKind::Despawned => format!("{}.events.despawned", self.prefix),
+            EventKind::Changed | EventKind::Removed => format!(
+                "{}.events.changed.{}",
+                self.prefix,
+                component.as_deref().unwrap_or("")
+            ),
+        };
+        let _ = self
+            .client
+            .publish(subject, serde_json::to_vec(&envelope).unwrap().into())
+            .await;
+    }
+
     pub async fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         use futures_util::StreamExt;
 
@@ -152,30 +364,44 @@ impl Api {
 
         info!("engine-server ready — listening for requests");
 
-        while let Some(msg) = sub.next().await {
+        loop {
+            // Race the next message against the gossip digest tick, so a
+            // quiet subject doesn't delay anti-entropy indefinitely.
+            let msg = match tokio::time::timeout(GOSSIP_DIGEST_INTERVAL, sub.next()).await {
+                Ok(Some(msg)) => msg,
+                Ok(None) => break,
+                Err(_) => {
+                    self.publish_gossip_digest().await;
+                    continue;
+                }
+            };
+
             let subject = msg.subject.as_str().to_string();
             let reply = msg.reply.clone();
 
-            // Strip prefix to get the operation
-            let op = subject
-                .strip_prefix(&self.prefix)
-                .and_then(|s| s.strip_prefix('.'))
-                .unwrap_or("");
+            let (codec, op) = Self::negotiate_codec(&subject, &self.prefix);
 
-            debug!(op = %op, "received request");
+            if let Some(gossip_op) = op.strip_prefix("gossip.") {
+                self.handle_gossip(gossip_op, &msg.payload).await;
+                continue;
+            }
+
+            debug!(op = %op, codec = %codec.name(), "received request");
 
             let response = match op {
-                "spawn" => self.handle_spawn(&msg.payload).await,
-                "despawn" => self.handle_despawn(&msg.payload).await,
-                "set" => self.handle_set(&msg.payload).await,
-                "get" => self.handle_get(&msg.payload),
-                "remove" => self.handle_remove(&msg.payload).await,
-                "query" => self.handle_query(&msg.payload),
-                "entity" => self.handle_entity(&msg.payload),
+                "spawn" => self.handle_spawn(&msg.payload, codec).await,
+                "despawn" => self.handle_despawn(&msg.payload, codec).await,
+                "set" => self.handle_set(&msg.payload, codec).await,
+                "cas" => self.handle_cas(&msg.payload, codec).await,
+                "get" => self.handle_get(&msg.payload, codec),
+                "remove" => self.handle_remove(&msg.payload, codec).await,
+                "query" => self.handle_query(&msg.payload, codec),
+                "entity" => self.handle_entity(&msg.payload, codec),
                 "entities" => self.handle_entities(),
+                "batch" => self.handle_batch(&msg.payload, codec).await,
                 "schema" => self.handle_schema(),
                 s if s.starts_with("schema.record") => {
-                    self.handle_schema_record(&msg.payload)
+                    self.handle_schema_record(&msg.payload, codec)
                 }
                 _ => {
                     warn!(op = %op, "unknown operation");
@@ -187,7 +413,7 @@ impl Api {
             if let Some(reply_to) = reply {
                 if let Err(e) = self
                     .client
-                    .publish(reply_to, response.to_bytes().into())
+                    .publish(reply_to, response.to_bytes(codec).into())
                     .await
                 {
                     error!(%e, "failed to publish reply");
@@ -198,47 +424,69 @@ impl Api {
         Ok(())
     }
 
+    /// This API's wire format before [`Codec`](engine_component::Codec)
+    /// negotiation existed, and what a subject with no codec segment still
+    /// gets today — every existing client, which has never sent one, keeps
+    /// working unchanged.
+    const DEFAULT_CODEC: CodecId = CodecId::Json;
+
+    /// Splits a subject into the operation name and the wire codec the
+    /// requester negotiated for it, via an optional `{prefix}.<codec>.<op>`
+    /// segment (e.g. `ecs.msgpack.set`). A bare `{prefix}.<op>`, with no
+    /// recognised codec segment, negotiates [`Api::DEFAULT_CODEC`].
+    fn negotiate_codec<'a>(subject: &'a str, prefix: &str) -> (CodecId, &'a str) {
+        let rest = subject
+            .strip_prefix(prefix)
+            .and_then(|s| s.strip_prefix('.'))
+            .unwrap_or("");
+        match rest.split_once('.').and_then(|(maybe_codec, remainder)| {
+            CodecId::from_name(maybe_codec).map(|codec| (codec, remainder))
+        }) {
+            Some((codec, op)) => (codec, op),
+            None => (Self::DEFAULT_CODEC, rest),
+        }
+    }
+
     // -- Handlers --
 
-    async fn handle_spawn(&mut self, payload: &[u8]) -> ApiResponse {
-        let req: SpawnRequest = match serde_json::from_slice(payload) {
+    async fn handle_spawn(&mut self, payload: &[u8], codec: CodecId) -> ApiResponse {
+        let req: SpawnRequest = match codec.decode(payload) {
             Ok(r) => r,
             Err(e) => return ApiResponse::error(format!("invalid request: {e}")),
         };
 
+        let components = req.components.clone();
         match self.world.spawn(req.components) {
             Ok(id) => {
-                // Broadcast spawn event
-                let event = EntityEvent { entity_id: id };
-                let subject = format!("{}.events.spawned", self.prefix);
-                let _ = self
-                    .client
-                    .publish(subject, serde_json::to_vec(&event).unwrap().into())
+                self.publish_event(EventKind::Spawned, id, None, None, req.correlation_id)
                     .await;
 
+                for (component, value) in components.into_iter().flatten() {
+                    self.gossip_component_write(id, &component, value).await;
+                }
+
                 ApiResponse::ok(serde_json::to_value(SpawnResponse { entity_id: id }).unwrap())
             }
             Err(e) => ApiResponse::error(e.to_string()),
         }
     }
 
-    async fn handle_despawn(&mut self, payload: &[u8]) -> ApiResponse {
-        let req: EntityRequest = match serde_json::from_slice(payload) {
+    async fn handle_despawn(&mut self, payload: &[u8], codec: CodecId) -> ApiResponse {
+        let req: EntityRequest = match codec.decode(payload) {
             Ok(r) => r,
             Err(e) => return ApiResponse::error(format!("invalid request: {e}")),
         };
 
         match self.world.despawn(req.entity_id) {
             Ok(()) => {
-                // Broadcast despawn event
-                let event = EntityEvent {
-                    entity_id: req.entity_id,
-                };
-                let subject = format!("{}.events.despawned", self.prefix);
-                let _ = self
-                    .client
-                    .publish(subject, serde_json::to_vec(&event).unwrap().into())
-                    .await;
+                self.publish_event(
+                    EventKind::Despawned,
+                    req.entity_id,
+                    None,
+                    None,
+                    req.correlation_id,
+                )
+                .await;
 
                 ApiResponse::ok(Value::Null)
             }
@@ -246,8 +494,8 @@ impl Api {
         }
     }
 
-    async fn handle_set(&mut self, payload: &[u8]) -> ApiResponse {
-        let req: SetComponentRequest = match serde_json::from_slice(payload) {
+    async fn handle_set(&mut self, payload: &[u8], codec: CodecId) -> ApiResponse {
+        let req: SetComponentRequest = match codec.decode(payload) {
             Ok(r) => r,
             Err(e) => return ApiResponse::error(format!("invalid request: {e}")),
         };
@@ -257,16 +505,16 @@ impl Api {
             .set_component(req.entity_id, &req.component, req.value.clone())
         {
             Ok(()) => {
-                // Broadcast change event
-                let event = ComponentChangedEvent {
-                    entity_id: req.entity_id,
-                    component: req.component.clone(),
-                    value: req.value,
-                };
-                let subject = format!("{}.events.changed.{}", self.prefix, req.component);
-                let _ = self
-                    .client
-                    .publish(subject, serde_json::to_vec(&event).unwrap().into())
+                self.publish_event(
+                    EventKind::Changed,
+                    req.entity_id,
+                    Some(req.component.clone()),
+                    Some(req.value.clone()),
+                    req.correlation_id,
+                )
+                .await;
+
+                self.gossip_component_write(req.entity_id, &req.component, req.value)
                     .await;
 
                 ApiResponse::ok(Value::Null)
@@ -275,8 +523,57 @@ impl Api {
         }
     }
 
-    fn handle_get(&self, payload: &[u8]) -> ApiResponse {
-        let req: GetComponentRequest = match serde_json::from_slice(payload) {
+    /// Compare-and-swap: writes `value` only if the component's current
+    /// value equals `expected`, treating an absent component as `null`.
+    async fn handle_cas(&mut self, payload: &[u8], codec: CodecId) -> ApiResponse {
+        let req: CasRequest = match codec.decode(payload) {
+            Ok(r) => r,
+            Err(e) => return ApiResponse::error(format!("invalid request: {e}")),
+        };
+
+        if let Err(response) = Self::apply_cas(&mut self.world, &req) {
+            return response;
+        }
+
+        self.publish_event(
+            EventKind::Changed,
+            req.entity_id,
+            Some(req.component.clone()),
+            Some(req.value.clone()),
+            req.correlation_id,
+        )
+        .await;
+
+        self.gossip_component_write(req.entity_id, &req.component, req.value)
+            .await;
+
+        ApiResponse::ok(Value::Null)
+    }
+
+    /// The match/mismatch/write decision at the heart of [`Self::handle_cas`],
+    /// pulled out as a `World`-only helper — mirrors [`Self::plan_undo`]/
+    /// [`Self::rollback`] — so it's unit-testable without a live `Api`
+    /// (which needs a real `async_nats::Client` to construct). On a mismatch
+    /// the returned `Err` is already the full `error_with` response, current
+    /// value and all, ready to hand straight back to the caller.
+    fn apply_cas(world: &mut World, req: &CasRequest) -> Result<(), ApiResponse> {
+        let current = match world.get_component(req.entity_id, &req.component) {
+            Ok(value) => value.clone(),
+            Err(WorldError::ComponentNotFound(_, _)) => Value::Null,
+            Err(e) => return Err(ApiResponse::error(e.to_string())),
+        };
+
+        if current != req.expected.clone().unwrap_or(Value::Null) {
+            return Err(ApiResponse::error_with("cas mismatch", current));
+        }
+
+        world
+            .set_component(req.entity_id, &req.component, req.value.clone())
+            .map_err(|e| ApiResponse::error(e.to_string()))
+    }
+
+    fn handle_get(&self, payload: &[u8], codec: CodecId) -> ApiResponse {
+        let req: GetComponentRequest = match codec.decode(payload) {
             Ok(r) => r,
             Err(e) => return ApiResponse::error(format!("invalid request: {e}")),
         };
@@ -287,24 +584,27 @@ impl Api {
         }
     }
 
-    async fn handle_remove(&mut self, payload: &[u8]) -> ApiResponse {
-        let req: RemoveComponentRequest = match serde_json::from_slice(payload) {
+    async fn handle_remove(&mut self, payload: &[u8], codec: CodecId) -> ApiResponse {
+        let req: RemoveComponentRequest = match codec.decode(payload) {
             Ok(r) => r,
             Err(e) => return ApiResponse::error(format!("invalid request: {e}")),
         };
 
         match self.world.remove_component(req.entity_id, &req.component) {
             Ok(()) => {
-                // Broadcast removal as a change with null value
-                let event = ComponentChangedEvent {
-                    entity_id: req.entity_id,
-                    component: req.component.clone(),
-                    value: Value::Null,
-                };
-                let subject = format!("{}.events.changed.{}", self.prefix, req.component);
-                let _ = self
-                    .client
-                    .publish(subject, serde_json::to_vec(&event).unwrap().into())
+                self.publish_event(
+                    EventKind::Removed,
+                    req.entity_id,
+                    Some(req.component.clone()),
+                    None,
+                    req.correlation_id,
+                )
+                .await;
+
+                // A removal gossips as a null-valued delta — the same
+                // convention `handle_gossip_delta` uses to recognise and
+                // apply a remove on the receiving end.
+                self.gossip_component_write(req.entity_id, &req.component, Value::Null)
                     .await;
 
                 ApiResponse::ok(Value::Null)
@@ -313,18 +613,19 @@ impl Api {
         }
     }
 
-    fn handle_query(&self, payload: &[u8]) -> ApiResponse {
-        let req: QueryRequest = match serde_json::from_slice(payload) {
+    fn handle_query(&self, payload: &[u8], codec: CodecId) -> ApiResponse {
+        let req: QueryRequest = match codec.decode(payload) {
             Ok(r) => r,
             Err(e) => return ApiResponse::error(format!("invalid request: {e}")),
         };
 
-        let entities = self.world.query(&req.with, &req.without, &req.changed);
+        let mode = parse_change_mode(req.changed_mode.as_deref());
+        let entities = self.world.query(&req.with, &req.without, &req.changed, mode);
         ApiResponse::ok(serde_json::json!({ "entities": entities }))
     }
 
-    fn handle_entity(&self, payload: &[u8]) -> ApiResponse {
-        let req: EntityRequest = match serde_json::from_slice(payload) {
+    fn handle_entity(&self, payload: &[u8], codec: CodecId) -> ApiResponse {
+        let req: EntityRequest = match codec.decode(payload) {
             Ok(r) => r,
             Err(e) => return ApiResponse::error(format!("invalid request: {e}")),
         };
@@ -350,8 +651,8 @@ impl Api {
         ApiResponse::ok(self.world.schema().to_json())
     }
 
-    fn handle_schema_record(&self, payload: &[u8]) -> ApiResponse {
-        let req: SchemaRecordRequest = match serde_json::from_slice(payload) {
+    fn handle_schema_record(&self, payload: &[u8], codec: CodecId) -> ApiResponse {
+        let req: SchemaRecordRequest = match codec.decode(payload) {
             Ok(r) => r,
             Err(e) => return ApiResponse::error(format!("invalid request: {e}")),
         };
@@ -370,4 +671,624 @@ impl Api {
             None => ApiResponse::error(format!("unknown record: {}", req.name)),
         }
     }
+
+    /// Runs every op in `{"ops": [...]}` in order and replies with a JSON
+    /// array of `ApiResponse` values, one per op. With `"atomic": true`, the
+    /// first failing op aborts the batch and rolls back every mutation the
+    /// earlier ops in it made, via [`Undo`]; without it, ops after a
+    /// failure still run, same as submitting them one at a time.
+    ///
+    /// Note: each sub-op still broadcasts its event as it commits, even
+    /// inside an atomic batch — a rollback undoes `World` state but cannot
+    /// retract an already-published event. Gossip is not subject to this
+    /// caveat: while the batch is atomic, [`Self::gossip_component_write`]
+    /// stages its writes in [`Self::pending_gossip`] instead of publishing
+    /// them, so a rollback can simply drop what was staged rather than
+    /// having let peers (and this node's own `versions`) already observe a
+    /// write that no longer holds.
+    async fn handle_batch(&mut self, payload: &[u8], codec: CodecId) -> ApiResponse {
+        let req: BatchRequest = match codec.decode(payload) {
+            Ok(r) => r,
+            Err(e) => return ApiResponse::error(format!("invalid request: {e}")),
+        };
+
+        let mut results = Vec::with_capacity(req.ops.len());
+        let mut undo_log: Vec<Undo> = Vec::new();
+
+        if req.atomic {
+            self.pending_gossip = Some(Vec::new());
+        }
+
+        for batch_op in &req.ops {
+            if req.atomic && batch_op.op == "despawn" {
+                // A despawn can't be undone — the entity's full prior state
+                // would need to be re-inserted under its original id, which
+                // `World` has no way to do. Reject it up front rather than
+                // pretend atomicity we can't deliver.
+                results.push(ApiResponse::error(
+                    "despawn cannot be used inside an atomic batch",
+                ));
+                Self::rollback(&mut self.world, undo_log);
+                self.pending_gossip = None;
+                return ApiResponse::ok(Value::Array(
+                    results.iter().map(|r| serde_json::to_value(r).unwrap()).collect(),
+                ));
+            }
+
+            let undo = req
+                .atomic
+                .then(|| Self::plan_undo(&self.world, batch_op))
+                .flatten();
+            let response = self.apply_batch_op(batch_op, codec).await;
+            let failed = response.error.is_some();
+
+            if req.atomic {
+                if failed {
+                    results.push(response);
+                    Self::rollback(&mut self.world, undo_log);
+                    self.pending_gossip = None;
+                    return ApiResponse::ok(Value::Array(
+                        results.iter().map(|r| serde_json::to_value(r).unwrap()).collect(),
+                    ));
+                }
+                // `spawn`'s undo (despawn the id it just created) can only
+                // be known once it has run, unlike every other op's, which
+                // is planned from `World`'s state beforehand.
+                if batch_op.op == "spawn" {
+                    if let Some(id) = response.ok.as_ref().and_then(|v| v.get("entity_id")) {
+                        if let Some(id) = id.as_u64() {
+                            undo_log.push(Undo::Despawn(id as EntityId));
+                        }
+                    }
+                } else if let Some(undo) = undo {
+                    undo_log.push(undo);
+                }
+            }
+            results.push(response);
+        }
+
+        if req.atomic {
+            self.flush_pending_gossip().await;
+        }
+
+        ApiResponse::ok(Value::Array(
+            results.into_iter().map(|r| serde_json::to_value(&r).unwrap()).collect(),
+        ))
+    }
+
+    /// Publishes every write staged by [`Self::gossip_component_write`]
+    /// during an atomic batch that has now committed in full.
+    async fn flush_pending_gossip(&mut self) {
+        let Some(pending) = self.pending_gossip.take() else {
+            return;
+        };
+        for (entity_id, component, value) in pending {
+            self.gossip_component_write(entity_id, &component, value)
+                .await;
+        }
+    }
+
+    /// Dispatches one `BatchOp` through the same per-op handler `run` would
+    /// use for a top-level request, re-encoding its already-parsed payload
+    /// back to bytes in `codec`'s format so the existing handlers can decode
+    /// it unchanged.
+    async fn apply_batch_op(&mut self, batch_op: &BatchOp, codec: CodecId) -> ApiResponse {
+        let payload = codec.encode(&batch_op.payload).unwrap_or_default();
+        match batch_op.op.as_str() {
+            "spawn" => self.handle_spawn(&payload, codec).await,
+            "despawn" => self.handle_despawn(&payload, codec).await,
+            "set" => self.handle_set(&payload, codec).await,
+            "cas" => self.handle_cas(&payload, codec).await,
+            "get" => self.handle_get(&payload, codec),
+            "remove" => self.handle_remove(&payload, codec).await,
+            other => ApiResponse::error(format!("unknown batch operation: {other}")),
+        }
+    }
+
+    /// Captures, from `World`'s state *before* `batch_op` runs, how to
+    /// reverse it if a later op in the same atomic batch fails. Returns
+    /// `None` for ops with nothing to undo (e.g. `get`) or whose undo is
+    /// planned from their own result instead (`spawn`, see
+    /// [`Self::handle_batch`]'s call site).
+    fn plan_undo(world: &World, batch_op: &BatchOp) -> Option<Undo> {
+        let fields: BatchOpFields =
+            serde_json::from_value(batch_op.payload.clone()).unwrap_or_default();
+        let (id, component) = (fields.entity_id?, fields.component?);
+
+        match batch_op.op.as_str() {
+            "set" | "remove" | "cas" => {
+                if world.has_component(id, &component) {
+                    let value = world.get_component(id, &component).ok()?.clone();
+                    Some(Undo::RestoreComponent(id, component, value))
+                } else {
+                    Some(Undo::RemoveComponent(id, component))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Unwinds `undo_log` in reverse (most-recent op first) against `world`.
+    fn rollback(world: &mut World, undo_log: Vec<Undo>) {
+        for undo in undo_log.into_iter().rev() {
+            undo.apply(world);
+        }
+    }
+
+    // -- Gossip --
+
+    /// Records a local component write (or, for `value: Value::Null`, a
+    /// removal) and publishes it as a [`GossipDelta`] for peers to apply.
+    ///
+    /// While an atomic batch is in progress (see
+    /// [`Self::pending_gossip`]), the write is staged instead — it's only
+    /// safe to advance `versions` and tell peers about it once the whole
+    /// batch is known to have committed.
+    async fn gossip_component_write(&mut self, entity_id: EntityId, component: &str, value: Value) {
+        if self.pending_gossip.is_some() {
+            Self::stage_gossip_write(&mut self.pending_gossip, entity_id, component, value);
+            return;
+        }
+
+        let stamp = self.versions.bump(entity_id, component, &self.node_id);
+        let delta = GossipDelta {
+            entity_id,
+            component: component.to_string(),
+            value,
+            version: stamp.version,
+            node_id: stamp.node_id,
+        };
+        let subject = format!("{}.gossip.delta", self.prefix);
+        let _ = self
+            .client
+            .publish(subject, serde_json::to_vec(&delta).unwrap().into())
+            .await;
+    }
+
+    /// Stages `(entity_id, component, value)` into `pending` and returns
+    /// `true` if an atomic batch is in progress, leaving `pending` untouched
+    /// and returning `false` otherwise — pulled out of
+    /// [`Self::gossip_component_write`] so the staging/deferral guarantee
+    /// (and, together with [`Self::handle_batch`]'s sibling `pending_gossip
+    /// = None` on rollback, the discard-on-rollback guarantee) is
+    /// unit-testable without a live `Api`, which needs a real
+    /// `async_nats::Client` to construct.
+    fn stage_gossip_write(
+        pending: &mut Option<Vec<(EntityId, String, Value)>>,
+        entity_id: EntityId,
+        component: &str,
+        value: Value,
+    ) -> bool {
+        match pending.as_mut() {
+            Some(p) => {
+                p.push((entity_id, component.to_string(), value));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Dispatches one incoming `{prefix}.gossip.*` message. Unlike the
+    /// request/reply handlers, these are plain broadcasts — there is no
+    /// reply to send back.
+    async fn handle_gossip(&mut self, op: &str, payload: &[u8]) {
+        match op {
+            "delta" => self.handle_gossip_delta(payload).await,
+            "digest" => self.handle_gossip_digest(payload).await,
+            "pull" => self.handle_gossip_pull(payload).await,
+            other => warn!(op = %other, "unknown gossip operation"),
+        }
+    }
+
+    /// Applies an incoming [`GossipDelta`] if its version is newer than
+    /// what this node already has for that key; a stale delta (an older
+    /// write, or a replay of this node's own) is silently dropped.
+    ///
+    /// A delta for an entity this node has never seen can't be applied —
+    /// `World::set_component` requires the entity to already exist, and
+    /// there's no way to (re-)create one under a caller-chosen id. Such a
+    /// delta is dropped with a warning; full entity replication, not just
+    /// component-value replication, is out of scope here.
+    async fn handle_gossip_delta(&mut self, payload: &[u8]) {
+        let delta: GossipDelta = match serde_json::from_slice(payload) {
+            Ok(d) => d,
+            Err(e) => {
+                warn!(%e, "invalid gossip delta");
+                return;
+            }
+        };
+        if delta.node_id == self.node_id {
+            return;
+        }
+
+        let stamp = VersionStamp {
+            version: delta.version,
+            node_id: delta.node_id,
+        };
+        if !self.versions.observe(delta.entity_id, &delta.component, stamp) {
+            return;
+        }
+
+        let result = if delta.value.is_null() {
+            self.world.remove_component(delta.entity_id, &delta.component)
+        } else {
+            self.world
+                .set_component(delta.entity_id, &delta.component, delta.value)
+        };
+        if let Err(e) = result {
+            warn!(
+                entity_id = delta.entity_id,
+                component = %delta.component,
+                %e,
+                "failed to apply gossip delta"
+            );
+        }
+    }
+
+    /// Compares an incoming [`GossipDigest`] against this node's version map
+    /// and, if the peer is ahead on any key, asks for it via
+    /// [`GossipPull`].
+    async fn handle_gossip_digest(&mut self, payload: &[u8]) {
+        let digest: GossipDigest = match serde_json::from_slice(payload) {
+            Ok(d) => d,
+            Err(e) => {
+                warn!(%e, "invalid gossip digest");
+                return;
+            }
+        };
+        if digest.node_id == self.node_id {
+            return;
+        }
+
+        let wants = self.versions.stale_against(&digest.entries);
+        if wants.is_empty() {
+            return;
+        }
+
+        let pull = GossipPull {
+            node_id: self.node_id.clone(),
+            wants,
+        };
+        let subject = format!("{}.gossip.pull", self.prefix);
+        let _ = self
+            .client
+            .publish(subject, serde_json::to_vec(&pull).unwrap().into())
+            .await;
+    }
+
+    /// Re-publishes a [`GossipDelta`] for any requested key this node holds
+    /// both a version of and a current component value for — every node
+    /// that has one does the same, so the requester converges on the newest
+    /// regardless of which peer answers first. A key whose component is
+    /// absent here (e.g. removed) is not re-published.
+    async fn handle_gossip_pull(&mut self, payload: &[u8]) {
+        let pull: GossipPull = match serde_json::from_slice(payload) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!(%e, "invalid gossip pull");
+                return;
+            }
+        };
+        if pull.node_id == self.node_id {
+            return;
+        }
+
+        for (entity_id, component) in pull.wants {
+            let Some(stamp) = self.versions.stamp_of(entity_id, &component).cloned() else {
+                continue;
+            };
+            let Ok(value) = self.world.get_component(entity_id, &component) else {
+                continue;
+            };
+            let delta = GossipDelta {
+                entity_id,
+                component,
+                value: value.clone(),
+                version: stamp.version,
+                node_id: stamp.node_id,
+            };
+            let subject = format!("{}.gossip.delta", self.prefix);
+            let _ = self
+                .client
+                .publish(subject, serde_json::to_vec(&delta).unwrap().into())
+                .await;
+        }
+    }
+
+    /// Publishes this node's [`GossipDigest`], called on every
+    /// [`GOSSIP_DIGEST_INTERVAL`] tick of [`Self::run`]'s loop.
+    async fn publish_gossip_digest(&mut self) {
+        let digest = GossipDigest {
+            node_id: self.node_id.clone(),
+            entries: self.versions.digest(),
+        };
+        let subject = format!("{}.gossip.digest", self.prefix);
+        let _ = self
+            .client
+            .publish(subject, serde_json::to_vec(&digest).unwrap().into())
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_codec_defaults_without_codec_segment() {
+        let (codec, op) = Api::negotiate_codec("ecs.set", "ecs");
+        assert_eq!(codec, CodecId::Json);
+        assert_eq!(op, "set");
+    }
+
+    #[test]
+    fn test_negotiate_codec_reads_explicit_segment() {
+        let (codec, op) = Api::negotiate_codec("ecs.json.set", "ecs");
+        assert_eq!(codec, CodecId::Json);
+        assert_eq!(op, "set");
+    }
+
+    #[test]
+    fn test_negotiate_codec_leaves_dotted_ops_intact_when_not_a_codec() {
+        let (codec, op) = Api::negotiate_codec("ecs.schema.record", "ecs");
+        assert_eq!(codec, CodecId::Json);
+        assert_eq!(op, "schema.record");
+    }
+
+    #[test]
+    fn test_negotiate_codec_reads_explicit_segment_for_dotted_op() {
+        let (codec, op) = Api::negotiate_codec("ecs.msgpack.schema.record", "ecs");
+        assert_eq!(codec, CodecId::MsgPack);
+        assert_eq!(op, "schema.record");
+    }
+
+    fn make_test_world() -> World {
+        let mut schema = engine_schema::Schema::new();
+        schema
+            .load_source(
+                r#"
+            package test:game@0.1.0
+
+            record health {
+                current: f32,
+                max: f32,
+            }
+        "#,
+            )
+            .unwrap();
+        World::new(schema)
+    }
+
+    fn batch_op(op: &str, payload: Value) -> BatchOp {
+        BatchOp {
+            op: op.to_string(),
+            payload,
+        }
+    }
+
+    #[test]
+    fn test_plan_undo_for_set_on_new_component_is_remove() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+
+        let op = batch_op(
+            "set",
+            serde_json::json!({"entity_id": id, "component": "health", "value": {"current": 1.0, "max": 1.0}}),
+        );
+        match Api::plan_undo(&world, &op) {
+            Some(Undo::RemoveComponent(undo_id, component)) => {
+                assert_eq!(undo_id, id);
+                assert_eq!(component, "health");
+            }
+            other => panic!("expected RemoveComponent undo, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_plan_undo_for_set_on_existing_component_restores_old_value() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        world
+            .set_component(id, "health", serde_json::json!({"current": 5.0, "max": 10.0}))
+            .unwrap();
+
+        let op = batch_op(
+            "set",
+            serde_json::json!({"entity_id": id, "component": "health", "value": {"current": 9.0, "max": 10.0}}),
+        );
+        match Api::plan_undo(&world, &op) {
+            Some(Undo::RestoreComponent(undo_id, component, value)) => {
+                assert_eq!(undo_id, id);
+                assert_eq!(component, "health");
+                assert_eq!(value["current"], 5.0);
+            }
+            other => panic!("expected RestoreComponent undo, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_plan_undo_for_get_is_none() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        let op = batch_op("get", serde_json::json!({"entity_id": id, "component": "health"}));
+        assert!(Api::plan_undo(&world, &op).is_none());
+    }
+
+    #[test]
+    fn test_rollback_restores_prior_component_value() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        world
+            .set_component(id, "health", serde_json::json!({"current": 5.0, "max": 10.0}))
+            .unwrap();
+
+        let undo_log = vec![Undo::RestoreComponent(
+            id,
+            "health".to_string(),
+            serde_json::json!({"current": 5.0, "max": 10.0}),
+        )];
+        world
+            .set_component(id, "health", serde_json::json!({"current": 0.0, "max": 10.0}))
+            .unwrap();
+        Api::rollback(&mut world, undo_log);
+
+        assert_eq!(world.get_component(id, "health").unwrap()["current"], 5.0);
+    }
+
+    #[test]
+    fn test_rollback_despawns_a_spawned_entity() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        assert!(world.exists(id));
+
+        Api::rollback(&mut world, vec![Undo::Despawn(id)]);
+        assert!(!world.exists(id));
+    }
+
+    fn cas_request(id: EntityId, expected: Option<Value>, value: Value) -> CasRequest {
+        CasRequest {
+            entity_id: id,
+            component: "health".to_string(),
+            expected,
+            value,
+            correlation_id: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_cas_writes_when_current_matches_expected() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        world
+            .set_component(id, "health", serde_json::json!({"current": 5.0, "max": 10.0}))
+            .unwrap();
+
+        let req = cas_request(
+            id,
+            Some(serde_json::json!({"current": 5.0, "max": 10.0})),
+            serde_json::json!({"current": 9.0, "max": 10.0}),
+        );
+        assert!(Api::apply_cas(&mut world, &req).is_ok());
+        assert_eq!(world.get_component(id, "health").unwrap()["current"], 9.0);
+    }
+
+    #[test]
+    fn test_apply_cas_mismatch_leaves_component_untouched_and_returns_current_value() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        world
+            .set_component(id, "health", serde_json::json!({"current": 5.0, "max": 10.0}))
+            .unwrap();
+
+        let req = cas_request(
+            id,
+            Some(serde_json::json!({"current": 1.0, "max": 10.0})),
+            serde_json::json!({"current": 9.0, "max": 10.0}),
+        );
+        let response = Api::apply_cas(&mut world, &req).unwrap_err();
+        assert_eq!(response.error.as_deref(), Some("cas mismatch"));
+        assert_eq!(response.ok.unwrap()["current"], 5.0);
+        assert_eq!(world.get_component(id, "health").unwrap()["current"], 5.0);
+    }
+
+    #[test]
+    fn test_apply_cas_against_absent_component_matches_expected_null() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        assert!(!world.has_component(id, "health"));
+
+        let req = cas_request(id, None, serde_json::json!({"current": 1.0, "max": 10.0}));
+        assert!(Api::apply_cas(&mut world, &req).is_ok());
+        assert_eq!(world.get_component(id, "health").unwrap()["current"], 1.0);
+    }
+
+    #[test]
+    fn test_apply_cas_mismatch_against_absent_component_reports_null_as_current() {
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        assert!(!world.has_component(id, "health"));
+
+        let req = cas_request(
+            id,
+            Some(serde_json::json!({"current": 1.0, "max": 10.0})),
+            serde_json::json!({"current": 9.0, "max": 10.0}),
+        );
+        let response = Api::apply_cas(&mut world, &req).unwrap_err();
+        assert_eq!(response.error.as_deref(), Some("cas mismatch"));
+        assert_eq!(response.ok, Some(Value::Null));
+        assert!(!world.has_component(id, "health"));
+    }
+
+    #[test]
+    fn test_apply_cas_inside_atomic_batch_rolls_back_on_mismatch() {
+        // Mirrors how `handle_batch` drives a "cas" op: plan its undo from
+        // `World`'s state before running it, then — since `apply_cas` only
+        // writes on a match — a mismatch leaves nothing for `rollback` to
+        // actually undo, same as the earlier ops in the batch before it.
+        let mut world = make_test_world();
+        let id = world.spawn(None).unwrap();
+        world
+            .set_component(id, "health", serde_json::json!({"current": 5.0, "max": 10.0}))
+            .unwrap();
+
+        let op = batch_op(
+            "cas",
+            serde_json::json!({
+                "entity_id": id,
+                "component": "health",
+                "expected": {"current": 1.0, "max": 10.0},
+                "value": {"current": 9.0, "max": 10.0},
+            }),
+        );
+        let undo = Api::plan_undo(&world, &op);
+
+        let req = cas_request(
+            id,
+            Some(serde_json::json!({"current": 1.0, "max": 10.0})),
+            serde_json::json!({"current": 9.0, "max": 10.0}),
+        );
+        assert!(Api::apply_cas(&mut world, &req).is_err());
+
+        Api::rollback(&mut world, undo.into_iter().collect());
+        assert_eq!(world.get_component(id, "health").unwrap()["current"], 5.0);
+    }
+
+    #[test]
+    fn test_stage_gossip_write_defers_while_batch_pending() {
+        let mut pending = Some(Vec::new());
+        let staged = Api::stage_gossip_write(&mut pending, 1, "health", serde_json::json!(1.0));
+        assert!(
+            staged,
+            "an atomic batch is in progress, so the write must be staged, not published"
+        );
+        assert_eq!(
+            pending.unwrap(),
+            vec![(1, "health".to_string(), serde_json::json!(1.0))]
+        );
+    }
+
+    #[test]
+    fn test_stage_gossip_write_does_not_defer_outside_a_batch() {
+        let mut pending = None;
+        let staged = Api::stage_gossip_write(&mut pending, 1, "health", serde_json::json!(1.0));
+        assert!(
+            !staged,
+            "no atomic batch in progress, so the caller must publish immediately"
+        );
+        assert!(pending.is_none());
+    }
+
+    #[test]
+    fn test_rolled_back_batch_discards_every_staged_gossip_write() {
+        // Mirrors handle_batch's rollback path: on a failing op it drops
+        // pending_gossip entirely (`= None`) rather than flushing it, so a
+        // rolled-back batch gossips none of its staged writes to peers.
+        let mut pending = Some(Vec::new());
+        assert!(Api::stage_gossip_write(&mut pending, 1, "health", serde_json::json!(1.0)));
+        assert!(Api::stage_gossip_write(&mut pending, 2, "health", serde_json::json!(2.0)));
+        assert_eq!(pending.as_ref().unwrap().len(), 2);
+
+        pending = None;
+        assert!(pending.is_none());
+    }
 }
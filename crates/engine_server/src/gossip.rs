@@ -0,0 +1,270 @@
+//! Anti-entropy gossip between [`Api`](crate::api::Api) instances that share
+//! a NATS subject space, so several `engine-server` nodes can serve the same
+//! entity space without a central coordinator.
+//!
+//! Every component write gets a per-`(entity, component)` version, tracked
+//! by [`VersionMap`]. A write's [`GossipDelta`] is published on
+//! `{prefix}.gossip.delta`; every node applies an incoming delta only if its
+//! version is newer than what it already has (last-writer-wins, ties broken
+//! by `node_id`). Each node also periodically publishes a [`GossipDigest`]
+//! — its version map without the payloads — on `{prefix}.gossip.digest`, and
+//! a node that notices a peer's digest is ahead of it asks for the missing
+//! records with a [`GossipPull`] on `{prefix}.gossip.pull`; any node holding
+//! a newer version of a requested key just re-publishes its `gossip.delta`.
+
+use engine_ecs::EntityId;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Identifies who wrote a version and when, for last-writer-wins comparison.
+/// Ordering is by `version` first, `node_id` second, so two nodes racing on
+/// the same counter converge on the same winner instead of flapping.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct VersionStamp {
+    pub version: u64,
+    pub node_id: String,
+}
+
+/// One component write, gossiped so every node converges on it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GossipDelta {
+    pub entity_id: EntityId,
+    pub component: String,
+    pub value: Value,
+    pub version: u64,
+    pub node_id: String,
+}
+
+/// One `(entity, component)`'s highest known version, carried in a
+/// [`GossipDigest`] without its payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GossipDigestEntry {
+    pub entity_id: EntityId,
+    pub component: String,
+    pub version: u64,
+    pub node_id: String,
+}
+
+/// A node's full version map, minus payloads, published periodically so
+/// peers can spot what they're missing without shipping every value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GossipDigest {
+    pub node_id: String,
+    pub entries: Vec<GossipDigestEntry>,
+}
+
+/// Asks peers to re-publish the [`GossipDelta`] for keys this node is
+/// missing or holds a stale version of.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GossipPull {
+    pub node_id: String,
+    pub wants: Vec<(EntityId, String)>,
+}
+
+/// Tracks the highest [`VersionStamp`] this node has observed for every
+/// `(entity, component)` key, whether from a local write or a gossiped
+/// delta from a peer.
+#[derive(Debug, Default)]
+pub struct VersionMap {
+    versions: HashMap<(EntityId, String), VersionStamp>,
+}
+
+impl VersionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a local write and returns the [`VersionStamp`] to gossip for
+    /// it — one higher than whatever this node last saw for that key.
+    pub fn bump(&mut self, entity_id: EntityId, component: &str, node_id: &str) -> VersionStamp {
+        let key = (entity_id, component.to_string());
+        let version = self.versions.get(&key).map_or(0, |s| s.version) + 1;
+        let stamp = VersionStamp {
+            version,
+            node_id: node_id.to_string(),
+        };
+        self.versions.insert(key, stamp.clone());
+        stamp
+    }
+
+    /// Records an incoming stamp if it's newer than this node's current one
+    /// for that key. Returns whether the delta it came with should actually
+    /// be applied to `World` — `false` means it's stale and must be ignored
+    /// so an out-of-order delta can't clobber a newer value.
+    pub fn observe(&mut self, entity_id: EntityId, component: &str, stamp: VersionStamp) -> bool {
+        let key = (entity_id, component.to_string());
+        let is_newer = self.versions.get(&key).is_none_or(|current| stamp > *current);
+        if is_newer {
+            self.versions.insert(key, stamp);
+        }
+        is_newer
+    }
+
+    /// This node's current stamp for a key, if it has observed one.
+    pub fn stamp_of(&self, entity_id: EntityId, component: &str) -> Option<&VersionStamp> {
+        self.versions.get(&(entity_id, component.to_string()))
+    }
+
+    /// This node's current view, for publishing as a [`GossipDigest`].
+    pub fn digest(&self) -> Vec<GossipDigestEntry> {
+        self.versions
+            .iter()
+            .map(|((entity_id, component), stamp)| GossipDigestEntry {
+                entity_id: *entity_id,
+                component: component.clone(),
+                version: stamp.version,
+                node_id: stamp.node_id.clone(),
+            })
+            .collect()
+    }
+
+    /// Keys from a peer's digest that this node is missing or holds a
+    /// stale (lower-ordered) version of — what to ask for via
+    /// [`GossipPull`].
+    pub fn stale_against(&self, remote: &[GossipDigestEntry]) -> Vec<(EntityId, String)> {
+        remote
+            .iter()
+            .filter(|entry| {
+                let remote_stamp = VersionStamp {
+                    version: entry.version,
+                    node_id: entry.node_id.clone(),
+                };
+                self.versions
+                    .get(&(entry.entity_id, entry.component.clone()))
+                    .is_none_or(|local| remote_stamp > *local)
+            })
+            .map(|entry| (entry.entity_id, entry.component.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_increments_per_key() {
+        let mut versions = VersionMap::new();
+        let a = versions.bump(1, "health", "node-a");
+        let b = versions.bump(1, "health", "node-a");
+        assert_eq!(a.version, 1);
+        assert_eq!(b.version, 2);
+    }
+
+    #[test]
+    fn test_bump_tracks_keys_independently() {
+        let mut versions = VersionMap::new();
+        versions.bump(1, "health", "node-a");
+        let first_write_elsewhere = versions.bump(2, "health", "node-a");
+        assert_eq!(first_write_elsewhere.version, 1);
+    }
+
+    #[test]
+    fn test_observe_accepts_newer_version() {
+        let mut versions = VersionMap::new();
+        versions.bump(1, "health", "node-a");
+        let accepted = versions.observe(
+            1,
+            "health",
+            VersionStamp {
+                version: 5,
+                node_id: "node-b".to_string(),
+            },
+        );
+        assert!(accepted);
+    }
+
+    #[test]
+    fn test_observe_rejects_stale_version() {
+        let mut versions = VersionMap::new();
+        versions.observe(
+            1,
+            "health",
+            VersionStamp {
+                version: 5,
+                node_id: "node-a".to_string(),
+            },
+        );
+        let accepted = versions.observe(
+            1,
+            "health",
+            VersionStamp {
+                version: 3,
+                node_id: "node-b".to_string(),
+            },
+        );
+        assert!(!accepted);
+    }
+
+    #[test]
+    fn test_observe_breaks_ties_by_node_id() {
+        let mut versions = VersionMap::new();
+        versions.observe(
+            1,
+            "health",
+            VersionStamp {
+                version: 5,
+                node_id: "a".to_string(),
+            },
+        );
+        // Same version, lexically-larger node id wins the tie.
+        let accepted = versions.observe(
+            1,
+            "health",
+            VersionStamp {
+                version: 5,
+                node_id: "b".to_string(),
+            },
+        );
+        assert!(accepted);
+    }
+
+    #[test]
+    fn test_digest_reflects_all_observed_keys() {
+        let mut versions = VersionMap::new();
+        versions.bump(1, "health", "node-a");
+        versions.bump(2, "transform", "node-a");
+        let digest = versions.digest();
+        assert_eq!(digest.len(), 2);
+    }
+
+    #[test]
+    fn test_stale_against_finds_missing_and_behind_keys() {
+        let mut versions = VersionMap::new();
+        versions.bump(1, "health", "node-a"); // local version 1
+
+        let remote = vec![
+            GossipDigestEntry {
+                entity_id: 1,
+                component: "health".to_string(),
+                version: 3,
+                node_id: "node-b".to_string(),
+            },
+            GossipDigestEntry {
+                entity_id: 2,
+                component: "transform".to_string(),
+                version: 1,
+                node_id: "node-b".to_string(),
+            },
+        ];
+        let stale = versions.stale_against(&remote);
+        assert_eq!(stale.len(), 2);
+        assert!(stale.contains(&(1, "health".to_string())));
+        assert!(stale.contains(&(2, "transform".to_string())));
+    }
+
+    #[test]
+    fn test_stale_against_skips_keys_already_current() {
+        let mut versions = VersionMap::new();
+        versions.bump(1, "health", "node-a"); // local version 1
+
+        let remote = vec![GossipDigestEntry {
+            entity_id: 1,
+            component: "health".to_string(),
+            version: 1,
+            node_id: "node-a".to_string(),
+        }];
+        assert!(versions.stale_against(&remote).is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+//! Deferred structural-change command buffer for [`SystemContext`].
+//!
+//! A system only ever sees a copy of the component data it queried, shipped
+//! over NATS — it has no writable view of the world, so spawning or
+//! despawning an entity (or adding/removing a component) mid-tick would race
+//! with every other system reading or writing the same world state.
+//! [`CommandBuffer`] records these structural changes as an ordered list of
+//! typed commands instead of applying them immediately; `SystemRunner`
+//! serialises the buffer after `system_fn` returns and ships it to the
+//! coordinator, which replays the commands in recorded order once every
+//! system for the tick has acked.
+//!
+//! [`SystemContext`]: crate::context::SystemContext
+
+use engine_component::{Component, ComponentTypeId, Entity};
+use engine_net::messages::EntityCommand;
+
+/// Describes the components a new entity should be spawned with, built up
+/// via [`EntitySpawn::with`] and passed to [`CommandBuffer::spawn`].
+#[derive(Debug, Clone, Default)]
+pub struct EntitySpawn {
+    component_types: Vec<ComponentTypeId>,
+    component_data: Vec<serde_bytes::ByteBuf>,
+    component_sizes: Vec<usize>,
+}
+
+impl EntitySpawn {
+    /// Start describing a new entity with no components yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a component of type `T` to the entity being spawned.
+    ///
+    /// Silently skipped if `value` fails to serialise.
+    #[must_use]
+    pub fn with<T: Component>(mut self, value: &T) -> Self {
+        if let Ok(bytes) = rmp_serde::to_vec(value) {
+            self.component_types.push(T::component_type_id());
+            self.component_data.push(serde_bytes::ByteBuf::from(bytes));
+            self.component_sizes.push(T::meta().layout.size());
+        }
+        self
+    }
+}
+
+/// Records deferred structural changes — spawn, despawn, add-component,
+/// remove-component — for one system's tick, in the order they're recorded.
+///
+/// See the [module docs](self) for why these are deferred rather than
+/// applied directly.
+#[derive(Debug, Clone, Default)]
+pub struct CommandBuffer {
+    commands: Vec<EntityCommand>,
+}
+
+impl CommandBuffer {
+    /// Create an empty command buffer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if no commands have been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Returns the number of commands recorded so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Record a request to spawn a new entity with the given components.
+    pub fn spawn(&mut self, spawn: EntitySpawn) {
+        self.commands.push(EntityCommand::Spawn {
+            component_types: spawn.component_types,
+            component_data: spawn.component_data,
+            component_sizes: spawn.component_sizes,
+        });
+    }
+
+    /// Record a request to destroy an entity.
+    pub fn despawn(&mut self, entity: Entity) {
+        self.commands.push(EntityCommand::Despawn(entity));
+    }
+
+    /// Record a request to add a component to an existing entity.
+    ///
+    /// Silently skipped if `value` fails to serialise.
+    pub fn add_component<T: Component>(&mut self, entity: Entity, value: &T) {
+        if let Ok(bytes) = rmp_serde::to_vec(value) {
+            self.commands.push(EntityCommand::AddComponent {
+                entity,
+                component_type: T::component_type_id(),
+                data: serde_bytes::ByteBuf::from(bytes),
+                item_size: T::meta().layout.size(),
+            });
+        }
+    }
+
+    /// Record a request to remove a component from an existing entity.
+    pub fn remove_component<T: Component>(&mut self, entity: Entity) {
+        self.commands.push(EntityCommand::RemoveComponent {
+            entity,
+            component_type: T::component_type_id(),
+        });
+    }
+
+    /// Drain every recorded command, leaving the buffer empty.
+    pub(crate) fn take(&mut self) -> Vec<EntityCommand> {
+        std::mem::take(&mut self.commands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Velocity {
+        x: f32,
+    }
+    impl Component for Velocity {
+        fn type_name() -> &'static str {
+            "Velocity"
+        }
+    }
+
+    #[test]
+    fn test_empty_buffer_has_no_commands() {
+        let buf = CommandBuffer::new();
+        assert!(buf.is_empty());
+        assert_eq!(buf.len(), 0);
+    }
+
+    #[test]
+    fn test_spawn_records_component_data() {
+        let mut buf = CommandBuffer::new();
+        buf.spawn(EntitySpawn::new().with(&Velocity { x: 1.0 }));
+        assert_eq!(buf.len(), 1);
+        match &buf.commands[0] {
+            EntityCommand::Spawn {
+                component_types,
+                component_data,
+                component_sizes,
+            } => {
+                assert_eq!(component_types, &[Velocity::component_type_id()]);
+                assert_eq!(component_data.len(), 1);
+                assert_eq!(component_sizes, &[std::mem::size_of::<Velocity>()]);
+            }
+            other => panic!("expected Spawn, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_commands_preserve_recorded_order() {
+        let mut buf = CommandBuffer::new();
+        let entity = Entity(1);
+        buf.despawn(entity);
+        buf.add_component(entity, &Velocity { x: 2.0 });
+        buf.remove_component::<Velocity>(entity);
+
+        assert_eq!(buf.len(), 3);
+        assert!(matches!(buf.commands[0], EntityCommand::Despawn(_)));
+        assert!(matches!(buf.commands[1], EntityCommand::AddComponent { .. }));
+        assert!(matches!(buf.commands[2], EntityCommand::RemoveComponent { .. }));
+    }
+
+    #[test]
+    fn test_take_drains_and_clears_buffer() {
+        let mut buf = CommandBuffer::new();
+        buf.despawn(Entity(1));
+        let drained = buf.take();
+        assert_eq!(drained.len(), 1);
+        assert!(buf.is_empty());
+    }
+}
@@ -3,6 +3,8 @@
 use engine_component::QueryDescriptor;
 use engine_net::messages::ComponentSchema;
 
+use crate::telemetry::OtelConfig;
+
 /// Configuration for a system process.
 #[derive(Debug, Clone)]
 pub struct SystemConfig {
@@ -14,6 +16,12 @@ pub struct SystemConfig {
     pub nats_url: Option<String>,
     /// Component schemas this system uses (for polyglot registry).
     pub component_schemas: Vec<ComponentSchema>,
+    /// OTLP endpoint and service name to export spans and tick metrics to.
+    /// `None` disables tick metrics — `SystemRunner` still runs, it just
+    /// doesn't record [`telemetry::TickMetrics`](crate::telemetry::TickMetrics).
+    /// `SystemRunner` installs no tracer/meter provider either way; the
+    /// binary does that with [`telemetry::init`](crate::telemetry::init).
+    pub otel: Option<OtelConfig>,
 }
 
 impl SystemConfig {
@@ -25,6 +33,7 @@ impl SystemConfig {
             query,
             nats_url: None,
             component_schemas: Vec::new(),
+            otel: None,
         }
     }
 
@@ -41,4 +50,11 @@ impl SystemConfig {
         self.component_schemas = schemas;
         self
     }
+
+    /// Enable OTEL span and metric export to `config`'s OTLP endpoint.
+    #[must_use]
+    pub fn with_otel(mut self, config: OtelConfig) -> Self {
+        self.otel = Some(config);
+        self
+    }
 }
@@ -30,10 +30,20 @@
 //! }
 //! ```
 
+pub mod commands;
 pub mod config;
 pub mod context;
+pub mod multi_runner;
 pub mod runner;
+pub mod schedule;
+pub mod telemetry;
+pub mod viz;
 
+pub use commands::{CommandBuffer, EntitySpawn};
 pub use config::SystemConfig;
 pub use context::SystemContext;
+pub use multi_runner::{MultiSystemRunner, SystemFn};
 pub use runner::SystemRunner;
+pub use schedule::{Schedule, ScheduleError, ScheduledSystem, Stage, SystemId};
+pub use telemetry::OtelConfig;
+pub use viz::{conflict_graph_to_dot, to_dot};
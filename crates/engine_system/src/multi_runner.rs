@@ -0,0 +1,391 @@
+//! Multi-system runner — hosts several systems in one process, executing
+//! every non-conflicting group concurrently within a tick.
+//!
+//! [`SystemRunner`] drives exactly one system function per NATS connection.
+//! For systems tightly coupled enough that running them as separate
+//! processes is mostly connection and round-trip overhead,
+//! [`MultiSystemRunner`] instead takes several `(SystemConfig, system_fn)`
+//! pairs, builds the same [`QueryDescriptor::conflicts_with`] conflict graph
+//! the coordinator uses, and packs them into [`Stage`]s via [`Schedule`]:
+//! every system in a stage is pairwise non-conflicting, so their
+//! gather-and-execute passes can run concurrently; stages themselves still
+//! run in sequence. Systems are scheduled with no `order_after`/
+//! `order_before` edges, so stage order follows from conflicts alone. After a stage's systems finish executing (but before any of
+//! them publish), [`assert_no_duplicate_writes`] checks the one invariant
+//! the conflict graph is supposed to guarantee: no two systems in the stage
+//! wrote the same entity's same component.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use anyhow::{Result, anyhow};
+use engine_component::{ComponentTypeId, Entity};
+use engine_net::NatsConnection;
+use engine_net::messages::SystemSchedule;
+use engine_net::trace;
+use futures::StreamExt;
+use tracing::info;
+
+use crate::config::SystemConfig;
+use crate::context::SystemContext;
+use crate::runner::SystemRunner;
+use crate::schedule::{Schedule, ScheduleError, ScheduledSystem};
+
+/// A system function boxed for storage alongside its [`SystemConfig`] in a
+/// [`MultiSystemRunner`]. Unlike [`SystemRunner::run`]'s generic closure
+/// parameter, several differently-typed closures need to live in one `Vec`.
+pub type SystemFn = Box<dyn Fn(&mut SystemContext) + Send>;
+
+/// One system hosted by a [`MultiSystemRunner`].
+struct Member {
+    runner: SystemRunner,
+    system_fn: SystemFn,
+}
+
+/// Hosts several systems in one process.
+///
+/// Construct with [`MultiSystemRunner::new`], which computes the conflict-free
+/// execution stages up front, then drive the whole set with [`Self::run`].
+pub struct MultiSystemRunner {
+    /// Every hosted system, reordered so each stage (see `stage_ranges`)
+    /// occupies a contiguous range — this lets `run` take disjoint mutable
+    /// slices per stage without fighting the borrow checker over arbitrary
+    /// index sets.
+    members: Vec<Member>,
+    /// `(start, end)` index ranges into `members`, one per stage, in the
+    /// order stages must execute.
+    stage_ranges: Vec<(usize, usize)>,
+}
+
+impl MultiSystemRunner {
+    /// Build a multi-system runner from several `(SystemConfig, system_fn)`
+    /// pairs, grouping them into conflict-free stages exactly as the
+    /// coordinator's own scheduler would (see [`Schedule::build`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`ScheduleError`] from `Schedule::build`. Every system is
+    /// passed to it via `ScheduledSystem::new`, with no `order_after`/
+    /// `order_before` edges, so [`ScheduleError::OrderingCycle`] does not
+    /// arise from this constructor.
+    pub fn new(systems: Vec<(SystemConfig, SystemFn)>) -> Result<Self, ScheduleError> {
+        let scheduled: Vec<ScheduledSystem> = systems
+            .iter()
+            .map(|(config, _)| ScheduledSystem::new(config.name.clone(), config.query.clone()))
+            .collect();
+        let stages = Schedule::build(&scheduled)?;
+
+        let mut by_name: HashMap<String, (SystemConfig, SystemFn)> = systems
+            .into_iter()
+            .map(|(config, system_fn)| (config.name.clone(), (config, system_fn)))
+            .collect();
+
+        let mut members = Vec::with_capacity(by_name.len());
+        let mut stage_ranges = Vec::with_capacity(stages.len());
+        for stage in &stages {
+            let start = members.len();
+            for system_id in stage {
+                // `system_id` came from `scheduled`, built from the same
+                // `by_name` keys above, so the lookup always succeeds.
+                if let Some((config, system_fn)) = by_name.remove(&system_id.0) {
+                    members.push(Member {
+                        runner: SystemRunner::new(config),
+                        system_fn,
+                    });
+                }
+            }
+            stage_ranges.push((start, members.len()));
+        }
+
+        Ok(Self {
+            members,
+            stage_ranges,
+        })
+    }
+
+    /// Returns the number of hosted systems.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if no systems are hosted.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Returns the size of each computed stage, in execution order —
+    /// `stage_sizes().iter().sum()` equals [`Self::len`].
+    #[must_use]
+    pub fn stage_sizes(&self) -> Vec<usize> {
+        self.stage_ranges
+            .iter()
+            .map(|(start, end)| end - start)
+            .collect()
+    }
+
+    /// Run every hosted system's lifecycle on one shared NATS connection.
+    ///
+    /// Each system still registers, subscribes, and publishes on its own
+    /// subjects exactly as a standalone [`SystemRunner`] would — the
+    /// coordinator can't tell the difference. What changes is local to this
+    /// process: for each stage, this waits for every member's
+    /// [`SystemSchedule`], then runs their gather-and-execute passes
+    /// concurrently, merges the resulting output shards and checks
+    /// [`assert_no_duplicate_writes`] before any of them publish, and only
+    /// then moves on to the next stage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if NATS connection or message handling fails, or if
+    /// two systems in the same stage wrote the same entity's same component
+    /// (a conflict the stage computation is supposed to rule out).
+    pub async fn run(mut self) -> Result<()> {
+        let url = self
+            .members
+            .first()
+            .and_then(|member| member.runner.config().nats_url.clone());
+        let url = url
+            .as_deref()
+            .unwrap_or(engine_net::connection::DEFAULT_NATS_URL);
+
+        info!(systems = self.members.len(), url, "multi-system runner starting");
+        let conn = NatsConnection::connect_to(url).await?;
+
+        let mut schedule_subs = Vec::with_capacity(self.members.len());
+        let mut data_subs = Vec::with_capacity(self.members.len());
+        let mut changed_subjects = Vec::with_capacity(self.members.len());
+        for member in &self.members {
+            let name = member.runner.config().name.clone();
+            conn.publish(
+                engine_net::subjects::SYSTEM_REGISTER,
+                &member.runner.descriptor(),
+            )
+            .await?;
+            data_subs.push(
+                conn.subscribe(&engine_net::subjects::component_set(&name))
+                    .await?,
+            );
+            schedule_subs.push(
+                conn.subscribe(&engine_net::subjects::system_schedule(&name))
+                    .await?,
+            );
+            changed_subjects.push(engine_net::subjects::component_changed(&name));
+            info!(system = name, "registered and subscribed");
+        }
+
+        'ticks: loop {
+            for &(start, end) in &self.stage_ranges {
+                let schedule_msgs = futures::future::join_all(
+                    schedule_subs[start..end].iter_mut().map(StreamExt::next),
+                )
+                .await;
+
+                // A closed subscription means this instance is shutting down.
+                if schedule_msgs.iter().any(Option::is_none) {
+                    break 'ticks;
+                }
+
+                let mut schedule = None;
+                let mut parent_traces = Vec::with_capacity(end - start);
+                for msg in schedule_msgs.into_iter().flatten() {
+                    schedule = Some(engine_net::decode::<SystemSchedule>(msg.payload.as_ref())?);
+                    parent_traces.push(
+                        msg.headers
+                            .as_ref()
+                            .and_then(trace::extract)
+                            .map(|(ctx, _)| ctx),
+                    );
+                }
+                // The stage is non-empty (every range comes from a non-empty
+                // `Stage`) and every member's schedule was just confirmed
+                // `Some` above, so at least one iteration of the loop ran.
+                let schedule = schedule.ok_or_else(|| anyhow!("empty stage"))?;
+
+                let pass_start = Instant::now();
+                let exec_futs = self.members[start..end]
+                    .iter_mut()
+                    .zip(data_subs[start..end].iter_mut())
+                    .zip(parent_traces.iter())
+                    .map(|((member, data_sub), &parent_trace)| {
+                        member
+                            .runner
+                            .gather_and_execute(&schedule, data_sub, parent_trace, &*member.system_fn)
+                    });
+                let results = futures::future::join_all(exec_futs).await;
+
+                assert_no_duplicate_writes(results.iter().map(|(ctx, _)| ctx))?;
+
+                for (i, (ctx, deserialize_failures)) in results.into_iter().enumerate() {
+                    self.members[start + i]
+                        .runner
+                        .publish_pass(
+                            &conn,
+                            &schedule,
+                            &changed_subjects[start + i],
+                            parent_traces[i],
+                            &ctx,
+                            pass_start,
+                            deserialize_failures,
+                        )
+                        .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks that no two contexts in `contexts` published an output shard for
+/// the same `(Entity, ComponentTypeId)` pair.
+///
+/// [`QueryDescriptor::conflicts_with`](engine_component::QueryDescriptor::conflicts_with)
+/// guarantees two systems sharing a stage never both write a component type
+/// the other reads or writes, so this should never trip — it exists as a
+/// safety net against a bug in the conflict graph rather than an expected
+/// runtime condition.
+///
+/// # Errors
+///
+/// Returns an error naming the entity and component type written more than
+/// once within the same stage.
+fn assert_no_duplicate_writes<'a>(
+    contexts: impl Iterator<Item = &'a SystemContext>,
+) -> Result<()> {
+    let mut seen: HashSet<(Entity, ComponentTypeId)> = HashSet::new();
+    for ctx in contexts {
+        for shard in &ctx.output_shards {
+            for &entity in &shard.entities {
+                if !seen.insert((entity, shard.component_type)) {
+                    return Err(anyhow!(
+                        "conflicting writes to entity {entity:?} component {:?} within one stage",
+                        shard.component_type
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_component::QueryDescriptor;
+
+    use super::*;
+
+    fn config(name: &str, query: QueryDescriptor) -> SystemConfig {
+        SystemConfig::new(name, query)
+    }
+
+    #[test]
+    fn test_disjoint_systems_share_one_stage() {
+        let runner = MultiSystemRunner::new(vec![
+            (
+                config("physics", QueryDescriptor::new().write(ComponentTypeId(1))),
+                Box::new(|_: &mut SystemContext| {}) as SystemFn,
+            ),
+            (
+                config("ai", QueryDescriptor::new().write(ComponentTypeId(2))),
+                Box::new(|_: &mut SystemContext| {}) as SystemFn,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(runner.len(), 2);
+        assert_eq!(runner.stage_sizes(), vec![2]);
+    }
+
+    #[test]
+    fn test_conflicting_systems_split_into_stages() {
+        let runner = MultiSystemRunner::new(vec![
+            (
+                config("physics", QueryDescriptor::new().write(ComponentTypeId(1))),
+                Box::new(|_: &mut SystemContext| {}) as SystemFn,
+            ),
+            (
+                config("render", QueryDescriptor::new().read(ComponentTypeId(1))),
+                Box::new(|_: &mut SystemContext| {}) as SystemFn,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(runner.stage_sizes(), vec![1, 1]);
+    }
+
+    #[test]
+    fn test_empty_runner_has_no_stages() {
+        let runner = MultiSystemRunner::new(Vec::new()).unwrap();
+        assert!(runner.is_empty());
+        assert!(runner.stage_sizes().is_empty());
+    }
+
+    #[test]
+    fn test_assert_no_duplicate_writes_passes_for_disjoint_entities() {
+        use engine_component::Tick;
+        use engine_net::messages::ComponentShard;
+
+        let mut a = SystemContext::new(1);
+        a.output_shards.push(ComponentShard {
+            component_type: ComponentTypeId(1),
+            entities: vec![Entity::from_raw(1)],
+            data: vec![serde_bytes::ByteBuf::from(vec![0u8; 4])],
+            origin_tick: Tick::ZERO,
+            instance_id: String::new(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        });
+        let mut b = SystemContext::new(1);
+        b.output_shards.push(ComponentShard {
+            component_type: ComponentTypeId(1),
+            entities: vec![Entity::from_raw(2)],
+            data: vec![serde_bytes::ByteBuf::from(vec![0u8; 4])],
+            origin_tick: Tick::ZERO,
+            instance_id: String::new(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        });
+
+        assert!(assert_no_duplicate_writes(vec![&a, &b].into_iter()).is_ok());
+    }
+
+    #[test]
+    fn test_assert_no_duplicate_writes_catches_same_entity_same_component() {
+        use engine_component::Tick;
+        use engine_net::messages::ComponentShard;
+
+        let entity = Entity::from_raw(1);
+        let mut a = SystemContext::new(1);
+        a.output_shards.push(ComponentShard {
+            component_type: ComponentTypeId(1),
+            entities: vec![entity],
+            data: vec![serde_bytes::ByteBuf::from(vec![0u8; 4])],
+            origin_tick: Tick::ZERO,
+            instance_id: String::new(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        });
+        let mut b = SystemContext::new(1);
+        b.output_shards.push(ComponentShard {
+            component_type: ComponentTypeId(1),
+            entities: vec![entity],
+            data: vec![serde_bytes::ByteBuf::from(vec![0u8; 4])],
+            origin_tick: Tick::ZERO,
+            instance_id: String::new(),
+            changed_ticks: Vec::new(),
+            added_ticks: Vec::new(),
+            layout_version: 0,
+            producing_system: String::new(),
+        });
+
+        assert!(assert_no_duplicate_writes(vec![&a, &b].into_iter()).is_err());
+    }
+}
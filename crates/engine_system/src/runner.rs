@@ -3,20 +3,24 @@
 //! The runner handles NATS connection, registration, and the per-tick
 //! receive/execute/publish loop.
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use futures::StreamExt;
 use tracing::{debug, info};
 use uuid::Uuid;
 
+use engine_component::Tick;
 use engine_net::NatsConnection;
 use engine_net::messages::{
-    self, ChangesDone, ComponentShard, SystemDescriptor, SystemSchedule, SystemUnregister, TickAck,
+    self, ChangesDone, ComponentShard, EntityCommandBatch, SystemDescriptor, SystemSchedule,
+    SystemUnregister, TickAck,
 };
+use engine_net::trace::{self, TraceContext};
 
 use crate::config::SystemConfig;
 use crate::context::SystemContext;
+use crate::telemetry::{self, TickMetrics};
 
 /// The system runner turns a system function into a NATS-connected process.
 ///
@@ -27,6 +31,14 @@ pub struct SystemRunner {
     config: SystemConfig,
     /// Unique instance identifier for this process.
     instance_id: String,
+    /// The tick this instance last completed a pass at. `Tick::ZERO` until
+    /// the first pass completes, so that pass's `SystemContext` reports it
+    /// has never observed the world. Updated after each successful pass so
+    /// the system function can self-filter to changed-since-last-pass data
+    /// via `SystemContext::read_changed_components`.
+    last_run_tick: Tick,
+    /// Per-tick OTEL instruments, present whenever `config.otel` is set.
+    metrics: Option<TickMetrics>,
 }
 
 impl SystemRunner {
@@ -34,9 +46,15 @@ impl SystemRunner {
     #[must_use]
     pub fn new(config: SystemConfig) -> Self {
         let instance_id = Uuid::new_v4().to_string();
+        let metrics = config
+            .otel
+            .as_ref()
+            .map(|_| TickMetrics::new(&config.name));
         Self {
             config,
             instance_id,
+            last_run_tick: Tick::ZERO,
+            metrics,
         }
     }
 
@@ -66,9 +84,11 @@ impl SystemRunner {
     ///
     /// 1. Connect to NATS.
     /// 2. Publish a `system.register` message.
-    /// 3. Subscribe to the schedule and data subjects.
-    /// 4. Loop: receive data shards (until `DataDone` sentinel) → receive
-    ///    schedule → execute → publish changes → publish `ChangesDone` → ack.
+    /// 3. Subscribe to the schedule and data subjects, plus this instance's
+    ///    ad-hoc invoke subjects (for `TickLoop::run_system_by_id`).
+    /// 4. Loop: on a normal schedule or an ad-hoc invoke, receive data shards
+    ///    (until `DataDone` sentinel) → execute → publish changes → publish
+    ///    `ChangesDone` → publish this tick's entity command batch → ack.
     ///
     /// The `system_fn` is called once per tick with a [`SystemContext`]
     /// containing the received component data.
@@ -76,7 +96,7 @@ impl SystemRunner {
     /// # Errors
     ///
     /// Returns an error if NATS connection or message handling fails.
-    pub async fn run<F>(self, system_fn: F) -> Result<()>
+    pub async fn run<F>(mut self, system_fn: F) -> Result<()>
     where
         F: Fn(&mut SystemContext) + Send + 'static,
     {
@@ -116,124 +136,268 @@ impl SystemRunner {
         let mut schedule_sub = conn.subscribe(&schedule_subject).await?;
         info!(subject = schedule_subject, "subscribed to schedule");
 
-        // Main loop: wait for schedule messages.
-        while let Some(schedule_msg) = schedule_sub.next().await {
-            // Decode the schedule message.
-            let schedule: SystemSchedule = engine_net::decode(schedule_msg.payload.as_ref())?;
+        // Subscribe to this instance's ad-hoc invoke subjects — a coordinator
+        // running `TickLoop::run_system_by_id` targets these directly,
+        // bypassing the shared, broadcast-to-every-instance subjects above.
+        let invoke_data_subject = engine_net::subjects::component_invoke(&self.instance_id);
+        let mut invoke_data_sub = conn.subscribe(&invoke_data_subject).await?;
+        let invoke_subject = engine_net::subjects::system_invoke(&self.instance_id);
+        let mut invoke_sub = conn.subscribe(&invoke_subject).await?;
+        info!(subject = invoke_subject, "subscribed to ad-hoc invoke");
+
+        // Main loop: wait for a normal schedule or an ad-hoc invoke.
+        loop {
+            let schedule: SystemSchedule;
+            let changed_subject: String;
+            let parent_trace: Option<TraceContext>;
+            tokio::select! {
+                msg = schedule_sub.next() => {
+                    let Some(msg) = msg else { break };
+                    schedule = engine_net::decode(msg.payload.as_ref())?;
+                    parent_trace = msg.headers.as_ref().and_then(trace::extract).map(|(ctx, _)| ctx);
+                    changed_subject = engine_net::subjects::component_changed(&self.config.name);
+                    self.run_one_pass(&conn, &schedule, &mut data_sub, &changed_subject, parent_trace, &system_fn)
+                        .await?;
+                }
+                msg = invoke_sub.next() => {
+                    let Some(msg) = msg else { break };
+                    schedule = engine_net::decode(msg.payload.as_ref())?;
+                    parent_trace = msg.headers.as_ref().and_then(trace::extract).map(|(ctx, _)| ctx);
+                    changed_subject = engine_net::subjects::component_invoke_changed(&self.instance_id);
+                    self.run_one_pass(&conn, &schedule, &mut invoke_data_sub, &changed_subject, parent_trace, &system_fn)
+                        .await?;
+                }
+            }
+        }
 
-            debug!(
-                system = self.config.name,
-                tick_id = schedule.tick_id,
-                "schedule received"
-            );
+        // Graceful shutdown: unregister this instance from the coordinator.
+        let unreg = SystemUnregister {
+            name: self.config.name.clone(),
+            instance_id: self.instance_id.clone(),
+        };
+        conn.publish(engine_net::subjects::SYSTEM_UNREGISTER, &unreg)
+            .await?;
+        info!(
+            system = self.config.name,
+            instance_id = self.instance_id,
+            "unregistered from coordinator"
+        );
 
-            // Collect component data shards that arrived before/with the schedule.
-            // The coordinator sends all shards followed by a DataDone sentinel
-            // on `component.set.<system>`, so we drain until we see it.
-            let mut input_shards = Vec::new();
-            let data_deadline = tokio::time::Instant::now() + Duration::from_secs(5);
-            loop {
-                match tokio::time::timeout_at(data_deadline, data_sub.next()).await {
-                    Ok(Some(msg)) => {
-                        // Check if this is the DataDone sentinel.
-                        let is_done = msg
-                            .headers
-                            .as_ref()
-                            .and_then(|h| h.get(messages::headers::MSG_TYPE))
-                            .is_some_and(|v| v.as_str() == messages::DATA_DONE_MSG_TYPE);
-
-                        if is_done {
-                            break;
-                        }
-
-                        if let Ok(shard) =
-                            engine_net::decode::<ComponentShard>(msg.payload.as_ref())
-                        {
-                            input_shards.push(shard);
-                        }
-                    }
-                    Ok(None) => break, // subscriber closed
-                    Err(_) => {
-                        debug!(
-                            system = self.config.name,
-                            tick_id = schedule.tick_id,
-                            "data-done timeout — proceeding with collected shards"
-                        );
+        Ok(())
+    }
+
+    /// Run one receive/execute/publish pass for a single schedule message,
+    /// reading input data from `data_sub` and publishing changes to
+    /// `changed_subject`. Shared by the normal per-tick schedule path and the
+    /// ad-hoc `run_system_by_id` invoke path — they differ only in which
+    /// subjects carry the data.
+    async fn run_one_pass<F>(
+        &mut self,
+        conn: &NatsConnection,
+        schedule: &SystemSchedule,
+        data_sub: &mut async_nats::Subscriber,
+        changed_subject: &str,
+        parent_trace: Option<TraceContext>,
+        system_fn: &F,
+    ) -> Result<()>
+    where
+        F: Fn(&mut SystemContext) + Send + 'static,
+    {
+        let pass_start = Instant::now();
+        let (ctx, deserialize_failures) = self
+            .gather_and_execute(schedule, data_sub, parent_trace, system_fn)
+            .await;
+        self.publish_pass(conn, schedule, changed_subject, parent_trace, &ctx, pass_start, deserialize_failures)
+            .await
+    }
+
+    /// Drain `data_sub` for component shards until its `DataDone` sentinel
+    /// (or a 5s timeout), then run `system_fn` against them inside the
+    /// per-tick trace span.
+    ///
+    /// Split out of [`run_one_pass`](Self::run_one_pass) so
+    /// [`crate::multi_runner::MultiSystemRunner`] can run several systems'
+    /// gather-and-execute steps concurrently (joined on one task) before any of
+    /// them publish, so their outputs can be merged and checked for
+    /// conflicts before anything goes over the wire.
+    pub(crate) async fn gather_and_execute(
+        &mut self,
+        schedule: &SystemSchedule,
+        data_sub: &mut async_nats::Subscriber,
+        parent_trace: Option<TraceContext>,
+        system_fn: &(dyn Fn(&mut SystemContext) + Send),
+    ) -> (SystemContext, u64) {
+        let tick_span = telemetry::tick_span(&self.config.name, schedule.tick_id, parent_trace);
+        let mut deserialize_failures = 0u64;
+
+        debug!(
+            system = self.config.name,
+            tick_id = schedule.tick_id,
+            "schedule received"
+        );
+
+        // Collect component data shards that arrived before/with the schedule.
+        // The coordinator sends all shards followed by a DataDone sentinel,
+        // so we drain until we see it.
+        let mut input_shards = Vec::new();
+        let data_deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        loop {
+            match tokio::time::timeout_at(data_deadline, data_sub.next()).await {
+                Ok(Some(msg)) => {
+                    // Check if this is the DataDone sentinel.
+                    let is_done = msg
+                        .headers
+                        .as_ref()
+                        .and_then(|h| h.get(messages::headers::MSG_TYPE))
+                        .is_some_and(|v| v.as_str() == messages::DATA_DONE_MSG_TYPE);
+
+                    if is_done {
                         break;
                     }
+
+                    match engine_net::decode::<ComponentShard>(msg.payload.as_ref()) {
+                        Ok(shard) => input_shards.push(shard),
+                        Err(_) => deserialize_failures += 1,
+                    }
+                }
+                Ok(None) => break, // subscriber closed
+                Err(_) => {
+                    debug!(
+                        system = self.config.name,
+                        tick_id = schedule.tick_id,
+                        "data-done timeout — proceeding with collected shards"
+                    );
+                    break;
                 }
             }
+        }
 
-            debug!(
-                system = self.config.name,
-                tick_id = schedule.tick_id,
-                shards = input_shards.len(),
-                "data shards collected"
-            );
+        debug!(
+            system = self.config.name,
+            tick_id = schedule.tick_id,
+            shards = input_shards.len(),
+            "data shards collected"
+        );
 
-            // Create context for this tick.
-            let mut ctx = SystemContext::new(schedule.tick_id);
-            ctx.input_shards = input_shards;
+        // Create context for this tick.
+        let mut ctx = SystemContext::new(schedule.tick_id);
+        ctx.input_shards = input_shards;
+        ctx.last_run_tick = self.last_run_tick;
 
-            // Execute the system function.
-            system_fn(&mut ctx);
+        // Execute the system function inside the per-tick span, linked to
+        // the coordinator's trace for this tick, so a tracing backend can
+        // attribute exactly this duration to this instance's pass.
+        tick_span.in_scope(|| system_fn(&mut ctx));
+        self.last_run_tick = Tick(schedule.tick_id as u32);
 
-            // Publish changed component data.
-            let changed_subject = engine_net::subjects::component_changed(&self.config.name);
-            for shard in &ctx.output_shards {
-                conn.publish(&changed_subject, shard).await?;
-            }
+        (ctx, deserialize_failures)
+    }
 
-            // Publish end-of-changes sentinel so the coordinator knows all
-            // changed data for this tick has been sent and can stop waiting.
-            let changes_done = ChangesDone {
-                tick_id: schedule.tick_id,
-                instance_id: self.instance_id.clone(),
-            };
+    /// Publish the output of an already-executed pass: changed component
+    /// shards, the `ChangesDone` sentinel, this tick's entity command batch,
+    /// and the final `TickAck`. Counterpart to
+    /// [`gather_and_execute`](Self::gather_and_execute).
+    pub(crate) async fn publish_pass(
+        &self,
+        conn: &NatsConnection,
+        schedule: &SystemSchedule,
+        changed_subject: &str,
+        parent_trace: Option<TraceContext>,
+        ctx: &SystemContext,
+        pass_start: Instant,
+        deserialize_failures: u64,
+    ) -> Result<()> {
+        // Continue the coordinator's trace for this tick with a child span
+        // covering this instance's pass, falling back to a fresh root if the
+        // schedule carried no (or an unparseable) traceparent.
+        let span_trace = parent_trace.map_or_else(TraceContext::new_root, TraceContext::child);
+
+        // Publish changed component data, stamped with this instance's ID,
+        // the producing system, and the tick it was written at, so the
+        // coordinator can resolve conflicting writes by last-writer-wins and
+        // assemble a per-tick causal DAG from the dependency list.
+        for (i, shard) in ctx.output_shards.iter().enumerate() {
+            let mut shard = shard.clone();
+            shard.origin_tick = Tick(schedule.tick_id as u32);
+            shard.instance_id = self.instance_id.clone();
+            shard.producing_system = self.config.name.clone();
             let mut headers = async_nats::HeaderMap::new();
             headers.insert(
-                engine_net::messages::headers::MSG_TYPE,
-                engine_net::messages::CHANGES_DONE_MSG_TYPE,
+                engine_net::messages::headers::SCHEMA_VERSION,
+                shard.layout_version.to_string(),
             );
-            conn.publish_with_headers(&changed_subject, headers, &changes_done)
-                .await?;
-
-            // Publish any entity spawn requests to the coordinator.
-            for req in &ctx.spawn_requests {
-                conn.publish(engine_net::subjects::ENTITY_SPAWN_REQUEST, req)
-                    .await?;
+            if let Some(dependencies) = ctx.output_dependencies.get(i) {
+                messages::inject_dependencies(&mut headers, dependencies);
             }
-
-            // Ack tick completion.
-            let ack = TickAck {
-                tick_id: schedule.tick_id,
-                instance_id: self.instance_id.clone(),
-            };
-            conn.publish(engine_net::subjects::COORD_TICK_DONE, &ack)
+            conn.publish_with_headers(changed_subject, headers, &shard)
                 .await?;
-
-            debug!(
-                system = self.config.name,
-                tick_id = schedule.tick_id,
-                "tick acked"
-            );
         }
 
-        // Graceful shutdown: unregister this instance from the coordinator.
-        let unreg = SystemUnregister {
-            name: self.config.name.clone(),
+        // Publish end-of-changes sentinel so the coordinator knows all
+        // changed data for this tick has been sent and can stop waiting.
+        let changes_done = ChangesDone {
+            tick_id: schedule.tick_id,
             instance_id: self.instance_id.clone(),
         };
-        conn.publish(engine_net::subjects::SYSTEM_UNREGISTER, &unreg)
+        let mut headers = async_nats::HeaderMap::new();
+        headers.insert(
+            engine_net::messages::headers::MSG_TYPE,
+            engine_net::messages::CHANGES_DONE_MSG_TYPE,
+        );
+        trace::inject(&mut headers, &span_trace, None);
+        conn.publish_with_headers(changed_subject, headers, &changes_done)
             .await?;
-        info!(
+
+        // Publish this tick's deferred structural changes (entity spawns,
+        // despawns, add/remove component), always — even an empty batch —
+        // so the coordinator can count exactly one message per instance per
+        // stage rather than needing a sentinel to know when to stop waiting.
+        let command_batch = EntityCommandBatch {
+            tick_id: schedule.tick_id,
+            system: self.config.name.clone(),
+            commands: ctx.commands.clone().take(),
+        };
+        let commands_subject = engine_net::subjects::entity_commands(&self.config.name);
+        conn.publish(&commands_subject, &command_batch).await?;
+
+        // Ack tick completion, carrying the same trace so the coordinator
+        // can close out this instance's span for the tick.
+        let ack = TickAck {
+            tick_id: schedule.tick_id,
+            instance_id: self.instance_id.clone(),
+        };
+        let mut ack_headers = async_nats::HeaderMap::new();
+        trace::inject(&mut ack_headers, &span_trace, None);
+        conn.publish_with_headers(engine_net::subjects::COORD_TICK_DONE, ack_headers, &ack)
+            .await?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_tick(
+                pass_start.elapsed(),
+                ctx.input_shards.len(),
+                ctx.output_shards.len(),
+            );
+            for _ in 0..deserialize_failures {
+                metrics.record_deserialize_failure();
+            }
+        }
+
+        debug!(
             system = self.config.name,
-            instance_id = self.instance_id,
-            "unregistered from coordinator"
+            tick_id = schedule.tick_id,
+            "tick acked"
         );
 
         Ok(())
     }
+
+    /// Returns this runner's [`SystemConfig`], e.g. for
+    /// [`crate::multi_runner::MultiSystemRunner`] to read the query back out
+    /// after handing ownership of the runner to it.
+    #[must_use]
+    pub(crate) fn config(&self) -> &SystemConfig {
+        &self.config
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,291 @@
+//! Parallel stage scheduler — packs systems into conflict-free stages.
+//!
+//! The coordinator's `engine_app` crate already groups its own registered
+//! systems into stages via a bitmask conflict graph. This module solves the
+//! same problem from the system side of the network boundary, where all a
+//! caller has is a [`SystemId`] and the [`QueryDescriptor`] it registered
+//! with — no coordinator state, just enough to mirror how legion/bevy
+//! derive disjoint system sets locally (e.g. for `to_dot` visualisation or
+//! local dry-run tooling before a real registration round-trip).
+
+use std::collections::HashMap;
+
+use engine_component::{pack_into_stages, QueryDescriptor};
+use thiserror::Error;
+
+/// A system's stable name, used as the scheduling unit and for
+/// `order_after`/`order_before` edges.
+///
+/// Distinct from `engine_app::registry::SystemId` (a coordinator-minted
+/// handle for ad-hoc one-shot invocations) — this one is the caller-assigned
+/// name a system registers under, matching the IDL's `order_after`/
+/// `order_before` identifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SystemId(pub String);
+
+impl SystemId {
+    /// Create a system id from a name.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+impl From<&str> for SystemId {
+    fn from(name: &str) -> Self {
+        Self(name.to_string())
+    }
+}
+
+impl From<String> for SystemId {
+    fn from(name: String) -> Self {
+        Self(name)
+    }
+}
+
+/// A system plus the metadata the scheduler needs: its data access (for
+/// conflict detection) and its ordering edges (for stage placement).
+#[derive(Debug, Clone)]
+pub struct ScheduledSystem {
+    /// The system's stable id.
+    pub id: SystemId,
+    /// The system's data access requirements.
+    pub query: QueryDescriptor,
+    /// Ids of systems that must run in a strictly earlier stage than this
+    /// one (`order_after` in the IDL: this system runs after them).
+    pub order_after: Vec<SystemId>,
+    /// Ids of systems that must run in a strictly later stage than this one
+    /// (`order_before` in the IDL: this system runs before them).
+    pub order_before: Vec<SystemId>,
+}
+
+impl ScheduledSystem {
+    /// Create a system with no ordering constraints.
+    #[must_use]
+    pub fn new(id: impl Into<SystemId>, query: QueryDescriptor) -> Self {
+        Self {
+            id: id.into(),
+            query,
+            order_after: Vec::new(),
+            order_before: Vec::new(),
+        }
+    }
+
+    /// Require this system to run no earlier than the named systems.
+    #[must_use]
+    pub fn order_after(mut self, ids: impl IntoIterator<Item = impl Into<SystemId>>) -> Self {
+        self.order_after.extend(ids.into_iter().map(Into::into));
+        self
+    }
+
+    /// Require this system to run no later than the named systems.
+    #[must_use]
+    pub fn order_before(mut self, ids: impl IntoIterator<Item = impl Into<SystemId>>) -> Self {
+        self.order_before.extend(ids.into_iter().map(Into::into));
+        self
+    }
+}
+
+/// A group of systems with no conflicts between them, safe to run in
+/// parallel. Stages execute sequentially in the order [`Schedule::build`]
+/// returns them.
+pub type Stage = Vec<SystemId>;
+
+/// Errors produced while computing execution stages.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// The `order_after`/`order_before` constraints form a cycle, so no
+    /// valid topological ordering exists.
+    #[error("ordering cycle detected among systems: {0:?}")]
+    OrderingCycle(Vec<SystemId>),
+}
+
+/// Builds parallel execution stages from a set of systems.
+pub struct Schedule;
+
+impl Schedule {
+    /// Packs `systems` into stages such that no two systems sharing a stage
+    /// conflict (per [`QueryDescriptor::conflicts_with`]).
+    ///
+    /// Delegates the graph-plus-packing algorithm to
+    /// [`engine_component::pack_into_stages`], using the growing list of
+    /// system indices already placed in a stage as the per-stage
+    /// accumulator. That shared algorithm re-derives each system's floor
+    /// from where its `order_after`/`order_before` predecessors *actually*
+    /// landed, not just their topological depth, so a predecessor pushed
+    /// later by an unrelated conflict still forces its successors later
+    /// too.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScheduleError::OrderingCycle`] if `order_after`/
+    /// `order_before` constraints among `systems` are unsatisfiable.
+    pub fn build(systems: &[ScheduledSystem]) -> Result<Vec<Stage>, ScheduleError> {
+        if systems.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let edges = order_edges(systems);
+
+        let stages = pack_into_stages::<Vec<usize>>(
+            systems.len(),
+            &edges,
+            |stage_members, sys_idx| {
+                stage_members.iter().any(|&other_idx| {
+                    systems[sys_idx].query.conflicts_with(&systems[other_idx].query)
+                })
+            },
+            |stage_members, sys_idx| stage_members.push(sys_idx),
+        )
+        .map_err(|cyclic| {
+            ScheduleError::OrderingCycle(cyclic.into_iter().map(|i| systems[i].id.clone()).collect())
+        })?;
+
+        Ok(stages
+            .into_iter()
+            .map(|stage| stage.into_iter().map(|idx| systems[idx].id.clone()).collect())
+            .collect())
+    }
+}
+
+/// Builds the `a -> b` dependency edges (`b.order_after` names `a`, or
+/// `a.order_before` names `b`) that [`engine_component::pack_into_stages`]
+/// treats as "`b` must run no earlier than one past `a`'s actual stage".
+fn order_edges(systems: &[ScheduledSystem]) -> Vec<(usize, usize)> {
+    let index_of: HashMap<&SystemId, usize> = systems
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (&s.id, i))
+        .collect();
+
+    let mut edges = Vec::new();
+    for (i, system) in systems.iter().enumerate() {
+        for pred_id in &system.order_after {
+            if let Some(&pred_idx) = index_of.get(pred_id) {
+                edges.push((pred_idx, i));
+            }
+        }
+        for succ_id in &system.order_before {
+            if let Some(&succ_idx) = index_of.get(succ_id) {
+                edges.push((i, succ_idx));
+            }
+        }
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_component::ComponentTypeId;
+
+    use super::*;
+
+    fn query(reads: &[u64], writes: &[u64]) -> QueryDescriptor {
+        let mut q = QueryDescriptor::new();
+        for &r in reads {
+            q = q.read(ComponentTypeId(r));
+        }
+        for &w in writes {
+            q = q.write(ComponentTypeId(w));
+        }
+        q
+    }
+
+    #[test]
+    fn test_disjoint_systems_share_one_stage() {
+        let systems = vec![
+            ScheduledSystem::new("physics", query(&[], &[1])),
+            ScheduledSystem::new("ai", query(&[], &[2])),
+        ];
+        let stages = Schedule::build(&systems).unwrap();
+        assert_eq!(stages.len(), 1);
+        assert_eq!(stages[0].len(), 2);
+    }
+
+    #[test]
+    fn test_conflicting_writes_split_into_stages() {
+        let systems = vec![
+            ScheduledSystem::new("physics", query(&[], &[1])),
+            ScheduledSystem::new("render", query(&[1], &[])),
+        ];
+        let stages = Schedule::build(&systems).unwrap();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0], vec![SystemId::new("physics")]);
+        assert_eq!(stages[1], vec![SystemId::new("render")]);
+    }
+
+    #[test]
+    fn test_order_after_forces_later_stage_even_without_conflict() {
+        let systems = vec![
+            ScheduledSystem::new("physics", query(&[], &[1])),
+            ScheduledSystem::new("ai", query(&[], &[2]))
+                .order_after(["physics"]),
+        ];
+        let stages = Schedule::build(&systems).unwrap();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0], vec![SystemId::new("physics")]);
+        assert_eq!(stages[1], vec![SystemId::new("ai")]);
+    }
+
+    #[test]
+    fn test_order_before_mirrors_order_after() {
+        let systems = vec![
+            ScheduledSystem::new("physics", query(&[], &[1]))
+                .order_before(["render"]),
+            ScheduledSystem::new("render", query(&[1], &[])),
+        ];
+        let stages = Schedule::build(&systems).unwrap();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0], vec![SystemId::new("physics")]);
+        assert_eq!(stages[1], vec![SystemId::new("render")]);
+    }
+
+    #[test]
+    fn test_ordering_cycle_is_an_error() {
+        let systems = vec![
+            ScheduledSystem::new("a", query(&[], &[1])).order_after(["b"]),
+            ScheduledSystem::new("b", query(&[], &[2])).order_after(["a"]),
+        ];
+        let err = Schedule::build(&systems).unwrap_err();
+        match err {
+            ScheduleError::OrderingCycle(mut names) => {
+                names.sort();
+                assert_eq!(names, vec![SystemId::new("a"), SystemId::new("b")]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_system_set_yields_no_stages() {
+        assert_eq!(Schedule::build(&[]).unwrap(), Vec::<Stage>::new());
+    }
+
+    #[test]
+    fn test_order_after_predecessor_pushed_later_by_unrelated_conflict_still_orders_successor() {
+        // "z" and "a" both write component 1, so they can't share a stage —
+        // "a" gets pushed into stage 1 even though it has no ordering
+        // constraints of its own (topological depth 0). "c" has no
+        // component conflict with anyone but declares order_after("a"), so
+        // it must land strictly after "a"'s *actual* stage (1), not "a"'s
+        // topological depth (0).
+        let systems = vec![
+            ScheduledSystem::new("z", query(&[], &[1])),
+            ScheduledSystem::new("a", query(&[], &[1])),
+            ScheduledSystem::new("c", query(&[], &[2])).order_after(["a"]),
+        ];
+        let stages = Schedule::build(&systems).unwrap();
+
+        let stage_of = |id: &str| {
+            stages
+                .iter()
+                .position(|s| s.contains(&SystemId::new(id)))
+                .unwrap()
+        };
+        assert_eq!(stage_of("z"), 0);
+        assert_eq!(stage_of("a"), 1, "a conflicts with z, so must move to stage 1");
+        assert_eq!(
+            stage_of("c"), 2,
+            "c must land strictly after a's actual stage (1), not a's topological depth (0)"
+        );
+    }
+}
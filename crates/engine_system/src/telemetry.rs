@@ -0,0 +1,167 @@
+//! OpenTelemetry span and metric export for system processes.
+//!
+//! [`SystemRunner::run`](crate::SystemRunner::run) already carries a W3C
+//! trace context on the NATS headers of every hop (see
+//! `engine_net::trace`), so one tick's coordinator → system → coordinator
+//! fan-out is already linkable in principle. This module turns that into an
+//! actual exported trace: [`init`] installs an OTLP tracer/meter pair and
+//! returns a `tracing` layer the binary adds alongside its usual `fmt`
+//! layer, and [`TickMetrics`] records the per-tick numbers an operator
+//! needs to see which instance stalled a tick (wall time, shard counts,
+//! deserialize failures).
+
+use std::time::Duration;
+
+use anyhow::Result;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Histogram};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::Registry;
+
+use engine_net::trace::TraceContext;
+
+/// Where and as whom this process should export OTEL spans and metrics.
+#[derive(Debug, Clone)]
+pub struct OtelConfig {
+    /// Service name attached to every exported span and metric, e.g.
+    /// `"physics"` for the system registered under that name.
+    pub service_name: String,
+    /// OTLP gRPC collector endpoint, e.g. `"http://localhost:4317"`.
+    pub otlp_endpoint: String,
+}
+
+impl OtelConfig {
+    /// Build a config pointing at `otlp_endpoint` under `service_name`.
+    #[must_use]
+    pub fn new(service_name: impl Into<String>, otlp_endpoint: impl Into<String>) -> Self {
+        Self {
+            service_name: service_name.into(),
+            otlp_endpoint: otlp_endpoint.into(),
+        }
+    }
+}
+
+/// Installs a global OTLP tracer provider and meter provider for `config`,
+/// and returns the `tracing` layer that records spans into the tracer. The
+/// caller composes it with their own `fmt` layer, e.g.:
+///
+/// ```rust,ignore
+/// use tracing_subscriber::layer::SubscriberExt;
+/// let otel_layer = engine_system::telemetry::init(&config.otel.unwrap())?;
+/// tracing_subscriber::registry()
+///     .with(tracing_subscriber::fmt::layer())
+///     .with(otel_layer)
+///     .init();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the OTLP exporter can't be built (e.g. an invalid
+/// `otlp_endpoint`).
+pub fn init(
+    config: &OtelConfig,
+) -> Result<OpenTelemetryLayer<Registry, opentelemetry_sdk::trace::Tracer>> {
+    let resource = opentelemetry_sdk::Resource::new(vec![KeyValue::new(
+        "service.name",
+        config.service_name.clone(),
+    )]);
+
+    let tracer_provider = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(&config.otlp_endpoint),
+        )
+        .with_trace_config(opentelemetry_sdk::trace::config().with_resource(resource.clone()))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+    let tracer = tracer_provider.tracer("engine_system");
+    opentelemetry::global::set_tracer_provider(tracer_provider);
+
+    let meter_provider = opentelemetry_otlp::new_pipeline()
+        .metrics(opentelemetry_sdk::runtime::Tokio)
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(&config.otlp_endpoint),
+        )
+        .with_resource(resource)
+        .build()?;
+    opentelemetry::global::set_meter_provider(meter_provider);
+
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Per-tick counters and a duration histogram for one system instance,
+/// backed by the global OTEL meter [`init`] installed.
+#[derive(Debug, Clone)]
+pub struct TickMetrics {
+    tick_duration: Histogram<f64>,
+    input_shards: Counter<u64>,
+    output_shards: Counter<u64>,
+    deserialize_failures: Counter<u64>,
+}
+
+impl TickMetrics {
+    /// Create the instrument set for `system_name`, read off the global
+    /// meter provider (a no-op provider if [`init`] was never called, so
+    /// constructing this is safe even when OTEL export is disabled).
+    #[must_use]
+    pub fn new(system_name: &str) -> Self {
+        let meter = opentelemetry::global::meter(system_name.to_string());
+        Self {
+            tick_duration: meter
+                .f64_histogram("engine.system.tick_duration_seconds")
+                .with_description("Wall time of one system pass")
+                .init(),
+            input_shards: meter
+                .u64_counter("engine.system.input_shards")
+                .with_description("Component shards received per pass")
+                .init(),
+            output_shards: meter
+                .u64_counter("engine.system.output_shards")
+                .with_description("Component shards published per pass")
+                .init(),
+            deserialize_failures: meter
+                .u64_counter("engine.system.deserialize_failures")
+                .with_description("Component shards that failed to decode")
+                .init(),
+        }
+    }
+
+    /// Record one completed pass: its wall time, and how many shards were
+    /// received and published.
+    pub fn record_tick(&self, duration: Duration, input_shards: usize, output_shards: usize) {
+        self.tick_duration.record(duration.as_secs_f64(), &[]);
+        self.input_shards.add(input_shards as u64, &[]);
+        self.output_shards.add(output_shards as u64, &[]);
+    }
+
+    /// Record one component shard that failed to deserialize.
+    pub fn record_deserialize_failure(&self) {
+        self.deserialize_failures.add(1, &[]);
+    }
+}
+
+/// Starts the per-tick execution span, linked as a child of `parent_trace`
+/// (the context extracted from the incoming schedule's NATS headers) if
+/// present, so a tracing backend can reconstruct the whole distributed tick
+/// from the coordinator's root span down through every system instance.
+pub fn tick_span(
+    system_name: &str,
+    tick_id: u64,
+    parent_trace: Option<TraceContext>,
+) -> tracing::Span {
+    use opentelemetry::trace::TraceContextExt;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let span = tracing::info_span!("system.tick", system = system_name, tick_id);
+    if let Some(parent_trace) = parent_trace {
+        let parent_cx = opentelemetry::Context::new()
+            .with_remote_span_context(parent_trace.to_otel_span_context());
+        span.set_parent(parent_cx);
+    }
+    span
+}
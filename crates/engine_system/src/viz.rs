@@ -0,0 +1,152 @@
+//! Graphviz DOT export of the system/component access graph.
+//!
+//! Two rendering modes, both over the same [`ScheduledSystem`] list used by
+//! [`crate::schedule`]:
+//!
+//! - [`to_dot`] renders the raw access graph: one node per system, one node
+//!   per `ComponentTypeId`, with directed edges for reads/writes/optionals.
+//! - [`conflict_graph_to_dot`] instead draws an edge between any two systems
+//!   [`QueryDescriptor::conflicts_with`] reports as conflicting, which is
+//!   the more useful view when debugging why two systems landed in
+//!   different [`crate::schedule::Stage`]s.
+//!
+//! Either output is a `String` of valid DOT a caller can pipe into
+//! `dot -Tsvg`.
+
+use std::collections::BTreeSet;
+
+use crate::schedule::ScheduledSystem;
+
+/// Escapes `"` and `\` so a string is safe to embed in a quoted DOT label.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Renders the component access graph for `systems` as a Graphviz `digraph`.
+///
+/// Edges: component -> system for reads (blue, solid), system -> component
+/// for writes (red, solid), system -> component for optionals (gray,
+/// dashed).
+#[must_use]
+pub fn to_dot(systems: &[ScheduledSystem]) -> String {
+    let mut out = String::from("digraph access {\n    rankdir=LR;\n");
+
+    for system in systems {
+        let label = escape(&system.id.0);
+        out.push_str(&format!(
+            "    \"sys:{label}\" [shape=box, label=\"{label}\"];\n"
+        ));
+    }
+
+    let mut components = BTreeSet::new();
+    for system in systems {
+        components.extend(system.query.all_accessed_types());
+    }
+    for ty in &components {
+        out.push_str(&format!(
+            "    \"comp:{0}\" [shape=ellipse, label=\"{0}\"];\n",
+            ty.0
+        ));
+    }
+
+    for system in systems {
+        let label = escape(&system.id.0);
+        for r in &system.query.reads {
+            out.push_str(&format!(
+                "    \"comp:{}\" -> \"sys:{label}\" [color=blue];\n",
+                r.0
+            ));
+        }
+        for w in &system.query.writes {
+            out.push_str(&format!(
+                "    \"sys:{label}\" -> \"comp:{}\" [color=red];\n",
+                w.0
+            ));
+        }
+        for o in &system.query.optionals {
+            out.push_str(&format!(
+                "    \"sys:{label}\" -> \"comp:{}\" [color=gray, style=dashed];\n",
+                o.0
+            ));
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Renders the conflict graph for `systems` as an undirected Graphviz
+/// `graph`, with an edge between every pair `QueryDescriptor::conflicts_with`
+/// reports as conflicting.
+#[must_use]
+pub fn conflict_graph_to_dot(systems: &[ScheduledSystem]) -> String {
+    let mut out = String::from("graph conflicts {\n");
+
+    for system in systems {
+        out.push_str(&format!("    \"{}\";\n", escape(&system.id.0)));
+    }
+
+    for i in 0..systems.len() {
+        for j in (i + 1)..systems.len() {
+            if systems[i].query.conflicts_with(&systems[j].query) {
+                out.push_str(&format!(
+                    "    \"{}\" -- \"{}\" [color=red];\n",
+                    escape(&systems[i].id.0),
+                    escape(&systems[j].id.0)
+                ));
+            }
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_component::{ComponentTypeId, QueryDescriptor};
+
+    use super::*;
+
+    #[test]
+    fn test_to_dot_emits_nodes_and_typed_edges() {
+        let systems = vec![ScheduledSystem::new(
+            "physics",
+            QueryDescriptor::new()
+                .read(ComponentTypeId(1))
+                .write(ComponentTypeId(2))
+                .optional(ComponentTypeId(3)),
+        )];
+        let dot = to_dot(&systems);
+
+        assert!(dot.starts_with("digraph access {"));
+        assert!(dot.contains("\"sys:physics\""));
+        assert!(dot.contains("\"comp:1\" -> \"sys:physics\" [color=blue];"));
+        assert!(dot.contains("\"sys:physics\" -> \"comp:2\" [color=red];"));
+        assert!(dot.contains("\"sys:physics\" -> \"comp:3\" [color=gray, style=dashed];"));
+    }
+
+    #[test]
+    fn test_conflict_graph_edges_only_conflicting_pairs() {
+        let systems = vec![
+            ScheduledSystem::new("physics", QueryDescriptor::new().write(ComponentTypeId(1))),
+            ScheduledSystem::new("render", QueryDescriptor::new().read(ComponentTypeId(1))),
+            ScheduledSystem::new("ai", QueryDescriptor::new().write(ComponentTypeId(2))),
+        ];
+        let dot = conflict_graph_to_dot(&systems);
+
+        assert!(dot.contains("\"physics\" -- \"render\""));
+        assert!(!dot.contains("\"physics\" -- \"ai\""));
+        assert!(!dot.contains("\"render\" -- \"ai\""));
+    }
+
+    #[test]
+    fn test_quotes_in_system_name_are_escaped() {
+        let systems = vec![ScheduledSystem::new(
+            "weird\"name",
+            QueryDescriptor::new(),
+        )];
+        let dot = to_dot(&systems);
+        assert!(dot.contains("weird\\\"name"));
+    }
+}
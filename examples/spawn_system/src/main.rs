@@ -1,10 +1,12 @@
 //! Spawn system — creates an entity with a Velocity component.
 //!
 //! This system demonstrates how to request entity creation from the
-//! coordinator. On its first tick it publishes an [`EntitySpawnRequest`]
-//! containing a [`Velocity`] component. The coordinator allocates the
-//! entity and places it into the appropriate archetype so other systems
-//! (e.g. `accelerate_system`, `print_velocity_system`) can operate on it.
+//! coordinator. On its first tick it records a spawn command on the
+//! context's command buffer with a [`Velocity`] component. The coordinator
+//! replays the command after every system for the tick has acked, allocating
+//! the entity and placing it into the appropriate archetype so other
+//! systems (e.g. `accelerate_system`, `print_velocity_system`) can operate
+//! on it.
 
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -14,8 +16,7 @@ use tracing_subscriber::EnvFilter;
 
 use components::Velocity;
 use engine_component::{Component, QueryDescriptor};
-use engine_net::messages::EntitySpawnRequest;
-use engine_system::{SystemConfig, SystemRunner};
+use engine_system::{EntitySpawn, SystemConfig, SystemRunner};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -37,19 +38,11 @@ async fn main() -> Result<()> {
     runner
         .run(move |ctx| {
             if !spawned.load(Ordering::Relaxed) {
-                // Build the Velocity component to attach to the new entity.
+                // Record a spawn command with the Velocity component to
+                // attach to the new entity. The runner ships the command
+                // buffer to the coordinator after this closure returns.
                 let velocity = Velocity::new(1.0, 0.0, 0.0);
-                let vel_bytes =
-                    engine_net::encode(&velocity).expect("failed to serialise Velocity");
-
-                let request = EntitySpawnRequest {
-                    component_types: vec![Velocity::component_type_id()],
-                    component_data: vec![vel_bytes],
-                };
-
-                // Queue the spawn request — the system context gives us
-                // access to publish it via output.
-                ctx.spawn_requests.push(request);
+                ctx.commands.spawn(EntitySpawn::new().with(&velocity));
 
                 info!(
                     tick = ctx.tick_id,